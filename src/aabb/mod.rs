@@ -0,0 +1,194 @@
+//! This module defines a struct representing an Axis-Aligned Bounding Box
+
+use super::ray::Ray;
+use super::vector3d::Vector3;
+
+/// The struct representing an Axis-Aligned Bounding Box
+///
+/// Contains a Vector3 for the corner with the smallest components, and a
+/// Vector3 for the corner with the largest components
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Aabb {
+    pub min: Vector3,
+    pub max: Vector3,
+}
+
+impl Aabb {
+    /// Creates a new bounding box with the given minimum and maximum corners
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let min = ray_tracer::vector3d::Vector3::new(-1.0, -2.0, -3.0);
+    /// let max = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0);
+    /// let aabb = ray_tracer::aabb::Aabb::new(min, max);
+    ///
+    /// assert_eq!(aabb.min.y, -2.0);
+    /// assert_eq!(aabb.max.z, 3.0);
+    /// ```
+    pub fn new(min: Vector3, max: Vector3) -> Aabb {
+        Aabb { min, max }
+    }
+
+    /// Returns the smallest bounding box containing all of the given points
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let points = [
+    ///     ray_tracer::vector3d::Vector3::new(1.0, -1.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(-2.0, 3.0, 1.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -4.0),
+    /// ];
+    /// let aabb = ray_tracer::aabb::Aabb::from_points(&points);
+    ///
+    /// assert_eq!(aabb.min, ray_tracer::vector3d::Vector3::new(-2.0, -1.0, -4.0));
+    /// assert_eq!(aabb.max, ray_tracer::vector3d::Vector3::new(1.0, 3.0, 1.0));
+    /// ```
+    pub fn from_points(points: &[Vector3]) -> Aabb {
+        let infinity = f64::INFINITY;
+        let mut min = Vector3::new(infinity, infinity, infinity);
+        let mut max = Vector3::new(-infinity, -infinity, -infinity);
+        for p in points {
+            min = Vector3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+            max = Vector3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+        }
+        Aabb { min, max }
+    }
+
+    /// Returns the smallest bounding box containing both this box and the given box
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let aabb1 = ray_tracer::aabb::Aabb::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(1.0, 1.0, 1.0),
+    /// );
+    /// let aabb2 = ray_tracer::aabb::Aabb::new(
+    ///     ray_tracer::vector3d::Vector3::new(-1.0, 0.5, 0.5),
+    ///     ray_tracer::vector3d::Vector3::new(0.5, 2.0, 0.5),
+    /// );
+    /// let union = aabb1.union(&aabb2);
+    ///
+    /// assert_eq!(union.min, ray_tracer::vector3d::Vector3::new(-1.0, 0.0, 0.0));
+    /// assert_eq!(union.max, ray_tracer::vector3d::Vector3::new(1.0, 2.0, 1.0));
+    /// ```
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb::from_points(&[
+            self.min.clone(),
+            self.max.clone(),
+            other.min.clone(),
+            other.max.clone(),
+        ])
+    }
+
+    /// Returns the point at the center of this box
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let aabb = ray_tracer::aabb::Aabb::new(
+    ///     ray_tracer::vector3d::Vector3::new(-1.0, 0.0, 2.0),
+    ///     ray_tracer::vector3d::Vector3::new(3.0, 1.0, 2.0),
+    /// );
+    ///
+    /// assert_eq!(aabb.centroid(), ray_tracer::vector3d::Vector3::new(1.0, 0.5, 2.0));
+    /// ```
+    pub fn centroid(&self) -> Vector3 {
+        self.min.lerp(&self.max, 0.5)
+    }
+
+    /// Returns the index of the axis this box is longest along: 0 for x, 1 for y, and 2 for z
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let aabb = ray_tracer::aabb::Aabb::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(1.0, 5.0, 2.0),
+    /// );
+    ///
+    /// assert_eq!(aabb.longest_axis(), 1);
+    /// ```
+    pub fn longest_axis(&self) -> usize {
+        let extent = self.max.sub(&self.min);
+        if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Returns whether the given ray passes through this box with a _t_ between `t_min` and
+    /// `t_max`, using `slab_interval`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let aabb = ray_tracer::aabb::Aabb::new(
+    ///     ray_tracer::vector3d::Vector3::new(-1.0, -1.0, -6.0),
+    ///     ray_tracer::vector3d::Vector3::new(1.0, 1.0, -4.0),
+    /// );
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// assert!(aabb.hit(&ray, 0.0, f64::INFINITY));
+    /// // The box is further away than the range being searched
+    /// assert!(!aabb.hit(&ray, 0.0, 3.0));
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// assert!(!aabb.hit(&ray, 0.0, f64::INFINITY));
+    /// ```
+    pub fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> bool {
+        self.slab_interval(r, t_min, t_max).is_some()
+    }
+
+    /// Returns the part of the range of _t_ from `t_min` to `t_max` in which the given ray is
+    /// inside this box, or "None" if it is never inside it in that range.
+    ///
+    /// This uses the slab method, narrowing the range of _t_ where the ray is between each pair
+    /// of parallel faces of the box.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let aabb = ray_tracer::aabb::Aabb::new(
+    ///     ray_tracer::vector3d::Vector3::new(-1.0, -1.0, -6.0),
+    ///     ray_tracer::vector3d::Vector3::new(1.0, 1.0, -4.0),
+    /// );
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let everywhere = (f64::NEG_INFINITY, f64::INFINITY);
+    /// assert_eq!(aabb.slab_interval(&ray, everywhere.0, everywhere.1), Some((4.0, 6.0)));
+    /// assert_eq!(aabb.slab_interval(&ray, 5.0, 10.0), Some((5.0, 6.0)));
+    /// assert_eq!(aabb.slab_interval(&ray, 0.0, 3.0), None);
+    /// ```
+    pub fn slab_interval(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<(f64, f64)> {
+        let (mut t_min, mut t_max) = (t_min, t_max);
+        let (pos, dir) = (r.pos.to_array(), r.dir.to_array());
+        let (min, max) = (self.min.to_array(), self.max.to_array());
+        for axis in 0..3 {
+            let inv_dir = 1.0 / dir[axis];
+            let mut t0 = (min[axis] - pos[axis]) * inv_dir;
+            let mut t1 = (max[axis] - pos[axis]) * inv_dir;
+            if inv_dir < 0.0 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            t_min = t_min.max(t0);
+            t_max = t_max.min(t1);
+            if t_max < t_min {
+                return None;
+            }
+        }
+        Some((t_min, t_max))
+    }
+}
@@ -0,0 +1,282 @@
+//! This module defines a Bounding Volume Hierarchy over a set of objects
+
+use crate::aabb::Aabb;
+use crate::object::{HitRecord, Object};
+use crate::ray::Ray;
+
+/// A node of a Bounding Volume Hierarchy
+///
+/// Every node contains the box bounding all of the objects beneath it. Leaves
+/// hold the index of a single object, and interior nodes hold two children.
+#[derive(Debug)]
+enum BvhNode {
+    Leaf {
+        bounds: Aabb,
+        index: usize,
+    },
+    Interior {
+        bounds: Aabb,
+        left: Box<BvhNode>,
+        right: Box<BvhNode>,
+    },
+}
+
+impl BvhNode {
+    /// Builds a hierarchy over the given objects, each paired with its bounding box
+    ///
+    /// The objects are split in half at the median of their centers along the longest axis of
+    /// the box bounding those centers, until each leaf holds a single object.
+    fn build(mut items: Vec<(usize, Aabb)>) -> BvhNode {
+        if items.len() == 1 {
+            let (index, bounds) = items.pop().unwrap();
+            return BvhNode::Leaf { bounds, index };
+        }
+
+        let centroids: Vec<_> = items.iter().map(|(_, bounds)| bounds.centroid()).collect();
+        let axis = Aabb::from_points(&centroids).longest_axis();
+        items.sort_by(|(_, a), (_, b)| {
+            let (a, b) = (a.centroid().to_array()[axis], b.centroid().to_array()[axis]);
+            a.total_cmp(&b)
+        });
+
+        let right_items = items.split_off(items.len() / 2);
+        let left = BvhNode::build(items);
+        let right = BvhNode::build(right_items);
+        BvhNode::Interior {
+            bounds: left.bounds().union(right.bounds()),
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    /// Returns the box bounding everything beneath this node
+    fn bounds(&self) -> &Aabb {
+        match self {
+            BvhNode::Leaf { bounds, .. } => bounds,
+            BvhNode::Interior { bounds, .. } => bounds,
+        }
+    }
+
+    /// Finds the nearest hit of the given ray with the objects beneath this node that is closer
+    /// than `closest`, updating `closest` with it, and counting each object tested in `tests`.
+    fn hit<'a>(
+        &self,
+        objects: &'a [Box<dyn Object>],
+        r: &Ray,
+        closest: &mut f64,
+        tests: &mut u32,
+    ) -> Option<HitRecord<'a>> {
+        if !self.bounds().hit(r, r.t_min, *closest) {
+            return None;
+        }
+        match self {
+            BvhNode::Leaf { index, .. } => match count_hit(objects[*index].as_ref(), r, tests) {
+                Some(hit) if hit.t < *closest => {
+                    *closest = hit.t;
+                    Some(hit)
+                }
+                _ => None,
+            },
+            BvhNode::Interior { left, right, .. } => {
+                let left_hit = left.hit(objects, r, closest, tests);
+                // The right child only reports hits closer than any found on the left
+                right.hit(objects, r, closest, tests).or(left_hit)
+            }
+        }
+    }
+
+    /// Returns whether the given ray hits any object beneath this node
+    fn any_hit(&self, objects: &[Box<dyn Object>], r: &Ray) -> bool {
+        if !self.bounds().hit(r, r.t_min, r.t_max) {
+            return false;
+        }
+        match self {
+            BvhNode::Leaf { index, .. } => objects[*index].ray_intersection(r).is_some(),
+            BvhNode::Interior { left, right, .. } => {
+                left.any_hit(objects, r) || right.any_hit(objects, r)
+            }
+        }
+    }
+}
+
+/// The struct representing a Bounding Volume Hierarchy
+///
+/// Contains the objects, and a tree of bounding boxes over them, so that finding
+/// what a ray hits only needs to test the objects in boxes the ray passes
+/// through. Objects that cannot be bounded, like planes, are kept apart and
+/// always tested.
+#[derive(Default)]
+pub struct Bvh {
+    objects: Vec<Box<dyn Object>>,
+    root: Option<BvhNode>,
+    unbounded: Vec<usize>,
+}
+
+impl Bvh {
+    /// Builds a new hierarchy over the given objects
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let objects: Vec<Box<dyn ray_tracer::object::Object>> = vec![
+    ///     Box::new(ray_tracer::object::sphere::Sphere::default()),
+    ///     Box::new(ray_tracer::object::plane::Plane::default()),
+    /// ];
+    /// let bvh = ray_tracer::accel::bvh::Bvh::new(objects);
+    ///
+    /// assert_eq!(bvh.objects().len(), 2);
+    /// ```
+    pub fn new(objects: Vec<Box<dyn Object>>) -> Bvh {
+        let mut bounded = vec![];
+        let mut unbounded = vec![];
+        for (index, object) in objects.iter().enumerate() {
+            match object.bounding_box() {
+                Some(bounds) => bounded.push((index, bounds)),
+                None => unbounded.push(index),
+            }
+        }
+        let root = if bounded.is_empty() {
+            None
+        } else {
+            Some(BvhNode::build(bounded))
+        };
+        Bvh {
+            objects,
+            root,
+            unbounded,
+        }
+    }
+
+    /// Returns the objects in this hierarchy
+    pub fn objects(&self) -> &[Box<dyn Object>] {
+        &self.objects
+    }
+
+    /// Takes the objects back out of this hierarchy
+    pub fn into_objects(self) -> Vec<Box<dyn Object>> {
+        self.objects
+    }
+
+    /// Finds the nearest intersection of the given ray with the objects in this hierarchy.
+    ///
+    /// If the ray does not hit any object, then "None" is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// // Scatter spheres around a grid
+    /// let sphere = |i: usize| {
+    ///     let (x, y, z) = ((i % 7) as f64, (i % 11) as f64, -((i % 13) as f64));
+    ///     ray_tracer::object::sphere::Sphere::new(
+    ///         ray_tracer::vector3d::Vector3::new(x * 1.5, y * 1.3, z * 1.7 - 5.0),
+    ///         0.2 + (i % 5) as f64 * 0.1,
+    ///         ray_tracer::material::Material::default(),
+    ///     )
+    /// };
+    /// let brute_force: Vec<_> = (0..200).map(sphere).collect();
+    /// let objects: Vec<Box<dyn ray_tracer::object::Object>> = (0..200)
+    ///     .map(|i| Box::new(sphere(i)) as Box<dyn ray_tracer::object::Object>)
+    ///     .collect();
+    /// let bvh = ray_tracer::accel::bvh::Bvh::new(objects);
+    ///
+    /// // The hierarchy finds the same nearest hit as testing every sphere
+    /// for i in 0..100 {
+    ///     let ray_position = ray_tracer::vector3d::Vector3::new(4.0, 6.0, 10.0);
+    ///     let ray_direction = ray_tracer::vector3d::Vector3::new(
+    ///         (i % 10) as f64 * 0.1 - 0.5,
+    ///         (i / 10) as f64 * 0.1 - 0.5,
+    ///         -1.0,
+    ///     )
+    ///     .into_unit();
+    ///     let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    ///     let expected = brute_force
+    ///         .iter()
+    ///         .filter_map(|sphere| sphere.ray_intersection(&ray))
+    ///         .fold(None, |nearest: Option<f64>, t| Some(nearest.map_or(t, |n| n.min(t))));
+    ///     assert_eq!(bvh.hit(&ray).map(|hit| hit.t), expected);
+    ///     assert_eq!(bvh.any_hit(&ray), expected.is_some());
+    /// }
+    /// ```
+    pub fn hit(&self, r: &Ray) -> Option<HitRecord<'_>> {
+        self.hit_counted(r, &mut 0)
+    }
+
+    /// Finds the nearest intersection of the given ray with the objects in this hierarchy like
+    /// `hit`, adding the number of objects whose intersection with the ray was tested to `tests`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // A row of spheres along the x axis
+    /// let objects: Vec<Box<dyn ray_tracer::object::Object>> = (0..8)
+    ///     .map(|i| {
+    ///         Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///             ray_tracer::vector3d::Vector3::new(i as f64 * 3.0, 0.0, -5.0),
+    ///             1.0,
+    ///             ray_tracer::material::Material::default(),
+    ///         )) as Box<dyn ray_tracer::object::Object>
+    ///     })
+    ///     .collect();
+    /// let bvh = ray_tracer::accel::bvh::Bvh::new(objects);
+    ///
+    /// // A ray towards one sphere only needs to test that sphere
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(6.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction.clone());
+    /// let mut tests = 0;
+    /// assert_eq!(bvh.hit_counted(&ray, &mut tests).unwrap().t, 4.0);
+    /// assert_eq!(tests, 1);
+    ///
+    /// // A ray missing every box tests nothing
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 10.0, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// let mut tests = 0;
+    /// assert!(bvh.hit_counted(&ray, &mut tests).is_none());
+    /// assert_eq!(tests, 0);
+    /// ```
+    pub fn hit_counted(&self, r: &Ray, tests: &mut u32) -> Option<HitRecord<'_>> {
+        let mut closest = r.t_max;
+        let mut nearest = match self.root {
+            Some(ref root) => root.hit(&self.objects, r, &mut closest, tests),
+            None => None,
+        };
+        for &index in &self.unbounded {
+            if let Some(hit) = count_hit(self.objects[index].as_ref(), r, tests) {
+                if hit.t < closest {
+                    closest = hit.t;
+                    nearest = Some(hit);
+                }
+            }
+        }
+        nearest
+    }
+
+    /// Returns whether the given ray intersects any object in this hierarchy.
+    ///
+    /// This can stop at the first intersection found, so it is cheaper than `hit` when the
+    /// nearest hit is not needed.
+    pub fn any_hit(&self, r: &Ray) -> bool {
+        let bounded_hit = match self.root {
+            Some(ref root) => root.any_hit(&self.objects, r),
+            None => false,
+        };
+        bounded_hit
+            || self
+                .unbounded
+                .iter()
+                .any(|&index| self.objects[index].ray_intersection(r).is_some())
+    }
+}
+
+/// Finds the intersection of the given ray with the given object, counting the test in `tests`
+pub(crate) fn count_hit<'a>(
+    object: &'a dyn Object,
+    r: &Ray,
+    tests: &mut u32,
+) -> Option<HitRecord<'a>> {
+    *tests += 1;
+    object.hit(r)
+}
@@ -0,0 +1,3 @@
+//! This module defines acceleration structures that speed up finding which objects a ray hits
+
+pub mod bvh;
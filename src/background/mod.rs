@@ -0,0 +1,189 @@
+//! This module defines what is seen along rays that do not hit any object
+
+use super::color::Color;
+use super::texture::{ImageTexture, Texture};
+use super::vector3d::Vector3;
+
+use std::f64::consts::PI;
+use std::path::Path;
+use std::sync::Arc;
+
+/// The enum representing a scene's background
+///
+/// The background is either a single flat Color, a vertical gradient from a
+/// Color seen looking straight down to a Color seen looking straight up, or
+/// an environment map image surrounding the scene.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Background {
+    Flat(Color),
+    Gradient { bottom: Color, top: Color },
+    Environment(Arc<EnvironmentMap>),
+}
+
+/// The struct representing an environment map
+///
+/// Contains an equirectangular image wrapped around the scene, with
+/// longitude running across the image and latitude running up it.
+#[derive(Debug, Default, PartialEq)]
+pub struct EnvironmentMap {
+    pub image: ImageTexture,
+}
+
+impl EnvironmentMap {
+    /// Creates a new environment map wrapping the given equirectangular image around the scene
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let pixels = vec![ray_tracer::color::Color::new(0.0, 0.0, 1.0); 8];
+    /// let image = ray_tracer::texture::ImageTexture::new(4, 2, pixels);
+    /// let environment = ray_tracer::background::EnvironmentMap::new(image);
+    ///
+    /// assert_eq!(environment.image.width, 4);
+    /// ```
+    pub fn new(image: ImageTexture) -> EnvironmentMap {
+        EnvironmentMap { image }
+    }
+
+    /// Loads an environment map from the equirectangular image file at the given path
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// let environment = ray_tracer::background::EnvironmentMap::from_file("sky.png").unwrap();
+    ///
+    /// println!("Loaded a {}x{} sky", environment.image.width, environment.image.height);
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<EnvironmentMap, String> {
+        Ok(EnvironmentMap::new(ImageTexture::from_file(path)?))
+    }
+
+    /// Returns the texture coordinates in the image seen looking in the given direction
+    ///
+    /// These match the texture coordinates of a sphere: _u_ is the longitude, starting from
+    /// the -x direction and passing through +z, +x, and -z, and _v_ is the latitude from
+    /// straight down up to straight up. The direction does not need to be a unit vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let environment = ray_tracer::background::EnvironmentMap::default();
+    ///
+    /// let forward = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0);
+    /// assert_eq!(environment.uv_at(&forward), (0.25, 0.5));
+    ///
+    /// let right = ray_tracer::vector3d::Vector3::new(3.0, 0.0, 0.0);
+    /// assert_eq!(environment.uv_at(&right), (0.5, 0.5));
+    ///
+    /// let up = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// assert_eq!(environment.uv_at(&up).1, 1.0);
+    /// ```
+    pub fn uv_at(&self, dir: &Vector3) -> (f64, f64) {
+        let d = dir.into_unit();
+        let theta = (-d.y).clamp(-1.0, 1.0).acos();
+        let phi = (-d.z).atan2(d.x) + PI;
+        (phi / (2.0 * PI), theta / PI)
+    }
+
+    /// Returns the color of the image seen looking in the given direction
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let red = ray_tracer::color::Color::new(1.0, 0.0, 0.0);
+    /// let blue = ray_tracer::color::Color::new(0.0, 0.0, 1.0);
+    /// let image = ray_tracer::texture::ImageTexture::new(2, 1, vec![red.clone(), blue.clone()]);
+    /// let environment = ray_tracer::background::EnvironmentMap::new(image);
+    ///
+    /// // +z is a quarter of the way across the image, at the center of the left pixel
+    /// let forward = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0);
+    /// assert!(environment.color_in_direction(&forward).approx_eq(&red, 1e-10));
+    ///
+    /// let backward = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// assert!(environment.color_in_direction(&backward).approx_eq(&blue, 1e-10));
+    /// ```
+    pub fn color_in_direction(&self, dir: &Vector3) -> Color {
+        let (u, v) = self.uv_at(dir);
+        self.image.color_at(u, v, dir)
+    }
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Background::Flat(Color::default())
+    }
+}
+
+impl From<Color> for Background {
+    fn from(color: Color) -> Background {
+        Background::Flat(color)
+    }
+}
+
+impl Background {
+    /// Creates a sky gradient, from white at the bottom to light blue at the top
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let sky = ray_tracer::background::Background::sky();
+    ///
+    /// match sky {
+    ///     ray_tracer::background::Background::Gradient { bottom, top } => {
+    ///         assert_eq!(bottom, ray_tracer::color::Color::new(1.0, 1.0, 1.0));
+    ///         assert_eq!(top, ray_tracer::color::Color::new(0.5, 0.7, 1.0));
+    ///     }
+    ///     _ => panic!("expected a gradient"),
+    /// }
+    /// ```
+    pub fn sky() -> Background {
+        Background::Gradient {
+            bottom: Color::new(1.0, 1.0, 1.0),
+            top: Color::new(0.5, 0.7, 1.0),
+        }
+    }
+
+    /// Returns the color seen looking in the given direction
+    ///
+    /// Gradients blend linearly with the height of the unit direction, so looking straight
+    /// down sees the bottom color, looking straight up sees the top color, and looking at the
+    /// horizon sees an even mix of the two. Environment maps are sampled by `uv_at`. The
+    /// direction does not need to be a unit vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let bottom = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let top = ray_tracer::color::Color::new(0.0, 0.0, 1.0);
+    /// let gradient = ray_tracer::background::Background::Gradient { bottom, top };
+    ///
+    /// let up = ray_tracer::vector3d::Vector3::new(0.0, 2.0, 0.0);
+    /// assert_eq!(gradient.color_in_direction(&up), ray_tracer::color::Color::new(0.0, 0.0, 1.0));
+    ///
+    /// let down = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// assert_eq!(gradient.color_in_direction(&down), ray_tracer::color::Color::new(1.0, 1.0, 1.0));
+    ///
+    /// let horizon = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// assert_eq!(gradient.color_in_direction(&horizon), ray_tracer::color::Color::new(0.5, 0.5, 1.0));
+    ///
+    /// let flat = ray_tracer::background::Background::Flat(ray_tracer::color::Color::new(1.0, 0.0, 0.0));
+    /// assert_eq!(flat.color_in_direction(&up), ray_tracer::color::Color::new(1.0, 0.0, 0.0));
+    ///
+    /// let pixels = vec![ray_tracer::color::Color::new(0.0, 1.0, 0.0); 8];
+    /// let image = ray_tracer::texture::ImageTexture::new(4, 2, pixels);
+    /// let environment = ray_tracer::background::Background::Environment(std::sync::Arc::new(
+    ///     ray_tracer::background::EnvironmentMap::new(image),
+    /// ));
+    /// assert_eq!(environment.color_in_direction(&up), ray_tracer::color::Color::new(0.0, 1.0, 0.0));
+    /// ```
+    pub fn color_in_direction(&self, dir: &Vector3) -> Color {
+        match self {
+            Background::Flat(color) => color.clone(),
+            Background::Gradient { bottom, top } => {
+                let t = 0.5 * (dir.into_unit().y + 1.0);
+                bottom.lerp(top, t)
+            }
+            Background::Environment(environment) => environment.color_in_direction(dir),
+        }
+    }
+}
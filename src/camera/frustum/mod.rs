@@ -0,0 +1,201 @@
+//! This module defines the Frustum, the region of space a Camera can see
+
+use super::{Camera, ProjectionMode};
+use crate::aabb::Aabb;
+use crate::object::Object;
+use crate::vector3d::Vector3;
+
+/// The struct representing the region of space seen by a camera
+///
+/// Contains the planes bounding the region, each as a unit normal pointing
+/// into the region and an offset, so that a point _p_ is on the inner side of
+/// the plane when _n·p ≥ offset_.
+///
+/// A perspective or orthographic frustum is bounded behind by the plane
+/// through the camera's position. A pinhole or orthographic camera is also
+/// bounded by the planes through the edges of its image. A camera with an
+/// aperture casts rays from across its lens, so it is only bounded behind, as
+/// is a fisheye camera, which sees everything in front of it. An
+/// equirectangular camera can see behind itself, so its frustum is unbounded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frustum {
+    pub planes: Vec<(Vector3, f64)>,
+}
+
+impl Frustum {
+    /// Creates the frustum of the given camera
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let camera = ray_tracer::camera::Camera::default();
+    /// let frustum = ray_tracer::camera::frustum::Frustum::new(&camera);
+    ///
+    /// // The plane behind the camera, and the four edges of the image
+    /// assert_eq!(frustum.planes.len(), 5);
+    ///
+    /// let camera = ray_tracer::camera::Camera {
+    ///     aperture: 0.5,
+    ///     ..Default::default()
+    /// };
+    /// let frustum = ray_tracer::camera::frustum::Frustum::new(&camera);
+    /// assert_eq!(frustum.planes.len(), 1);
+    ///
+    /// let camera = ray_tracer::camera::Camera {
+    ///     projection: ray_tracer::camera::ProjectionMode::Fisheye,
+    ///     ..Default::default()
+    /// };
+    /// let frustum = ray_tracer::camera::frustum::Frustum::new(&camera);
+    /// assert_eq!(frustum.planes.len(), 1);
+    ///
+    /// let camera = ray_tracer::camera::Camera {
+    ///     projection: ray_tracer::camera::ProjectionMode::Equirectangular,
+    ///     ..Default::default()
+    /// };
+    /// let frustum = ray_tracer::camera::frustum::Frustum::new(&camera);
+    /// assert!(frustum.planes.is_empty());
+    /// ```
+    pub fn new(camera: &Camera) -> Frustum {
+        let (forward, right, up) = camera.basis();
+        let position = &camera.position;
+        let plane = |normal: Vector3, offset: f64| (normal, offset);
+        if camera.projection == ProjectionMode::Equirectangular {
+            return Frustum { planes: vec![] };
+        }
+        let mut planes = vec![plane(forward.clone(), forward.dot(position))];
+
+        match camera.projection {
+            ProjectionMode::Perspective if camera.aperture <= 0.0 => {
+                let half_height = (camera.fov_y.to_radians() / 2.0).tan();
+                let half_width = camera.aspect * half_height;
+                // Each side contains the camera's position and the rays along one edge
+                for normal in [
+                    forward.mul(half_width).sub(&right),
+                    forward.mul(half_width).add(&right),
+                    forward.mul(half_height).sub(&up),
+                    forward.mul(half_height).add(&up),
+                ] {
+                    let normal = normal.into_unit();
+                    let offset = normal.dot(position);
+                    planes.push(plane(normal, offset));
+                }
+            }
+            ProjectionMode::Perspective
+            | ProjectionMode::Fisheye
+            | ProjectionMode::Equirectangular => {}
+            ProjectionMode::Ortho => {
+                let half_height = camera.ortho_scale / 2.0;
+                let half_width = camera.aspect * half_height;
+                for (axis, half_extent) in [(right, half_width), (up, half_height)] {
+                    let center = axis.dot(position);
+                    planes.push(plane(axis.negate(), -center - half_extent));
+                    planes.push(plane(axis, center - half_extent));
+                }
+            }
+        }
+        Frustum { planes }
+    }
+
+    /// Returns whether any of the given box might be inside this frustum
+    ///
+    /// A box is only outside if it is entirely on the outer side of one of the planes, so some
+    /// boxes near the corners of the frustum are kept even though they cannot be seen.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let camera = ray_tracer::camera::Camera::default();
+    /// let frustum = ray_tracer::camera::frustum::Frustum::new(&camera);
+    ///
+    /// let in_front = ray_tracer::aabb::Aabb::new(
+    ///     ray_tracer::vector3d::Vector3::new(-1.0, -1.0, -6.0),
+    ///     ray_tracer::vector3d::Vector3::new(1.0, 1.0, -4.0),
+    /// );
+    /// assert!(frustum.intersects(&in_front));
+    ///
+    /// // Straddling the camera's position still counts
+    /// let around = ray_tracer::aabb::Aabb::new(
+    ///     ray_tracer::vector3d::Vector3::new(-1.0, -1.0, -1.0),
+    ///     ray_tracer::vector3d::Vector3::new(1.0, 1.0, 1.0),
+    /// );
+    /// assert!(frustum.intersects(&around));
+    ///
+    /// let off_to_the_side = ray_tracer::aabb::Aabb::new(
+    ///     ray_tracer::vector3d::Vector3::new(10.0, -1.0, -3.0),
+    ///     ray_tracer::vector3d::Vector3::new(12.0, 1.0, -1.0),
+    /// );
+    /// assert!(!frustum.intersects(&off_to_the_side));
+    /// ```
+    pub fn intersects(&self, aabb: &Aabb) -> bool {
+        self.planes.iter().all(|(normal, offset)| {
+            // The corner of the box furthest along the normal
+            let corner = Vector3::new(
+                if normal.x >= 0.0 {
+                    aabb.max.x
+                } else {
+                    aabb.min.x
+                },
+                if normal.y >= 0.0 {
+                    aabb.max.y
+                } else {
+                    aabb.min.y
+                },
+                if normal.z >= 0.0 {
+                    aabb.max.z
+                } else {
+                    aabb.min.z
+                },
+            );
+            normal.dot(&corner) >= *offset
+        })
+    }
+
+    /// Returns the given objects that might be inside this frustum
+    ///
+    /// Objects that cannot be bounded, like planes, are always kept.
+    ///
+    /// This is for a scene's loose objects. Objects in its bounding volume hierarchy do not need
+    /// culling, since the hierarchy only tests the objects in boxes that a ray passes through,
+    /// and the boxes that a camera's rays pass through are all inside its frustum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let camera = ray_tracer::camera::Camera::default();
+    /// let frustum = ray_tracer::camera::frustum::Frustum::new(&camera);
+    ///
+    /// let objects: Vec<Box<dyn ray_tracer::object::Object>> = vec![
+    ///     Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///         ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///         1.0,
+    ///         ray_tracer::material::Material::default(),
+    ///     )),
+    ///     Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///         ray_tracer::vector3d::Vector3::new(0.0, 0.0, 5.0),
+    ///         1.0,
+    ///         ray_tracer::material::Material::default(),
+    ///     )),
+    ///     Box::new(ray_tracer::object::plane::Plane::new(
+    ///         ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0),
+    ///         ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0),
+    ///         ray_tracer::material::Material::default(),
+    ///     )),
+    /// ];
+    ///
+    /// // The sphere behind the camera is left out
+    /// let visible = frustum.cull(&objects);
+    /// assert_eq!(visible.len(), 2);
+    /// assert_eq!(visible[0].position().z, -5.0);
+    /// assert_eq!(visible[1].position().y, -1.0);
+    /// ```
+    pub fn cull<'a>(&self, objects: &'a [Box<dyn Object>]) -> Vec<&'a dyn Object> {
+        objects
+            .iter()
+            .map(|object| object.as_ref())
+            .filter(|object| match object.bounding_box() {
+                Some(aabb) => self.intersects(&aabb),
+                None => true,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,619 @@
+//! This module defines the Camera that primary rays are traced from
+
+use rand::Rng;
+
+use super::ray::Ray;
+use super::vector3d::Vector3;
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// The smallest angle, in radians, allowed between the view direction and the up direction, so
+/// that pitching the camera never makes its basis degenerate
+const MIN_PITCH_ANGLE: f64 = 0.01;
+
+/// Returns a random point inside the unit disk in the xy plane
+fn random_in_unit_disk<R: Rng>(rng: &mut R) -> Vector3 {
+    loop {
+        let p = Vector3::new(rng.gen_range(-1.0, 1.0), rng.gen_range(-1.0, 1.0), 0.0);
+        if p.length_squared() < 1.0 {
+            return p;
+        }
+    }
+}
+
+/// Represents the current projection mode: Ortho, Perspective, or one of the wide angle
+/// Fisheye and Equirectangular projections.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ProjectionMode {
+    Ortho,
+    #[default]
+    Perspective,
+    /// An equidistant fisheye, whose angle from the view direction grows evenly with the
+    /// distance from the center of the image, seeing 180 degrees across the largest circle that
+    /// fits in the image
+    Fisheye,
+    /// A 360 degree panorama, with the angle around the camera's up direction running across
+    /// the image and the angle above or below the horizon running up it
+    Equirectangular,
+}
+
+/// The struct representing a camera
+///
+/// Contains a Vector3 for the camera's position, a Vector3 for the point it
+/// looks at, a Vector3 for the direction that is up in the image, the angle in
+/// radians the image is rolled by around the view direction, the vertical
+/// field of view in degrees, the aspect ratio (width / height) of the image,
+/// the projection used to create rays, the height of the view in world units
+/// for the orthographic projection, the diameter of the lens aperture
+/// and the distance to the plane in focus, and the times at which the shutter
+/// opens and closes.
+///
+/// An aperture of 0.0 is a pinhole camera, where everything is in focus. A
+/// shutter that closes as soon as it opens freezes moving objects in place.
+#[derive(Debug)]
+pub struct Camera {
+    pub position: Vector3,
+    pub target: Vector3,
+    pub up: Vector3,
+    pub roll: f64,
+    pub fov_y: f64,
+    pub aspect: f64,
+    pub projection: ProjectionMode,
+    pub ortho_scale: f64,
+    pub aperture: f64,
+    pub focus_distance: f64,
+    pub shutter_open: f64,
+    pub shutter_close: f64,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Camera {
+            position: Vector3::new(0.0, 0.0, 0.0),
+            target: Vector3::new(0.0, 0.0, -1.0),
+            up: Vector3::new(0.0, 1.0, 0.0),
+            roll: 0.0,
+            fov_y: 90.0,
+            aspect: 1.0,
+            projection: ProjectionMode::Perspective,
+            ortho_scale: 2.0,
+            aperture: 0.0,
+            focus_distance: 1.0,
+            shutter_open: 0.0,
+            shutter_close: 0.0,
+        }
+    }
+}
+
+impl Camera {
+    /// Creates a new camera at the given eye position looking at the given target, with the
+    /// given up direction, vertical field of view in degrees, and aspect ratio, or returns None
+    /// if the field of view is not valid
+    ///
+    /// The up direction does not need to be perpendicular to the view direction, or a unit
+    /// vector, but it must not be parallel to the view direction.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let eye = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0);
+    /// let target = ray_tracer::vector3d::Vector3::new(-2.0, 0.0, -1.0);
+    /// let up = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let camera =
+    ///     ray_tracer::camera::Camera::look_at(eye.clone(), target.clone(), up.clone(), 60.0, 1.5)
+    ///         .unwrap();
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(0);
+    ///
+    /// let (forward, _, _) = camera.basis();
+    /// assert!(forward.approx_eq(&target.sub(&eye).into_unit(), 1e-10));
+    /// assert!(camera.generate_ray(0.5, 0.5, &mut rng).dir.approx_eq(&forward, 1e-10));
+    /// assert_eq!(camera.fov_y, 60.0);
+    ///
+    /// assert!(ray_tracer::camera::Camera::look_at(eye, target, up, 180.0, 1.5).is_none());
+    /// ```
+    pub fn look_at(
+        eye: Vector3,
+        target: Vector3,
+        up: Vector3,
+        fov_y: f64,
+        aspect: f64,
+    ) -> Option<Camera> {
+        if !Camera::is_valid_fov(fov_y) {
+            return None;
+        }
+        Some(Camera {
+            position: eye,
+            target,
+            up,
+            roll: 0.0,
+            fov_y,
+            aspect,
+            projection: ProjectionMode::Perspective,
+            ortho_scale: 2.0,
+            aperture: 0.0,
+            focus_distance: 1.0,
+            shutter_open: 0.0,
+            shutter_close: 0.0,
+        })
+    }
+
+    /// Returns the orthonormal frame of this camera, as the unit forward, right, and up vectors.
+    ///
+    /// Forward points from the camera's position towards its target, right is _forward × up_,
+    /// and up is _right × forward_, the camera's up direction made perpendicular to the other
+    /// two. The frame is right-handed, so the camera looks down its local -z axis, with +x to
+    /// the right and +y up.
+    ///
+    /// If the camera is rolled, right and up are then rotated about forward by the roll angle,
+    /// following the right hand rule, which tilts the image for Dutch angle shots.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let camera = ray_tracer::camera::Camera::look_at(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(5.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(1.0, 2.0, 0.0),
+    ///     90.0,
+    ///     1.0,
+    /// )
+    /// .unwrap();
+    ///
+    /// let (forward, right, up) = camera.basis();
+    /// assert_eq!(forward, ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0));
+    /// assert_eq!(right, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// assert_eq!(up, ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0));
+    /// assert_eq!(right.cross(&up), forward.negate());
+    ///
+    /// // A quarter roll turns right into down, and up into right
+    /// let camera = ray_tracer::camera::Camera {
+    ///     roll: std::f64::consts::FRAC_PI_2,
+    ///     ..Default::default()
+    /// };
+    /// let (_, right, up) = camera.basis();
+    /// assert!(right.approx_eq(&ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0), 1e-10));
+    /// assert!(up.approx_eq(&ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0), 1e-10));
+    /// ```
+    pub fn basis(&self) -> (Vector3, Vector3, Vector3) {
+        let forward = self.target.sub(&self.position).into_unit();
+        let right = forward.cross(&self.up).into_unit();
+        let up = right.cross(&forward);
+        if self.roll == 0.0 {
+            return (forward, right, up);
+        }
+        let right = right.rotate_around(&forward, self.roll);
+        let up = up.rotate_around(&forward, self.roll);
+        (forward, right, up)
+    }
+
+    /// Creates the primary ray through the given point of the image.
+    ///
+    /// `u` runs from 0.0 at the left edge of the image to 1.0 at the right edge, and `v` runs
+    /// from 0.0 at the bottom edge to 1.0 at the top edge.
+    ///
+    /// With a perspective projection, every ray starts at the camera's position and the rays
+    /// through the edges of the image span the field of view. With an orthographic projection,
+    /// every ray travels towards the target, starting from a point on a view plane through the
+    /// camera's position, which is `ortho_scale` high and as wide as the aspect ratio makes it.
+    ///
+    /// With a fisheye projection, every ray starts at the camera's position, and turns away
+    /// from forward in proportion to its distance from the center, by 90 degrees at the edge of
+    /// the largest circle that fits in the image. Rays outside of the circle hit nothing, so the
+    /// background fills the rest of the image. With an equirectangular projection, the rays through the left
+    /// and right edges look straight behind the camera, from either side, and those through the
+    /// top and bottom edges look straight up and down.
+    ///
+    /// With a perspective projection and a positive aperture, each ray starts from a random
+    /// point on the lens, and is aimed through the point on the plane in focus that the pinhole
+    /// ray would have hit. Objects on that plane are sharp, and others are blurred as more rays
+    /// are averaged. The aperture is ignored by the other projections.
+    ///
+    /// Each ray is cast at a random time while the shutter is open, so that moving objects are
+    /// blurred along their paths as more rays are averaged. The random points on the lens and
+    /// times are taken from the given generator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let camera = ray_tracer::camera::Camera {
+    ///     aspect: 2.0,
+    ///     ..Default::default()
+    /// };
+    /// let forward = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(0);
+    ///
+    /// // The center of the image looks straight ahead
+    /// let center = camera.generate_ray(0.5, 0.5, &mut rng);
+    /// assert_eq!(center.pos, camera.position);
+    /// assert_eq!(center.dir, forward);
+    ///
+    /// // The top and bottom edges span the vertical field of view
+    /// let top = camera.generate_ray(0.5, 1.0, &mut rng);
+    /// let bottom = camera.generate_ray(0.5, 0.0, &mut rng);
+    /// assert!((top.dir.angle_between(&bottom.dir).to_degrees() - 90.0).abs() < 1e-10);
+    /// assert!(top.dir.y > 0.0);
+    ///
+    /// // The left and right edges are wider, by the aspect ratio
+    /// let left = camera.generate_ray(0.0, 0.5, &mut rng);
+    /// let right = camera.generate_ray(1.0, 0.5, &mut rng);
+    /// let half_fov_x = left.dir.angle_between(&forward);
+    /// assert!((half_fov_x.tan() - 2.0).abs() < 1e-10);
+    /// assert!((right.dir.angle_between(&forward) - half_fov_x).abs() < 1e-10);
+    /// assert!(right.dir.x > 0.0);
+    ///
+    /// // The corners span both
+    /// let corner = camera.generate_ray(1.0, 1.0, &mut rng);
+    /// let expected = ray_tracer::vector3d::Vector3::new(2.0, 1.0, -1.0).into_unit();
+    /// assert!(corner.dir.approx_eq(&expected, 1e-10));
+    ///
+    /// // Rolling the camera a quarter turn swaps the horizontal and vertical spread
+    /// let rolled = ray_tracer::camera::Camera {
+    ///     aspect: 2.0,
+    ///     roll: std::f64::consts::FRAC_PI_2,
+    ///     ..Default::default()
+    /// };
+    /// let corner = rolled.generate_ray(1.0, 1.0, &mut rng);
+    /// let expected = ray_tracer::vector3d::Vector3::new(1.0, -2.0, -1.0).into_unit();
+    /// assert!(corner.dir.approx_eq(&expected, 1e-10));
+    ///
+    /// // Orthographic rays are parallel, starting from different points
+    /// let camera = ray_tracer::camera::Camera {
+    ///     projection: ray_tracer::camera::ProjectionMode::Ortho,
+    ///     ..Default::default()
+    /// };
+    /// let corner = camera.generate_ray(1.0, 1.0, &mut rng);
+    /// assert_eq!(corner.dir, forward);
+    /// assert!(corner.pos.approx_eq(&ray_tracer::vector3d::Vector3::new(1.0, 1.0, 0.0), 1e-10));
+    ///
+    /// // Doubling the orthographic scale doubles the area the rays cover
+    /// let camera = ray_tracer::camera::Camera {
+    ///     projection: ray_tracer::camera::ProjectionMode::Ortho,
+    ///     ortho_scale: 4.0,
+    ///     aspect: 2.0,
+    ///     ..Default::default()
+    /// };
+    /// let left = camera.generate_ray(0.0, 0.5, &mut rng).pos;
+    /// let right = camera.generate_ray(1.0, 0.5, &mut rng).pos;
+    /// let top = camera.generate_ray(0.5, 1.0, &mut rng).pos;
+    /// let bottom = camera.generate_ray(0.5, 0.0, &mut rng).pos;
+    /// assert!((left.distance(&right) - 8.0).abs() < 1e-10);
+    /// assert!((top.distance(&bottom) - 4.0).abs() < 1e-10);
+    ///
+    /// // With an aperture of zero, the camera is a pinhole camera
+    /// let pinhole = ray_tracer::camera::Camera::default();
+    /// let camera = ray_tracer::camera::Camera {
+    ///     aperture: 0.0,
+    ///     focus_distance: 5.0,
+    ///     ..Default::default()
+    /// };
+    /// for &(u, v) in &[(0.5, 0.5), (0.1, 0.9), (1.0, 0.0)] {
+    ///     let ray = camera.generate_ray(u, v, &mut rng);
+    ///     let pinhole_ray = pinhole.generate_ray(u, v, &mut rng);
+    ///     assert_eq!(ray.pos, pinhole_ray.pos);
+    ///     assert_eq!(ray.dir, pinhole_ray.dir);
+    /// }
+    ///
+    /// // With a positive aperture, rays start from different points on the lens, but all meet
+    /// // on the plane in focus
+    /// let camera = ray_tracer::camera::Camera {
+    ///     aperture: 0.5,
+    ///     focus_distance: 5.0,
+    ///     ..Default::default()
+    /// };
+    /// let in_focus = ray_tracer::vector3d::Vector3::new(-5.0, 0.0, -5.0);
+    /// let rays: Vec<_> = (0..10).map(|_| camera.generate_ray(0.0, 0.5, &mut rng)).collect();
+    /// assert!(rays.iter().any(|ray| ray.pos != rays[0].pos));
+    /// for ray in &rays {
+    ///     assert!(ray.pos.distance(&camera.position) <= 0.25);
+    ///     let t = (in_focus.z - ray.pos.z) / ray.dir.z;
+    ///     assert!(ray.at(t).approx_eq(&in_focus, 1e-10));
+    /// }
+    ///
+    /// // Rays are cast while the shutter is open
+    /// assert_eq!(pinhole.generate_ray(0.5, 0.5, &mut rng).time, 0.0);
+    /// let camera = ray_tracer::camera::Camera {
+    ///     shutter_open: 0.25,
+    ///     shutter_close: 0.75,
+    ///     ..Default::default()
+    /// };
+    /// let times: Vec<_> = (0..10).map(|_| camera.generate_ray(0.5, 0.5, &mut rng).time).collect();
+    /// assert!(times.iter().all(|time| (0.25..0.75).contains(time)));
+    /// assert!(times.iter().any(|&time| time != times[0]));
+    ///
+    /// // A fisheye sees straight ahead at the center, and sideways at the top edge
+    /// let camera = ray_tracer::camera::Camera {
+    ///     projection: ray_tracer::camera::ProjectionMode::Fisheye,
+    ///     aspect: 4.0 / 3.0,
+    ///     ..Default::default()
+    /// };
+    /// assert!(camera.generate_ray(0.5, 0.5, &mut rng).dir.approx_eq(&forward, 1e-10));
+    /// let up = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// assert!(camera.generate_ray(0.5, 1.0, &mut rng).dir.approx_eq(&up, 1e-10));
+    /// let halfway = camera.generate_ray(0.5, 0.75, &mut rng).dir;
+    /// assert!((halfway.angle_between(&forward).to_degrees() - 45.0).abs() < 1e-10);
+    ///
+    /// // The image is wider than it is high, so sideways is reached before the left and right
+    /// // edges, and the corners are outside of the circle and hit nothing
+    /// let right = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// assert!(camera.generate_ray(0.875, 0.5, &mut rng).dir.approx_eq(&right, 1e-10));
+    /// let ray = camera.generate_ray(1.0, 0.5, &mut rng);
+    /// assert!(ray.dir.z.abs() < 1e-10);
+    /// assert!(!ray.in_bounds(1.0));
+    /// assert!(!camera.generate_ray(1.0, 1.0, &mut rng).in_bounds(1.0));
+    ///
+    /// // An equirectangular panorama maps the leftmost and rightmost columns to opposite
+    /// // angles around the camera
+    /// let camera = ray_tracer::camera::Camera {
+    ///     projection: ray_tracer::camera::ProjectionMode::Equirectangular,
+    ///     ..Default::default()
+    /// };
+    /// let mut azimuth = |u: f64| {
+    ///     let dir = camera.generate_ray(u, 0.5, &mut rng).dir;
+    ///     dir.x.atan2(-dir.z)
+    /// };
+    /// let width = 64.0;
+    /// let leftmost = azimuth(0.5 / width);
+    /// let rightmost = azimuth(1.0 - 0.5 / width);
+    /// assert!((leftmost + rightmost).abs() < 1e-10);
+    /// assert!((rightmost - std::f64::consts::PI * (1.0 - 1.0 / width)).abs() < 1e-10);
+    ///
+    /// // A quarter of the way across looks left, and three quarters looks right
+    /// let left = camera.generate_ray(0.25, 0.5, &mut rng).dir;
+    /// let right = camera.generate_ray(0.75, 0.5, &mut rng).dir;
+    /// assert!(left.approx_eq(&ray_tracer::vector3d::Vector3::new(-1.0, 0.0, 0.0), 1e-10));
+    /// assert!(right.approx_eq(&left.negate(), 1e-10));
+    /// assert!(camera.generate_ray(0.5, 1.0, &mut rng).dir.approx_eq(&up, 1e-10));
+    ///
+    /// // The same seed gives the same points on the lens and times
+    /// let camera = ray_tracer::camera::Camera {
+    ///     aperture: 0.5,
+    ///     shutter_close: 1.0,
+    ///     ..Default::default()
+    /// };
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(1);
+    /// let ray = camera.generate_ray(0.2, 0.7, &mut rng);
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(1);
+    /// let same_ray = camera.generate_ray(0.2, 0.7, &mut rng);
+    /// assert_eq!(ray.pos, same_ray.pos);
+    /// assert_eq!(ray.dir, same_ray.dir);
+    /// assert_eq!(ray.time, same_ray.time);
+    /// ```
+    pub fn generate_ray<R: Rng>(&self, u: f64, v: f64, rng: &mut R) -> Ray {
+        let time = if self.shutter_close > self.shutter_open {
+            rng.gen_range(self.shutter_open, self.shutter_close)
+        } else {
+            self.shutter_open
+        };
+        self.primary_ray(u, v, rng).at_time(time)
+    }
+
+    /// Creates the primary ray through the given point of the image, before it is given a time
+    fn primary_ray<R: Rng>(&self, u: f64, v: f64, rng: &mut R) -> Ray {
+        let (forward, right, up) = self.basis();
+
+        let half_height = match self.projection {
+            ProjectionMode::Perspective => (self.fov_y.to_radians() / 2.0).tan(),
+            ProjectionMode::Ortho => self.ortho_scale / 2.0,
+            ProjectionMode::Fisheye | ProjectionMode::Equirectangular => 1.0,
+        };
+        let half_width = self.aspect * half_height;
+        let x = (2.0 * u - 1.0) * half_width;
+        let y = (2.0 * v - 1.0) * half_height;
+
+        match self.projection {
+            ProjectionMode::Perspective => {
+                let dir = forward.add(&right.mul(x)).add(&up.mul(y));
+                if self.aperture <= 0.0 {
+                    return Ray::new(self.position.clone(), dir.into_unit());
+                }
+
+                let focus_point = self.position.add(&dir.mul(self.focus_distance));
+                let lens = random_in_unit_disk(rng).mul(self.aperture / 2.0);
+                let origin = self.position.add(&right.mul(lens.x)).add(&up.mul(lens.y));
+                let dir = focus_point.sub(&origin).into_unit();
+                Ray::new(origin, dir)
+            }
+            ProjectionMode::Ortho => {
+                let offset = right.mul(x).add(&up.mul(y));
+                Ray::new(self.position.add(&offset), forward)
+            }
+            ProjectionMode::Fisheye => {
+                let radius = x.hypot(y) / half_width.min(half_height);
+                let angle = radius.min(1.0) * FRAC_PI_2;
+                let dir = if radius > 0.0 {
+                    let sideways = right.mul(x).add(&up.mul(y)).into_unit();
+                    forward.mul(angle.cos()).add(&sideways.mul(angle.sin()))
+                } else {
+                    forward
+                };
+                if radius > 1.0 {
+                    // An empty range of t, so that nothing is hit
+                    return Ray::new_bounded(self.position.clone(), dir.into_unit(), 0.0, 0.0);
+                }
+                Ray::new(self.position.clone(), dir.into_unit())
+            }
+            ProjectionMode::Equirectangular => {
+                let azimuth = (2.0 * u - 1.0) * PI;
+                let elevation = (v - 0.5) * PI;
+                let horizontal = forward.mul(azimuth.cos()).add(&right.mul(azimuth.sin()));
+                let dir = horizontal
+                    .mul(elevation.cos())
+                    .add(&up.mul(elevation.sin()));
+                Ray::new(self.position.clone(), dir.into_unit())
+            }
+        }
+    }
+
+    /// Returns whether the given vertical field of view, in degrees, can be used by a
+    /// perspective camera
+    ///
+    /// The field of view must be strictly between 0 and 180 degrees. At 0 every ray is the same,
+    /// and at 180 or more the image plane is infinitely large or behind the camera.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// assert!(ray_tracer::camera::Camera::is_valid_fov(60.0));
+    /// assert!(!ray_tracer::camera::Camera::is_valid_fov(0.0));
+    /// assert!(!ray_tracer::camera::Camera::is_valid_fov(180.0));
+    /// assert!(!ray_tracer::camera::Camera::is_valid_fov(f64::NAN));
+    /// ```
+    pub fn is_valid_fov(fov_y: f64) -> bool {
+        fov_y > 0.0 && fov_y < 180.0
+    }
+
+    /// Sets the vertical field of view to the given angle in degrees, returning whether it was
+    /// valid
+    ///
+    /// Invalid angles are rejected, leaving the field of view unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut camera = ray_tracer::camera::Camera::default();
+    ///
+    /// assert!(camera.set_fov_y(45.0));
+    /// assert_eq!(camera.fov_y, 45.0);
+    ///
+    /// assert!(!camera.set_fov_y(0.0));
+    /// assert!(!camera.set_fov_y(200.0));
+    /// assert_eq!(camera.fov_y, 45.0);
+    /// ```
+    pub fn set_fov_y(&mut self, fov_y: f64) -> bool {
+        if !Camera::is_valid_fov(fov_y) {
+            return false;
+        }
+        self.fov_y = fov_y;
+        true
+    }
+
+    /// Returns the horizontal field of view in degrees, which the vertical field of view and the
+    /// aspect ratio give
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let camera = ray_tracer::camera::Camera {
+    ///     aspect: 3.0_f64.sqrt(),
+    ///     ..ray_tracer::camera::Camera::look_at(
+    ///         ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///         ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0),
+    ///         ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0),
+    ///         60.0,
+    ///         1.0,
+    ///     )
+    ///     .unwrap()
+    /// };
+    ///
+    /// assert!((camera.fov_x() - 90.0).abs() < 1e-10);
+    /// ```
+    pub fn fov_x(&self) -> f64 {
+        let half_width = (self.fov_y.to_radians() / 2.0).tan() * self.aspect;
+        2.0 * half_width.atan().to_degrees()
+    }
+
+    /// Sets the horizontal field of view to the given angle in degrees, by changing the vertical
+    /// field of view to match at the current aspect ratio, returning whether it was valid
+    ///
+    /// Invalid angles are rejected, leaving the field of view unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut camera = ray_tracer::camera::Camera {
+    ///     aspect: 2.0,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(camera.set_fov_x(90.0));
+    /// assert!((camera.fov_x() - 90.0).abs() < 1e-10);
+    /// assert!(((camera.fov_y.to_radians() / 2.0).tan() - 0.5).abs() < 1e-10);
+    ///
+    /// assert!(!camera.set_fov_x(180.0));
+    /// assert!((camera.fov_x() - 90.0).abs() < 1e-10);
+    /// ```
+    pub fn set_fov_x(&mut self, fov_x: f64) -> bool {
+        if !Camera::is_valid_fov(fov_x) {
+            return false;
+        }
+        let half_height = (fov_x.to_radians() / 2.0).tan() / self.aspect;
+        self.set_fov_y(2.0 * half_height.atan().to_degrees())
+    }
+
+    /// Moves the camera and its target the given distances along the camera's forward, right,
+    /// and up directions, so that the camera keeps looking the same way
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut camera = ray_tracer::camera::Camera::look_at(
+    ///     ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(1.0, 0.0, -10.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0),
+    ///     90.0,
+    ///     1.0,
+    /// )
+    /// .unwrap();
+    ///
+    /// // A step forward moves the eye along the view direction
+    /// camera.translate(2.0, 0.0, 0.0);
+    /// assert_eq!(camera.position, ray_tracer::vector3d::Vector3::new(1.0, 0.0, -2.0));
+    /// assert_eq!(camera.target, ray_tracer::vector3d::Vector3::new(1.0, 0.0, -12.0));
+    ///
+    /// camera.translate(0.0, -1.0, 0.5);
+    /// assert_eq!(camera.position, ray_tracer::vector3d::Vector3::new(0.0, 0.5, -2.0));
+    /// ```
+    pub fn translate(&mut self, forward: f64, right: f64, up: f64) {
+        let (forward_dir, right_dir, up_dir) = self.basis();
+        let offset = forward_dir
+            .mul(forward)
+            .add(&right_dir.mul(right))
+            .add(&up_dir.mul(up));
+        self.position += &offset;
+        self.target += &offset;
+    }
+
+    /// Turns the camera by the given yaw and pitch angles, in radians, keeping its position and
+    /// the distance to its target
+    ///
+    /// Positive yaw turns the camera to the left, around its up vector, and positive pitch turns
+    /// it upwards, around its right direction. Pitch stops just short of looking straight along
+    /// the up vector, where the camera's basis is not defined.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut camera = ray_tracer::camera::Camera::default();
+    ///
+    /// camera.rotate(std::f64::consts::FRAC_PI_2, 0.0);
+    /// let left = ray_tracer::vector3d::Vector3::new(-1.0, 0.0, 0.0);
+    /// assert!(camera.basis().0.approx_eq(&left, 1e-10));
+    ///
+    /// camera.rotate(0.0, std::f64::consts::FRAC_PI_4);
+    /// let up_left = ray_tracer::vector3d::Vector3::new(-1.0, 1.0, 0.0).into_unit();
+    /// assert!(camera.basis().0.approx_eq(&up_left, 1e-10));
+    ///
+    /// // Pitching past straight up is stopped
+    /// camera.rotate(0.0, std::f64::consts::PI);
+    /// assert!(camera.basis().0.y < 1.0);
+    /// assert!(camera.basis().0.x < 0.0);
+    /// ```
+    pub fn rotate(&mut self, yaw: f64, pitch: f64) {
+        let to_target = self.target.sub(&self.position);
+        // Turn around the unrolled right direction, so that pitch stays level with the up vector
+        let right = to_target.cross(&self.up).into_unit();
+
+        let up = self.up.into_unit();
+        let yawed = to_target.rotate_around(&up, yaw);
+        let angle_from_up = yawed.angle_between(&up);
+        let pitch = pitch.clamp(
+            angle_from_up - PI + MIN_PITCH_ANGLE,
+            angle_from_up - MIN_PITCH_ANGLE,
+        );
+        let rotated = yawed.rotate_around(&right.rotate_around(&up, yaw), pitch);
+
+        self.target = self.position.add(&rotated);
+    }
+}
+
+pub mod frustum;
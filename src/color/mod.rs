@@ -0,0 +1,332 @@
+//! This module defines a floating-point color, independent of any display library
+
+use sdl2::pixels;
+
+use std::iter::Sum;
+use std::ops::AddAssign;
+
+/// The struct representing a color
+///
+/// Contains an f64 for each of the red, green, and blue components. Components
+/// from 0.0 to 1.0 can be displayed, but lighting calculations may push them
+/// outside of that range, so they are only clamped when converting to an
+/// 8-bit color.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Color {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+}
+
+impl Color {
+    /// Creates a new color with the given red, green, and blue components
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let color = ray_tracer::color::Color::new(1.0, 0.5, 0.0);
+    ///
+    /// assert_eq!(color.r, 1.0);
+    /// assert_eq!(color.g, 0.5);
+    /// assert_eq!(color.b, 0.0);
+    /// ```
+    pub fn new(r: f64, g: f64, b: f64) -> Color {
+        Color { r, g, b }
+    }
+
+    /// Adds two colors together, component by component
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let color1 = ray_tracer::color::Color::new(0.5, 0.25, 0.0);
+    /// let color2 = ray_tracer::color::Color::new(0.75, 0.25, 0.5);
+    ///
+    /// assert_eq!(color1.add(&color2), ray_tracer::color::Color::new(1.25, 0.5, 0.5));
+    /// ```
+    pub fn add(&self, other: &Color) -> Color {
+        Color {
+            r: self.r + other.r,
+            g: self.g + other.g,
+            b: self.b + other.b,
+        }
+    }
+
+    /// Scales each component of this color by the given factor
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let color = ray_tracer::color::Color::new(1.0, 0.5, 0.0);
+    ///
+    /// assert_eq!(color.mul(0.5), ray_tracer::color::Color::new(0.5, 0.25, 0.0));
+    /// ```
+    pub fn mul(&self, factor: f64) -> Color {
+        Color {
+            r: self.r * factor,
+            g: self.g * factor,
+            b: self.b * factor,
+        }
+    }
+
+    /// Multiplies two colors together, component by component
+    ///
+    /// This is how a surface of one color filters light of another.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let light = ray_tracer::color::Color::new(1.0, 0.5, 1.0);
+    /// let surface = ray_tracer::color::Color::new(0.5, 1.0, 0.0);
+    ///
+    /// assert_eq!(light.component_mul(&surface), ray_tracer::color::Color::new(0.5, 0.5, 0.0));
+    /// ```
+    pub fn component_mul(&self, other: &Color) -> Color {
+        Color {
+            r: self.r * other.r,
+            g: self.g * other.g,
+            b: self.b * other.b,
+        }
+    }
+
+    /// Linearly interpolates between this color and another
+    ///
+    /// A `t` of 0.0 gives this color, and a `t` of 1.0 gives the other color.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let black = ray_tracer::color::Color::new(0.0, 0.0, 0.0);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    ///
+    /// assert_eq!(black.lerp(&white, 0.25), ray_tracer::color::Color::new(0.25, 0.25, 0.25));
+    /// ```
+    pub fn lerp(&self, other: &Color, t: f64) -> Color {
+        self.mul(1.0 - t).add(&other.mul(t))
+    }
+
+    /// Returns the luminance of this color, its brightness as seen by the eye, using the
+    /// Rec. 709 weights for each component
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// assert!((white.luminance() - 1.0).abs() < 1e-10);
+    ///
+    /// // Green looks brighter than red, which looks brighter than blue
+    /// let red = ray_tracer::color::Color::new(1.0, 0.0, 0.0).luminance();
+    /// let green = ray_tracer::color::Color::new(0.0, 1.0, 0.0).luminance();
+    /// let blue = ray_tracer::color::Color::new(0.0, 0.0, 1.0).luminance();
+    /// assert!(green > red && red > blue);
+    /// ```
+    pub fn luminance(&self) -> f64 {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+
+    /// Clamps each component of this color to the given range
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let color = ray_tracer::color::Color::new(1.5, 0.5, -0.5);
+    ///
+    /// assert_eq!(color.clamp(0.0, 1.0), ray_tracer::color::Color::new(1.0, 0.5, 0.0));
+    /// ```
+    pub fn clamp(&self, min: f64, max: f64) -> Color {
+        Color {
+            r: self.r.clamp(min, max),
+            g: self.g.clamp(min, max),
+            b: self.b.clamp(min, max),
+        }
+    }
+
+    /// Returns whether each component of this color is within `epsilon` of the other's
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let color1 = ray_tracer::color::Color::new(0.1 + 0.2, 0.0, 1.0);
+    /// let color2 = ray_tracer::color::Color::new(0.3, 0.0, 1.0);
+    ///
+    /// assert_ne!(color1, color2);
+    /// assert!(color1.approx_eq(&color2, 1e-10));
+    /// ```
+    pub fn approx_eq(&self, other: &Color, epsilon: f64) -> bool {
+        (self.r - other.r).abs() <= epsilon
+            && (self.g - other.g).abs() <= epsilon
+            && (self.b - other.b).abs() <= epsilon
+    }
+}
+
+/// Tone maps a color with the Reinhard operator, mapping each component _c_ to _c / (1 + c)_
+///
+/// This compresses any brightness into the range 0.0 to 1.0, rolling off bright highlights
+/// gradually instead of clamping them, while dark colors are barely changed.
+///
+/// # Example
+///
+/// ```
+/// let bright = ray_tracer::color::reinhard(ray_tracer::color::Color::new(100.0, 9.0, 1.0));
+/// assert!(bright.r < 1.0 && bright.r > 0.99);
+/// assert_eq!(bright.g, 0.9);
+/// assert_eq!(bright.b, 0.5);
+///
+/// let mid_gray = ray_tracer::color::reinhard(ray_tracer::color::Color::new(0.18, 0.18, 0.18));
+/// assert!(mid_gray.approx_eq(&ray_tracer::color::Color::new(0.18, 0.18, 0.18), 0.03));
+/// ```
+pub fn reinhard(color: Color) -> Color {
+    let map = |c: f64| c / (1.0 + c);
+    Color::new(map(color.r), map(color.g), map(color.b))
+}
+
+/// The ways of mapping linear colors of any brightness down to displayable colors
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ToneMapping {
+    /// Colors are left unchanged, so anything brighter than 1.0 is clamped when displayed
+    #[default]
+    None,
+    /// Colors are compressed with the Reinhard operator
+    Reinhard,
+}
+
+impl ToneMapping {
+    /// Tone maps the given color
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let color = ray_tracer::color::Color::new(3.0, 1.0, 0.0);
+    ///
+    /// assert_eq!(ray_tracer::color::ToneMapping::None.apply(color.clone()), color);
+    /// assert_eq!(
+    ///     ray_tracer::color::ToneMapping::Reinhard.apply(color),
+    ///     ray_tracer::color::Color::new(0.75, 0.5, 0.0)
+    /// );
+    /// ```
+    pub fn apply(&self, color: Color) -> Color {
+        match self {
+            ToneMapping::None => color,
+            ToneMapping::Reinhard => reinhard(color),
+        }
+    }
+}
+
+/// Gamma corrects a color with linear components and converts it into an opaque 8-bit color
+///
+/// Each component is clamped to 0.0 to 1.0 and raised to the power of _1 / gamma_ before it is
+/// converted. Lighting is calculated with linear components, but displays expect them to be
+/// gamma encoded, so without this the image looks too dark. A gamma of 2.0 takes the square
+/// root of each component, and a gamma of 1.0 leaves them unchanged.
+///
+/// # Example
+///
+/// ```
+/// let linear = ray_tracer::color::Color::new(0.25, 1.0, 0.0);
+///
+/// let srgb = ray_tracer::color::to_srgb(linear.clone(), 2.0);
+/// assert_eq!(srgb, sdl2::pixels::Color::RGB(128, 255, 0));
+///
+/// let unchanged = ray_tracer::color::to_srgb(linear.clone(), 1.0);
+/// assert_eq!(unchanged, sdl2::pixels::Color::from(linear));
+/// ```
+pub fn to_srgb(color: Color, gamma: f64) -> pixels::Color {
+    let color = color.clamp(0.0, 1.0);
+    let exponent = 1.0 / gamma;
+    pixels::Color::from(Color::new(
+        color.r.powf(exponent),
+        color.g.powf(exponent),
+        color.b.powf(exponent),
+    ))
+}
+
+/// Converts an 8-bit color into a color with components from 0.0 to 1.0
+///
+/// The alpha channel is ignored.
+///
+/// # Example
+///
+/// ```
+/// let sdl_color = sdl2::pixels::Color::RGB(255, 0, 51);
+/// let color = ray_tracer::color::Color::from(sdl_color);
+///
+/// assert_eq!(color, ray_tracer::color::Color::new(1.0, 0.0, 0.2));
+/// ```
+impl From<pixels::Color> for Color {
+    fn from(color: pixels::Color) -> Color {
+        Color {
+            r: f64::from(color.r) / 255.0,
+            g: f64::from(color.g) / 255.0,
+            b: f64::from(color.b) / 255.0,
+        }
+    }
+}
+
+/// Converts a color into an opaque 8-bit color
+///
+/// Components outside of 0.0 to 1.0 are clamped, and the rest are rounded to
+/// the nearest 8-bit value, so converting an 8-bit color to a Color and back
+/// gives the same 8-bit color.
+///
+/// # Example
+///
+/// ```
+/// let color = ray_tracer::color::Color::new(1.5, 0.5, -0.5);
+/// let sdl_color = sdl2::pixels::Color::from(color);
+/// assert_eq!(sdl_color, sdl2::pixels::Color::RGBA(255, 128, 0, 255));
+///
+/// for value in 0..=255 {
+///     let sdl_color = sdl2::pixels::Color::RGB(value, 255 - value, value / 2);
+///     let color = ray_tracer::color::Color::from(sdl_color);
+///     assert_eq!(sdl2::pixels::Color::from(color), sdl_color);
+/// }
+/// ```
+impl From<Color> for pixels::Color {
+    fn from(color: Color) -> pixels::Color {
+        let color = color.clamp(0.0, 1.0).mul(255.0);
+        pixels::Color::RGB(
+            color.r.round() as u8,
+            color.g.round() as u8,
+            color.b.round() as u8,
+        )
+    }
+}
+
+impl AddAssign<&Color> for Color {
+    fn add_assign(&mut self, other: &Color) {
+        self.r += other.r;
+        self.g += other.g;
+        self.b += other.b;
+    }
+}
+
+impl AddAssign<Color> for Color {
+    fn add_assign(&mut self, other: Color) {
+        *self += &other;
+    }
+}
+
+/// Adds up colors, such as the light arriving from several light sources
+///
+/// # Example
+///
+/// ```
+/// let colors = vec![
+///     ray_tracer::color::Color::new(0.5, 0.0, 0.0),
+///     ray_tracer::color::Color::new(0.25, 0.5, 0.0),
+/// ];
+/// let total: ray_tracer::color::Color = colors.into_iter().sum();
+///
+/// assert_eq!(total, ray_tracer::color::Color::new(0.75, 0.5, 0.0));
+/// ```
+impl Sum<Color> for Color {
+    fn sum<I: Iterator<Item = Color>>(iter: I) -> Color {
+        iter.fold(Color::default(), |total, color| total.add(&color))
+    }
+}
+
+impl<'a> Sum<&'a Color> for Color {
+    fn sum<I: Iterator<Item = &'a Color>>(iter: I) -> Color {
+        iter.fold(Color::default(), |total, color| total.add(color))
+    }
+}
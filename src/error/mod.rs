@@ -0,0 +1,115 @@
+//! This module defines the errors that can stop the ray tracer from running
+
+use sdl2::render::{TextureValueError, UpdateTextureError};
+use sdl2::video::WindowBuildError;
+use sdl2::IntegerOrSdlError;
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// An error from setting up or drawing to the ray tracer's window, or saving an image
+#[derive(Debug)]
+pub enum RayTracerError {
+    /// SDL, one of its subsystems, or the event pump could not be initialized, or drawing failed
+    Sdl(String),
+    /// The window could not be created
+    Window(WindowBuildError),
+    /// The canvas for drawing to the window could not be created
+    Canvas(IntegerOrSdlError),
+    /// The texture rendered frames are copied onto could not be created
+    Texture(TextureValueError),
+    /// A rendered frame could not be copied onto the texture
+    TextureUpdate(UpdateTextureError),
+    /// An image file could not be written
+    Io(io::Error),
+    /// An image could not be encoded and saved by SDL_image
+    Image(String),
+}
+
+impl fmt::Display for RayTracerError {
+    /// Formats the error, saying which step failed and why
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let error: ray_tracer::error::RayTracerError =
+    ///     sdl2::video::WindowBuildError::SdlError("No available video device".to_string()).into();
+    ///
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "could not create window: SDL error: No available video device"
+    /// );
+    ///
+    /// let error: ray_tracer::error::RayTracerError =
+    ///     sdl2::video::WindowBuildError::WidthOverflows(u32::MAX).into();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "could not create window: Window width (4294967295) is too high."
+    /// );
+    ///
+    /// let error: ray_tracer::error::RayTracerError =
+    ///     std::io::Error::new(std::io::ErrorKind::PermissionDenied, "permission denied").into();
+    /// assert_eq!(error.to_string(), "could not write image: permission denied");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RayTracerError::Sdl(e) => write!(f, "SDL error: {}", e),
+            RayTracerError::Window(e) => write!(f, "could not create window: {}", e),
+            RayTracerError::Canvas(e) => write!(f, "could not create canvas: {}", e),
+            RayTracerError::Texture(e) => write!(f, "could not create texture: {}", e),
+            RayTracerError::TextureUpdate(e) => write!(f, "could not update texture: {}", e),
+            RayTracerError::Io(e) => write!(f, "could not write image: {}", e),
+            RayTracerError::Image(e) => write!(f, "could not save image: {}", e),
+        }
+    }
+}
+
+impl Error for RayTracerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RayTracerError::Sdl(_) | RayTracerError::Image(_) => None,
+            RayTracerError::Window(e) => Some(e),
+            RayTracerError::Canvas(e) => Some(e),
+            RayTracerError::Texture(e) => Some(e),
+            RayTracerError::TextureUpdate(e) => Some(e),
+            RayTracerError::Io(e) => Some(e),
+        }
+    }
+}
+
+impl From<String> for RayTracerError {
+    fn from(e: String) -> RayTracerError {
+        RayTracerError::Sdl(e)
+    }
+}
+
+impl From<WindowBuildError> for RayTracerError {
+    fn from(e: WindowBuildError) -> RayTracerError {
+        RayTracerError::Window(e)
+    }
+}
+
+impl From<IntegerOrSdlError> for RayTracerError {
+    fn from(e: IntegerOrSdlError) -> RayTracerError {
+        RayTracerError::Canvas(e)
+    }
+}
+
+impl From<TextureValueError> for RayTracerError {
+    fn from(e: TextureValueError) -> RayTracerError {
+        RayTracerError::Texture(e)
+    }
+}
+
+impl From<UpdateTextureError> for RayTracerError {
+    fn from(e: UpdateTextureError) -> RayTracerError {
+        RayTracerError::TextureUpdate(e)
+    }
+}
+
+impl From<io::Error> for RayTracerError {
+    fn from(e: io::Error) -> RayTracerError {
+        RayTracerError::Io(e)
+    }
+}
@@ -0,0 +1,69 @@
+//! This module defines fog, which hides distant objects in a uniform haze
+
+use super::color::Color;
+
+/// The struct representing a uniform fog filling the scene
+///
+/// Contains the Color of the fog, and its density, which sets how quickly
+/// light passing through it is replaced by the fog's color.
+///
+/// A surface seen through a distance _d_ of fog is blended towards the fog's
+/// color by _1 - e^(-density * d)_, the fraction of its light scattered away
+/// on the way.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Fog {
+    pub color: Color,
+    pub density: f64,
+}
+
+impl Fog {
+    /// Creates a new fog with the given color and density
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let gray = ray_tracer::color::Color::new(0.5, 0.5, 0.5);
+    /// let fog = ray_tracer::fog::Fog::new(gray.clone(), 0.1);
+    ///
+    /// assert_eq!(fog.color, gray);
+    /// assert_eq!(fog.density, 0.1);
+    /// ```
+    pub fn new(color: Color, density: f64) -> Fog {
+        Fog { color, density }
+    }
+
+    /// Returns how much of the color of a surface the given distance away is replaced by the
+    /// fog, from 0.0 to 1.0
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let fog = ray_tracer::fog::Fog::new(ray_tracer::color::Color::new(0.5, 0.5, 0.5), 0.1);
+    ///
+    /// assert_eq!(fog.factor(0.0), 0.0);
+    /// assert!((fog.factor(10.0) - (1.0 - (-1.0f64).exp())).abs() < 1e-12);
+    /// assert!(fog.factor(20.0) > fog.factor(10.0));
+    /// ```
+    pub fn factor(&self, distance: f64) -> f64 {
+        1.0 - (-self.density * distance).exp()
+    }
+
+    /// Returns the given color of a surface, seen through the given distance of this fog
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let gray = ray_tracer::color::Color::new(0.5, 0.5, 0.5);
+    /// let fog = ray_tracer::fog::Fog::new(gray.clone(), 0.1);
+    /// let red = ray_tracer::color::Color::new(1.0, 0.0, 0.0);
+    ///
+    /// // Very near surfaces show almost no fog
+    /// assert!(fog.apply(&red, 1e-3).approx_eq(&red, 1e-3));
+    ///
+    /// // Very distant surfaces are nearly the color of the fog
+    /// assert!(fog.apply(&red, 1e3).approx_eq(&gray, 1e-3));
+    /// ```
+    pub fn apply(&self, color: &Color, distance: f64) -> Color {
+        color.lerp(&self.color, self.factor(distance))
+    }
+}
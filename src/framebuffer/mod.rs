@@ -0,0 +1,115 @@
+//! This module defines a Framebuffer that rendered pixels are stored in
+
+use super::color::{to_srgb, Color};
+
+/// The struct representing a framebuffer
+///
+/// Contains the width and height of the image in pixels, and the linear Color
+/// of each pixel, row by row from the top left.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Framebuffer {
+    pub width: u32,
+    pub height: u32,
+    pixels: Vec<Color>,
+}
+
+impl Framebuffer {
+    /// Creates a new framebuffer of the given size, with every pixel black
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let framebuffer = ray_tracer::framebuffer::Framebuffer::new(4, 3);
+    ///
+    /// assert_eq!(framebuffer.width, 4);
+    /// assert_eq!(framebuffer.height, 3);
+    /// assert_eq!(framebuffer.pixels().len(), 12);
+    /// ```
+    pub fn new(width: u32, height: u32) -> Framebuffer {
+        Framebuffer {
+            width,
+            height,
+            pixels: vec![Color::default(); (width * height) as usize],
+        }
+    }
+
+    /// Returns the color of the pixel at the given column and row, or "None" if it is outside of
+    /// the framebuffer
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let framebuffer = ray_tracer::framebuffer::Framebuffer::new(4, 3);
+    ///
+    /// assert_eq!(framebuffer.get(3, 2), Some(&ray_tracer::color::Color::default()));
+    /// assert_eq!(framebuffer.get(4, 0), None);
+    /// assert_eq!(framebuffer.get(0, 3), None);
+    /// ```
+    pub fn get(&self, x: u32, y: u32) -> Option<&Color> {
+        if x < self.width && y < self.height {
+            self.pixels.get((y * self.width + x) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Sets the color of the pixel at the given column and row
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pixel is outside of the framebuffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut framebuffer = ray_tracer::framebuffer::Framebuffer::new(4, 3);
+    /// let red = ray_tracer::color::Color::new(1.0, 0.0, 0.0);
+    ///
+    /// framebuffer.set(1, 2, red.clone());
+    /// assert_eq!(framebuffer.get(1, 2), Some(&red));
+    /// assert_eq!(framebuffer.pixels()[9], red);
+    /// ```
+    ///
+    /// ```should_panic
+    /// let mut framebuffer = ray_tracer::framebuffer::Framebuffer::new(4, 3);
+    ///
+    /// framebuffer.set(4, 0, ray_tracer::color::Color::default());
+    /// ```
+    pub fn set(&mut self, x: u32, y: u32, color: Color) {
+        assert!(
+            x < self.width && y < self.height,
+            "pixel ({}, {}) is outside of the {}x{} framebuffer",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        self.pixels[(y * self.width + x) as usize] = color;
+    }
+
+    /// Returns the colors of all of the pixels, row by row from the top left
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    /// Gamma corrects every pixel and returns them as 8-bit RGB bytes, row by row from the top
+    /// left
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut framebuffer = ray_tracer::framebuffer::Framebuffer::new(2, 1);
+    /// framebuffer.set(1, 0, ray_tracer::color::Color::new(0.25, 1.0, 0.0));
+    ///
+    /// assert_eq!(framebuffer.to_rgb_bytes(2.0), vec![0, 0, 0, 128, 255, 0]);
+    /// ```
+    pub fn to_rgb_bytes(&self, gamma: f64) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|color| {
+                let color = to_srgb(color.clone(), gamma);
+                vec![color.r, color.g, color.b]
+            })
+            .collect()
+    }
+}
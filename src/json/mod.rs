@@ -0,0 +1,427 @@
+//! This module parses JSON documents, and converts between them and the ray tracer's types
+
+use std::error::Error;
+use std::fmt;
+
+/// A value in a JSON document
+///
+/// Objects keep their members in the order they were written.
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+/// An error from parsing a JSON document, or from converting a JSON value into another type
+#[derive(Clone, Debug, PartialEq)]
+pub enum JsonError {
+    /// The document is not valid JSON, at the given byte offset
+    Syntax { position: usize, message: String },
+    /// The value is valid JSON, but does not describe the type it is being converted into
+    Invalid(String),
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonError::Syntax { position, message } => {
+                write!(f, "invalid JSON at byte {}: {}", position, message)
+            }
+            JsonError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for JsonError {}
+
+/// A type that can be created from a JSON value
+pub trait FromJson: Sized {
+    /// Converts the given JSON value into this type
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError>;
+}
+
+/// A type that can be converted into a JSON value
+pub trait ToJson {
+    /// Converts this into a JSON value
+    fn to_json(&self) -> JsonValue;
+}
+
+impl FromJson for f64 {
+    fn from_json(value: &JsonValue) -> Result<f64, JsonError> {
+        value
+            .as_f64()
+            .ok_or_else(|| JsonError::Invalid(format!("expected a number, found {}", value)))
+    }
+}
+
+impl FromJson for String {
+    fn from_json(value: &JsonValue) -> Result<String, JsonError> {
+        value
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| JsonError::Invalid(format!("expected a string, found {}", value)))
+    }
+}
+
+impl ToJson for f64 {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Number(*self)
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.clone())
+    }
+}
+
+impl JsonValue {
+    /// Returns the member of this object with the given key, or "None" if this is not an object
+    /// or has no such member
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let value = ray_tracer::json::parse(r#"{"radius": 2.5}"#).unwrap();
+    ///
+    /// assert_eq!(value.get("radius"), Some(&ray_tracer::json::JsonValue::Number(2.5)));
+    /// assert_eq!(value.get("color"), None);
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(members) => members
+                .iter()
+                .find(|(member_key, _)| member_key == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Returns this value as a number, or "None" if it is not a number
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns this value as a string, or "None" if it is not a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of this array, or "None" if it is not an array
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    /// Converts the member of this object with the given key, or returns "None" if there is no
+    /// such member
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let value = ray_tracer::json::parse(r#"{"radius": 2.5, "name": 3}"#).unwrap();
+    ///
+    /// assert_eq!(value.field::<f64>("radius"), Ok(Some(2.5)));
+    /// assert_eq!(value.field::<f64>("height"), Ok(None));
+    /// assert!(value.field::<String>("name").is_err());
+    /// ```
+    pub fn field<T: FromJson>(&self, key: &str) -> Result<Option<T>, JsonError> {
+        match self.get(key) {
+            Some(value) => T::from_json(value)
+                .map(Some)
+                .map_err(|e| JsonError::Invalid(format!("\"{}\": {}", key, e))),
+            None => Ok(None),
+        }
+    }
+
+    /// Converts the member of this object with the given key, which must be present
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let value = ray_tracer::json::parse(r#"{"radius": 2.5}"#).unwrap();
+    ///
+    /// assert_eq!(value.required_field::<f64>("radius"), Ok(2.5));
+    /// assert_eq!(
+    ///     value.required_field::<f64>("height").unwrap_err().to_string(),
+    ///     "missing \"height\""
+    /// );
+    /// ```
+    pub fn required_field<T: FromJson>(&self, key: &str) -> Result<T, JsonError> {
+        self.field(key)?
+            .ok_or_else(|| JsonError::Invalid(format!("missing \"{}\"", key)))
+    }
+}
+
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(b) => write!(f, "{}", b),
+            JsonValue::Number(n) => write!(f, "{}", n),
+            JsonValue::String(s) => write_string(f, s),
+            JsonValue::Array(elements) => {
+                write!(f, "[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, "]")
+            }
+            JsonValue::Object(members) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// Writes the given string as a quoted JSON string, escaping characters where needed
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// Parses the given JSON document
+///
+/// # Example
+///
+/// ```
+/// use ray_tracer::json::JsonValue;
+///
+/// let value = ray_tracer::json::parse(r#"{"a": [1, -2.5e1, true, null], "b": "x\ny"}"#).unwrap();
+///
+/// assert_eq!(
+///     value,
+///     JsonValue::Object(vec![
+///         (
+///             "a".to_string(),
+///             JsonValue::Array(vec![
+///                 JsonValue::Number(1.0),
+///                 JsonValue::Number(-25.0),
+///                 JsonValue::Bool(true),
+///                 JsonValue::Null,
+///             ])
+///         ),
+///         ("b".to_string(), JsonValue::String("x\ny".to_string())),
+///     ])
+/// );
+/// // Printing a value writes it back out as JSON
+/// assert_eq!(value.to_string(), r#"{"a":[1,-25,true,null],"b":"x\ny"}"#);
+///
+/// assert!(ray_tracer::json::parse("[1, 2").is_err());
+/// assert!(ray_tracer::json::parse("{} extra").is_err());
+/// ```
+pub fn parse(source: &str) -> Result<JsonValue, JsonError> {
+    let mut parser = Parser {
+        source: source.as_bytes(),
+        position: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.position < parser.source.len() {
+        return Err(parser.error("unexpected text after the document"));
+    }
+    Ok(value)
+}
+
+/// A recursive descent parser over the bytes of a JSON document
+struct Parser<'a> {
+    source: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> JsonError {
+        JsonError::Syntax {
+            position: self.position,
+            message: message.to_string(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.source.get(self.position).cloned()
+    }
+
+    /// Skips whitespace, then consumes the given byte if it comes next
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(byte) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), JsonError> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", byte as char)))
+        }
+    }
+
+    fn keyword(&mut self, word: &str, value: JsonValue) -> Result<JsonValue, JsonError> {
+        if self.source[self.position..].starts_with(word.as_bytes()) {
+            self.position += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("unexpected character"))
+        }
+    }
+
+    fn value(&mut self) -> Result<JsonValue, JsonError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => self.string().map(JsonValue::String),
+            Some(b't') => self.keyword("true", JsonValue::Bool(true)),
+            Some(b'f') => self.keyword("false", JsonValue::Bool(false)),
+            Some(b'n') => self.keyword("null", JsonValue::Null),
+            Some(b'-') | Some(b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of document")),
+        }
+    }
+
+    fn object(&mut self) -> Result<JsonValue, JsonError> {
+        self.expect(b'{')?;
+        let mut members = vec![];
+        if self.eat(b'}') {
+            return Ok(JsonValue::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(b':')?;
+            members.push((key, self.value()?));
+            if self.eat(b'}') {
+                return Ok(JsonValue::Object(members));
+            }
+            self.expect(b',')?;
+        }
+    }
+
+    fn array(&mut self) -> Result<JsonValue, JsonError> {
+        self.expect(b'[')?;
+        let mut elements = vec![];
+        if self.eat(b']') {
+            return Ok(JsonValue::Array(elements));
+        }
+        loop {
+            elements.push(self.value()?);
+            if self.eat(b']') {
+                return Ok(JsonValue::Array(elements));
+            }
+            self.expect(b',')?;
+        }
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        if self.peek() != Some(b'"') {
+            return Err(self.error("expected a string"));
+        }
+        self.position += 1;
+        let mut bytes = vec![];
+        loop {
+            let byte = self
+                .peek()
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.position += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escape = self
+                        .peek()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.position += 1;
+                    let c = match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                }
+                byte => bytes.push(byte),
+            }
+        }
+        String::from_utf8(bytes).map_err(|_| self.error("invalid UTF-8 in string"))
+    }
+
+    /// Parses the four hex digits after "\u", which must not be half of a surrogate pair
+    fn unicode_escape(&mut self) -> Result<char, JsonError> {
+        let digits = self
+            .source
+            .get(self.position..self.position + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.position += 4;
+        std::char::from_u32(digits).ok_or_else(|| self.error("unsupported unicode escape"))
+    }
+
+    fn number(&mut self) -> Result<JsonValue, JsonError> {
+        let start = self.position;
+        while let Some(b'-') | Some(b'+') | Some(b'.') | Some(b'e') | Some(b'E')
+        | Some(b'0'..=b'9') = self.peek()
+        {
+            self.position += 1;
+        }
+        std::str::from_utf8(&self.source[start..self.position])
+            .ok()
+            .and_then(|text| text.parse::<f64>().ok())
+            .map(JsonValue::Number)
+            .ok_or_else(|| JsonError::Syntax {
+                position: start,
+                message: "invalid number".to_string(),
+            })
+    }
+}
@@ -1,84 +1,114 @@
-use sdl2::event::Event;
+use sdl2::event::{Event, WindowEvent};
 use sdl2::keyboard::Keycode;
 use sdl2::pixels;
-
-use sdl2::gfx::primitives::DrawRenderer;
+use sdl2::rect::Rect;
 
 use std::f64::consts::PI;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+pub mod aabb;
+pub mod accel;
+pub mod background;
+pub mod camera;
+pub mod color;
+pub mod error;
+pub mod fog;
+pub mod framebuffer;
+pub mod json;
+pub mod light;
+pub mod logging;
 pub mod material;
+pub mod math;
 pub mod object;
+pub mod random;
 pub mod ray;
+pub mod render;
+pub mod scene;
+pub mod texture;
 pub mod vector3d;
+pub mod window;
 
+use background::Background;
+use camera::{Camera, ProjectionMode};
+use color::{Color, ToneMapping};
+use error::RayTracerError;
+use framebuffer::Framebuffer;
+use light::{AmbientLight, PointLight};
 use material::Material;
+use object::checker_plane::CheckerPlane;
 use object::sphere::Sphere;
-use object::Object;
-use ray::Ray;
+use render::accumulator::Accumulator;
+use render::adaptive::AdaptiveSampler;
+use scene::Scene;
 use vector3d::Vector3;
+use window::WindowConfig;
 
-/// Represents the current projection mode, either Ortho or Perspective.
-#[derive(PartialEq)]
-enum ProjectionMode {
-    Ortho,
-    Perspective,
-}
-
-/// Creates the window for the ray tracer.
+/// Creates the window for the ray tracer, with the given settings.
 fn create_window(
     context: &sdl2::Sdl,
-    title: &str,
-    width: u32,
-    height: u32,
-) -> Result<sdl2::video::Window, sdl2::video::WindowBuildError> {
-    let video_subsystem = context.video().unwrap();
-
-    video_subsystem
-        .window(title, width, height)
-        .position_centered()
-        .opengl()
-        .build()
-}
-
-/// Runs the raytracer with the given width and height.
-pub fn run(width: u32, height: u32) {
-    let sdl_context = sdl2::init().unwrap();
-    let window = create_window(&sdl_context, "Ray Tracer", width, height).unwrap();
-
-    let mut canvas = window.into_canvas().build().unwrap();
+    config: &WindowConfig,
+) -> Result<sdl2::video::Window, RayTracerError> {
+    let video_subsystem = context.video()?;
 
-    canvas.set_draw_color(pixels::Color::RGBA(0, 0, 0, 255));
-    // Create texture to draw onto (to avoid double buffer problem)
-    let creator = canvas.texture_creator();
-    let mut texture = creator
-        .create_texture_target(pixels::PixelFormatEnum::RGBA8888, width, height)
-        .unwrap();
-
-    // Clear texture
-    canvas
-        .with_texture_canvas(&mut texture, |texture_canvas| {
-            texture_canvas.clear();
-        })
-        .unwrap();
+    let mut builder = video_subsystem.window(&config.title, config.width, config.height);
+    builder.position_centered().opengl();
+    if config.resizable {
+        builder.resizable();
+    }
+    let window = builder.build()?;
+    Ok(window)
+}
 
+/// Creates the scene shown by the ray tracer: a row of colorful spheres on a checkered floor,
+/// under a sky.
+///
+/// Each sphere is logged at the info level as it is created.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+///
+/// struct CountInfo(Arc<Mutex<usize>>);
+///
+/// impl ray_tracer::logging::Logger for CountInfo {
+///     fn log(&self, record: &ray_tracer::logging::Record) {
+///         if record.level == ray_tracer::logging::Level::Info {
+///             *self.0.lock().unwrap() += 1;
+///         }
+///     }
+/// }
+///
+/// let count = Arc::new(Mutex::new(0));
+/// let logger = CountInfo(count.clone());
+/// ray_tracer::logging::set_logger(Box::new(logger), ray_tracer::logging::Level::Debug);
+///
+/// let scene = ray_tracer::demo_scene();
+/// assert_eq!(*count.lock().unwrap(), 9);
+/// // The spheres and the floor
+/// assert_eq!(scene.bvh.objects().len(), 10);
+/// ```
+pub fn demo_scene() -> Scene {
     const NUM_OBJECTS: usize = 10;
-    let mut objects: Vec<Box<dyn Object>> = vec![];
+    let mut scene = Scene {
+        background: Background::sky(),
+        ..Default::default()
+    };
     for i in 1..NUM_OBJECTS {
-        let red = ((i as f64 * 0.25 * 3.1415926535 * 10.0).sin() + 1.0) / 2.0 * 255.0;
-        let green =
-            ((i as f64 * 0.5 * 3.1415926535 * 10.0 + 5.0 * 3.1415926535).sin() + 1.0) / 2.0 * 255.0;
-        let blue = ((i as f64 * 0.75 * 3.1415926535 * 10.0 + 10.0 * 3.1415926535).sin() + 1.0)
-            / 2.0
-            * 255.0;
+        let red = ((i as f64 * 0.25 * PI * 10.0).sin() + 1.0) / 2.0;
+        let green = ((i as f64 * 0.5 * PI * 10.0 + 5.0 * PI).sin() + 1.0) / 2.0;
+        let blue = ((i as f64 * 0.75 * PI * 10.0 + 10.0 * PI).sin() + 1.0) / 2.0;
         // Set up sphere model
         let sphere = Sphere::new(
             Vector3::new(2.0 * (i as f64) - 5.0, 0.0, -3.0 - (i as f64)),
             1.0,
             Material {
-                color: pixels::Color::RGBA(red as u8, green as u8, blue as u8, 255),
+                color: Color::new(red, green, blue),
+                specular: 0.5,
+                ..Default::default()
             },
         );
-        println!(
+        info!(
             "Creating sphere at ({}, {}, {}) with radius {} and color ({}, {}, {})",
             sphere.pos.x,
             sphere.pos.y,
@@ -89,151 +119,227 @@ pub fn run(width: u32, height: u32) {
             sphere.material.color.b
         );
 
-        objects.push(Box::new(sphere));
+        scene.add(Box::new(sphere));
     }
 
-    let mut fovy = 90.0; // Degrees
-    let mut fovx = 90.0; // Degrees
+    // Ground plane underneath the spheres
+    let mut ground = CheckerPlane::new(
+        Vector3::new(0.0, -1.0, 0.0),
+        Vector3::new(0.0, 1.0, 0.0),
+        Color::new(0.25, 0.25, 0.25),
+        Color::new(0.75, 0.75, 0.75),
+    );
+    ground.plane.material.reflectivity = 0.3;
+    scene.add(Box::new(ground));
+
+    scene.build_bvh();
+
+    scene.ambient = AmbientLight::new(Color::new(1.0, 1.0, 1.0), 0.1);
+    scene.add_light(Box::new(PointLight::new(
+        Vector3::new(-5.0, 5.0, 0.0),
+        Color::new(1.0, 1.0, 1.0),
+        75.0,
+    )));
+
+    scene
+}
+
+/// Runs the raytracer in a window with the given settings, until the window is closed.
+///
+/// If the window is resizable, frames are rendered at its new size after each resize.
+///
+/// Returns an error if the window cannot be set up, a frame cannot be drawn to it, or a
+/// screenshot cannot be saved.
+pub fn run(mut config: WindowConfig) -> Result<(), RayTracerError> {
+    let sdl_context = sdl2::init()?;
+    let window = create_window(&sdl_context, &config)?;
+
+    let canvas_builder = window.into_canvas();
+    let canvas_builder = if config.vsync {
+        canvas_builder.present_vsync()
+    } else {
+        canvas_builder
+    };
+    let mut canvas = canvas_builder.build()?;
+
+    canvas.set_draw_color(pixels::Color::RGBA(0, 0, 0, 255));
+    // Create texture to copy rendered frames onto (to avoid double buffer problem)
+    let creator = canvas.texture_creator();
+    let mut texture = creator.create_texture_streaming(
+        pixels::PixelFormatEnum::RGB24,
+        config.width,
+        config.height,
+    )?;
+
+    // How far the camera moves for each key press
+    const MOVE_STEP: f64 = 0.25;
+    // How far the camera turns for each pixel the mouse is dragged, in radians
+    const TURN_PER_PIXEL: f64 = 0.005;
+    // The fractions of the window's resolution that frames can be rendered at
+    const RESOLUTION_SCALES: [f64; 3] = [0.25, 0.5, 1.0];
+    let scene = demo_scene();
 
-    let camera_pos = Vector3::new(0.0, 0.0, 0.0);
-    let camera_dir = Vector3::new(0.0, 0.0, -1.0);
+    let mut camera = Camera {
+        aspect: config.aspect(),
+        ..Default::default()
+    };
 
-    let blank_color = pixels::Color::RGBA(0, 0, 0, 255);
+    let mut tone_mapping = ToneMapping::None;
+    let mut resolution_scale = RESOLUTION_SCALES.len() - 1;
+    let mut last_frame: Option<Framebuffer> = None;
+    // While nothing changes, each frame adds samples at random points in the pixels to the last
+    let mut accumulator = Accumulator::new();
+    let mut last_view = None;
+    let jittered = AdaptiveSampler::new(1, 1, 0.0);
 
-    let mut event_pump = sdl_context.event_pump().unwrap();
-    let mut mode = ProjectionMode::Perspective;
+    let mut event_pump = sdl_context.event_pump()?;
     'main: loop {
         for event in event_pump.poll_iter() {
             match event {
                 Event::Quit { .. } => break 'main,
 
+                // The texture holds full size frames, so it is recreated at the new size
+                Event::Window {
+                    win_event: WindowEvent::Resized(new_width, new_height),
+                    ..
+                } => {
+                    config.resize(new_width, new_height);
+                    camera.aspect = config.aspect();
+                    texture = creator.create_texture_streaming(
+                        pixels::PixelFormatEnum::RGB24,
+                        config.width,
+                        config.height,
+                    )?;
+                    last_frame = None;
+                    debug!("window size = {}x{}", config.width, config.height);
+                }
+
                 Event::KeyDown {
                     keycode: Some(keycode),
                     ..
                 } => match keycode {
                     Keycode::Escape => break 'main,
                     Keycode::P => {
-                        if mode == ProjectionMode::Ortho {
-                            mode = ProjectionMode::Perspective
-                        } else {
-                            mode = ProjectionMode::Ortho
+                        camera.projection = match camera.projection {
+                            ProjectionMode::Perspective => ProjectionMode::Ortho,
+                            ProjectionMode::Ortho => ProjectionMode::Fisheye,
+                            ProjectionMode::Fisheye => ProjectionMode::Equirectangular,
+                            ProjectionMode::Equirectangular => ProjectionMode::Perspective,
                         }
                     }
+                    Keycode::T => {
+                        tone_mapping = match tone_mapping {
+                            ToneMapping::None => ToneMapping::Reinhard,
+                            ToneMapping::Reinhard => ToneMapping::None,
+                        }
+                    }
+                    Keycode::F12 => {
+                        if let Some(ref frame) = last_frame {
+                            let since_epoch = SystemTime::now()
+                                .duration_since(UNIX_EPOCH)
+                                .unwrap_or_default();
+                            let path = render::screenshot_filename(since_epoch);
+                            render::save_framebuffer(frame, &path)?;
+                            info!("Saved screenshot to {}", path);
+                        }
+                    }
+                    Keycode::R => {
+                        resolution_scale = (resolution_scale + 1) % RESOLUTION_SCALES.len();
+                        debug!("resolution scale = {}", RESOLUTION_SCALES[resolution_scale]);
+                    }
                     Keycode::Up => {
-                        fovy += 1.0;
-                        println!("fovy = {}", fovy);
+                        camera.set_fov_y(camera.fov_y + 1.0);
+                        debug!("fov_y = {}", camera.fov_y);
                     }
                     Keycode::Down => {
-                        fovy -= 1.0;
-                        println!("fovy = {}", fovy);
+                        camera.set_fov_y(camera.fov_y - 1.0);
+                        debug!("fov_y = {}", camera.fov_y);
                     }
                     Keycode::Right => {
-                        fovx += 1.0;
-                        println!("fovx = {}", fovx);
+                        camera.set_fov_x(camera.fov_x() + 1.0);
+                        debug!("fov_x = {}", camera.fov_x());
                     }
                     Keycode::Left => {
-                        fovx -= 1.0;
-                        println!("fovx = {}", fovx);
+                        camera.set_fov_x(camera.fov_x() - 1.0);
+                        debug!("fov_x = {}", camera.fov_x());
                     }
+                    Keycode::W => camera.translate(MOVE_STEP, 0.0, 0.0),
+                    Keycode::S => camera.translate(-MOVE_STEP, 0.0, 0.0),
+                    Keycode::D => camera.translate(0.0, MOVE_STEP, 0.0),
+                    Keycode::A => camera.translate(0.0, -MOVE_STEP, 0.0),
+                    Keycode::E => camera.translate(0.0, 0.0, MOVE_STEP),
+                    Keycode::Q => camera.translate(0.0, 0.0, -MOVE_STEP),
                     _ => {}
                 },
+
+                // Dragging with the left mouse button turns the camera
+                Event::MouseMotion {
+                    mousestate,
+                    xrel,
+                    yrel,
+                    ..
+                } if mousestate.left() => {
+                    camera.rotate(
+                        -f64::from(xrel) * TURN_PER_PIXEL,
+                        -f64::from(yrel) * TURN_PER_PIXEL,
+                    );
+                }
                 _ => {}
             }
         }
 
         // Ray Trace!
-        canvas
-            .with_texture_canvas(&mut texture, |texture_canvas| {
-                for dx in 0..width {
-                    for dy in 0..height {
-                        let pos = camera_pos.clone();
-                        let dir = match mode {
-                            ProjectionMode::Ortho => {
-                                let x = ((dx as f64) - (width as f64) / 2.0) / (width as f64);
-                                let y = ((dy as f64) - (height as f64) / 2.0) / (height as f64);
-                                let view_plane_pos = Vector3::new(x, y, 0.0).add(&camera_dir);
-                                view_plane_pos.into_unit()
-                            }
-                            ProjectionMode::Perspective => {
-                                // from https://www.scratchapixel.com/lessons/3d-basic-rendering/ray-tracing-generating-camera-rays/generating-camera-rays
-                                let pixel_x_ndc = (dx as f64 + 0.5f64) / width as f64;
-                                let pixel_y_ndc = (dy as f64 + 0.5f64) / height as f64;
-
-                                let pixel_screen_x = 2.0 * pixel_x_ndc - 1.0;
-                                let pixel_screen_y = 2.0 * pixel_y_ndc - 1.0;
-
-                                //println!("Now rendering screen coords ({}, {})", pixel_screen_x, pixel_screen_y);
-
-                                const DEGREES_TO_RADIANS: f64 = PI / 180.0;
-                                let aspect_ratio = width as f64 / height as f64;
-                                let pixel_camera_x = pixel_screen_x
-                                    * aspect_ratio
-                                    * (fovx / 2.0 as f64 * DEGREES_TO_RADIANS).tan();
-                                let pixel_camera_y =
-                                    pixel_screen_y * (fovy / 2.0 as f64 * DEGREES_TO_RADIANS).tan();
-                                let pixel_camera_space =
-                                    Vector3::new(pixel_camera_x, pixel_camera_y, -1.0);
-                                //println!("\tfinal direction vec (before normalization): {:?}", pixel_camera_space);
-                                pixel_camera_space.into_unit()
-
-                                // TODO in case of moving camera, make sure to transform this point
-                                // into world space before normalizing it!
-                            }
-                        };
-
-                        let r = Ray::new(pos, dir);
-
-                        let mut t: Option<(f64, &Box<dyn Object>)> = None;
-                        for obj in &objects {
-                            if let Some(t0) = obj.ray_intersection(&r) {
-                                match t {
-                                    Some((val, o)) => {
-                                        if t0 < val {
-                                            t = Some((t0, &obj))
-                                        } else {
-                                            t = Some((val, o))
-                                        }
-                                    }
-                                    None => t = Some((t0, &obj)),
-                                }
-                            }
-                        }
-                        if let Some((t, obj)) = t {
-                            let p = r.pos.add(&(r.dir.mul(t)));
-                            let normal = obj.position().sub(&p).into_unit();
-                            let view = p.sub(&camera_pos).into_unit();
-                            let mut proportion = normal.dot(&view);
-                            let (mut red, mut green, mut blue) = obj.material().color.rgb();
-                            if proportion < 0.0 {
-                                proportion = 0.0;
-                            }
-                            red = ((red as f64) * proportion) as u8;
-                            blue = ((blue as f64) * proportion) as u8;
-                            green = ((green as f64) * proportion) as u8;
-                            let color = pixels::Color::RGB(red, green, blue);
-                            texture_canvas.pixel(dx as i16, dy as i16, color).unwrap();
-                        } else {
-                            texture_canvas
-                                .pixel(dx as i16, dy as i16, blank_color)
-                                .unwrap();
-                        }
-                    }
-                }
-            })
-            .unwrap();
+        let (render_width, render_height) = render::scaled_resolution(
+            config.width,
+            config.height,
+            RESOLUTION_SCALES[resolution_scale],
+        );
+        let view = scene::hash::scene_hash(&scene, &camera, render_width, render_height);
+        if last_view != Some(view) {
+            accumulator.reset();
+            last_view = Some(view);
+        }
+        // The first frame samples pixel centers, for a sharp image while moving
+        let sample = if accumulator.frames() == 0 {
+            render::render(
+                &scene,
+                &camera,
+                render_width,
+                render_height,
+                ToneMapping::None,
+                0,
+            )
+        } else {
+            render::render_adaptive(
+                &scene,
+                &camera,
+                render_width,
+                render_height,
+                ToneMapping::None,
+                &jittered,
+                u64::from(accumulator.frames()),
+            )
+        };
+        accumulator.add(&sample);
+        let framebuffer = accumulator.average(tone_mapping);
+        let bytes = framebuffer.to_rgb_bytes(render::GAMMA);
+        // Smaller frames only fill the top left of the texture, and are stretched over the window
+        let frame = Rect::new(0, 0, render_width, render_height);
+        texture.update(frame, &bytes, render_width as usize * 3)?;
+        last_frame = Some(framebuffer);
 
         // The rest of the game loop goes here
         canvas.clear();
-        canvas
-            .copy_ex(
-                &texture, None,  // Source Rect (None = whole texture)
-                None,  // Destination Rect (None = whole canvas)
-                0.0,   // rotation angle
-                None,  // Center of Rotation (None = center of dst, or src if dst is None)
-                false, // flip horizontal?
-                false, // flip vertical?
-            )
-            .unwrap();
+        canvas.copy_ex(
+            &texture, frame, // Source Rect (the part of the texture holding the frame)
+            None,  // Destination Rect (None = whole canvas)
+            0.0,   // rotation angle
+            None,  // Center of Rotation (None = center of dst, or src if dst is None)
+            false, // flip horizontal?
+            false, // flip vertical?
+        )?;
         canvas.present();
     }
+
+    Ok(())
 }
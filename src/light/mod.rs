@@ -0,0 +1,844 @@
+//! This module defines the lights that illuminate a Scene
+
+use std::hash::Hasher;
+
+use super::color::Color;
+use super::scene::hash::SceneHasher;
+use super::vector3d::Vector3;
+
+pub trait Light {
+    /// Returns the unit vector pointing from the given point towards this light.
+    fn direction_from(&self, point: &Vector3) -> Vector3;
+
+    /// Returns the distance from the given point to this light.
+    ///
+    /// Lights that are infinitely far away return infinity, so that shadow rays towards them
+    /// are unbounded.
+    fn distance_from(&self, point: &Vector3) -> f64;
+
+    /// Returns the color of this light, with red, green, and blue components from 0.0 to 1.0.
+    fn color(&self) -> &Color;
+
+    /// Returns the intensity of this light.
+    fn intensity(&self) -> f64;
+
+    /// Feeds every parameter of this light that changes how it lights a scene to the given
+    /// hasher, so that `scene::hash::scene_hash` can tell when a scene has changed.
+    ///
+    /// By default this only feeds the light's color and intensity. Lights should feed all of
+    /// their parameters instead.
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write_color(self.color());
+        hasher.write_f64(self.intensity());
+    }
+
+    /// Returns the fraction of this light's intensity that reaches the given point.
+    ///
+    /// By default lights do not fall off with distance.
+    fn attenuation(&self, _point: &Vector3) -> f64 {
+        1.0
+    }
+
+    /// Returns the points on this light that shadow rays are traced towards, for lights with
+    /// an area that cast soft shadows.
+    ///
+    /// By default lights are a single point, or infinitely far away, so this returns "None" and
+    /// a single shadow ray is traced along `direction_from`.
+    fn sample_points(&self) -> Option<Vec<Vector3>> {
+        None
+    }
+
+    /// Calculates the Lambertian diffuse lighting factor for a surface at the given point with
+    /// the given unit normal.
+    ///
+    /// This is _max(0, n·l) * intensity * attenuation_, where _n_ is the normal and _l_ is the
+    /// direction from the point towards the light. Surfaces facing away from the light receive
+    /// no light.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::light::Light;
+    ///
+    /// let light = ray_tracer::light::PointLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     0.8,
+    /// );
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let up = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// assert_eq!(light.diffuse(&point, &up), 0.8);
+    ///
+    /// let down = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// assert_eq!(light.diffuse(&point, &down), 0.0);
+    /// ```
+    fn diffuse(&self, point: &Vector3, normal: &Vector3) -> f64 {
+        normal.dot(&self.direction_from(point)).max(0.0)
+            * self.intensity()
+            * self.attenuation(point)
+    }
+
+    /// Calculates the Phong specular lighting factor for a surface at the given point with the
+    /// given unit normal, seen from the given unit view direction.
+    ///
+    /// This is _max(0, r·v)^shininess * intensity * attenuation_, where _r_ is the direction of
+    /// the light reflected about the normal and _v_ is the direction from the point towards the
+    /// viewer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::light::Light;
+    ///
+    /// let light = ray_tracer::light::PointLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     1.0,
+    /// );
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let normal = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    ///
+    /// // Viewed from where the light reflects to, the highlight is at full strength
+    /// let view = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// assert_eq!(light.specular(&point, &normal, &view, 32.0), 1.0);
+    ///
+    /// // Viewed from a grazing angle, the highlight has nearly vanished
+    /// let grazing = ray_tracer::vector3d::Vector3::new(1.0, 0.1, 0.0).into_unit();
+    /// assert!(light.specular(&point, &normal, &grazing, 32.0) < 0.01);
+    /// ```
+    fn specular(&self, point: &Vector3, normal: &Vector3, view: &Vector3, shininess: f64) -> f64 {
+        let reflected = self.direction_from(point).negate().reflect(normal);
+        reflected.dot(view).max(0.0).powf(shininess) * self.intensity() * self.attenuation(point)
+    }
+}
+
+/// The struct representing a point light
+///
+/// Contains a Vector3 for the light's position, a Color for its color
+/// (with red, green, and blue components from 0.0 to 1.0), and a
+/// f64 intensity that scales the color.
+///
+/// The light falls off with the distance _d_ from the light as
+/// _1 / (constant + linear * d + quadratic * d^2)_.
+#[derive(Debug)]
+pub struct PointLight {
+    pub position: Vector3,
+    pub color: Color,
+    pub intensity: f64,
+    pub constant: f64,
+    pub linear: f64,
+    pub quadratic: f64,
+}
+
+/// The default point light does not fall off, so it stays finite even at its own position
+///
+/// # Example
+///
+/// ```
+/// use ray_tracer::light::Light;
+///
+/// let light = ray_tracer::light::PointLight::default();
+/// assert_eq!(light.attenuation(&light.position), 1.0);
+/// ```
+impl Default for PointLight {
+    fn default() -> Self {
+        PointLight {
+            position: Vector3::default(),
+            color: Color::default(),
+            intensity: 0.0,
+            constant: 1.0,
+            linear: 0.0,
+            quadratic: 0.0,
+        }
+    }
+}
+
+impl PointLight {
+    /// Creates a new point light with the given position, color, and intensity
+    ///
+    /// The light falls off with the inverse square of the distance from it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let position = ray_tracer::vector3d::Vector3::new(0.0, 5.0, 0.0);
+    /// let color = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let light = ray_tracer::light::PointLight::new(position, color, 0.5);
+    ///
+    /// assert_eq!(light.position.y, 5.0);
+    /// assert_eq!(light.intensity, 0.5);
+    /// assert_eq!(light.quadratic, 1.0);
+    /// ```
+    pub fn new(position: Vector3, color: Color, intensity: f64) -> PointLight {
+        PointLight::with_attenuation(position, color, intensity, 0.0, 0.0, 1.0)
+    }
+
+    /// Creates a new point light with the given position, color, intensity, and constant,
+    /// linear, and quadratic attenuation coefficients
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::light::Light;
+    ///
+    /// let position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let color = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let light = ray_tracer::light::PointLight::with_attenuation(position, color, 1.0, 1.0, 0.0, 0.0);
+    ///
+    /// // With only a constant term, the light does not fall off
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, 100.0, 0.0);
+    /// assert_eq!(light.attenuation(&point), 1.0);
+    /// ```
+    pub fn with_attenuation(
+        position: Vector3,
+        color: Color,
+        intensity: f64,
+        constant: f64,
+        linear: f64,
+        quadratic: f64,
+    ) -> PointLight {
+        PointLight {
+            position,
+            color,
+            intensity,
+            constant,
+            linear,
+            quadratic,
+        }
+    }
+}
+
+impl Light for PointLight {
+    /// Returns the unit vector pointing from the given point towards this light
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::light::Light;
+    ///
+    /// let light = ray_tracer::light::PointLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 5.0, 0.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     1.0,
+    /// );
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let direction = light.direction_from(&point);
+    /// assert_eq!(direction, ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0));
+    /// ```
+    fn direction_from(&self, point: &Vector3) -> Vector3 {
+        self.position.sub(point).into_unit()
+    }
+
+    /// Returns the distance from the given point to this light's position
+    fn distance_from(&self, point: &Vector3) -> f64 {
+        self.position.distance(point)
+    }
+
+    /// Returns this light's color
+    fn color(&self) -> &Color {
+        &self.color
+    }
+
+    /// Returns this light's intensity
+    fn intensity(&self) -> f64 {
+        self.intensity
+    }
+
+    /// Returns the fraction of this light's intensity that reaches the given point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::light::Light;
+    ///
+    /// let light = ray_tracer::light::PointLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     1.0,
+    /// );
+    /// let normal = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    ///
+    /// // A surface twice as far away receives a quarter of the light
+    /// let near = ray_tracer::vector3d::Vector3::new(0.0, 2.0, 0.0);
+    /// let far = ray_tracer::vector3d::Vector3::new(0.0, 4.0, 0.0);
+    /// assert_eq!(light.attenuation(&near), 0.25);
+    /// assert_eq!(light.diffuse(&far, &normal), light.diffuse(&near, &normal) / 4.0);
+    /// ```
+    fn attenuation(&self, point: &Vector3) -> f64 {
+        distance_falloff(
+            self.position.distance_squared(point),
+            self.constant,
+            self.linear,
+            self.quadratic,
+        )
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"PointLight");
+        hasher.write_vector(&self.position);
+        hasher.write_color(&self.color);
+        hasher.write_f64(self.intensity);
+        hasher.write_f64(self.constant);
+        hasher.write_f64(self.linear);
+        hasher.write_f64(self.quadratic);
+    }
+}
+
+/// The struct representing a directional light, like the sun
+///
+/// Contains a unit Vector3 for the direction the light travels in, a Color
+/// for its color (with red, green, and blue components from 0.0 to 1.0), and a
+/// f64 intensity that scales the color.
+///
+/// A directional light is infinitely far away, so its light arrives from the
+/// same direction at every point, and it does not fall off with distance.
+#[derive(Debug, Default)]
+pub struct DirectionalLight {
+    pub direction: Vector3,
+    pub color: Color,
+    pub intensity: f64,
+}
+
+impl DirectionalLight {
+    /// Creates a new directional light shining in the given direction, with the given color and
+    /// intensity
+    ///
+    /// The direction does not need to be a unit vector, it is normalized here.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let direction = ray_tracer::vector3d::Vector3::new(0.0, -2.0, 0.0);
+    /// let color = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let light = ray_tracer::light::DirectionalLight::new(direction, color, 0.5);
+    ///
+    /// assert_eq!(light.direction.y, -1.0);
+    /// assert_eq!(light.intensity, 0.5);
+    /// ```
+    pub fn new(direction: Vector3, color: Color, intensity: f64) -> DirectionalLight {
+        DirectionalLight {
+            direction: direction.into_unit(),
+            color,
+            intensity,
+        }
+    }
+}
+
+impl Light for DirectionalLight {
+    /// Returns the direction towards this light, which is the same from every point
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::light::Light;
+    ///
+    /// let light = ray_tracer::light::DirectionalLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(1.0, -1.0, 0.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     1.0,
+    /// );
+    ///
+    /// // A flat plane facing up is lit the same everywhere
+    /// let up = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let point1 = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let point2 = ray_tracer::vector3d::Vector3::new(100.0, 0.0, -50.0);
+    /// assert_eq!(light.diffuse(&point1, &up), light.diffuse(&point2, &up));
+    /// assert!((light.diffuse(&point1, &up) - 0.5f64.sqrt()).abs() < 1e-10);
+    /// ```
+    fn direction_from(&self, _point: &Vector3) -> Vector3 {
+        self.direction.negate()
+    }
+
+    /// Directional lights are infinitely far away
+    fn distance_from(&self, _point: &Vector3) -> f64 {
+        f64::INFINITY
+    }
+
+    /// Returns this light's color
+    fn color(&self) -> &Color {
+        &self.color
+    }
+
+    /// Returns this light's intensity
+    fn intensity(&self) -> f64 {
+        self.intensity
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"DirectionalLight");
+        hasher.write_vector(&self.direction);
+        hasher.write_color(&self.color);
+        hasher.write_f64(self.intensity);
+    }
+}
+
+/// The struct representing a spotlight
+///
+/// Contains a Vector3 for the light's position, a unit Vector3 for the
+/// direction it points in, a Color for its color (with red, green, and blue
+/// components from 0.0 to 1.0), a f64 intensity that scales the color, and
+/// the angles of its inner and outer cones from the direction it points in,
+/// in radians.
+///
+/// Points inside the inner cone are fully lit, points outside the outer cone
+/// are not lit at all, and the light fades smoothly between the two. Like a
+/// point light, it also falls off with the distance _d_ from the light as
+/// _1 / (constant + linear * d + quadratic * d^2)_.
+#[derive(Debug)]
+pub struct SpotLight {
+    pub position: Vector3,
+    pub direction: Vector3,
+    pub color: Color,
+    pub intensity: f64,
+    pub inner_angle: f64,
+    pub outer_angle: f64,
+    pub constant: f64,
+    pub linear: f64,
+    pub quadratic: f64,
+}
+
+/// The default spotlight points down and does not fall off with distance, so it stays finite
+/// even at its own position
+///
+/// # Example
+///
+/// ```
+/// use ray_tracer::light::Light;
+///
+/// let light = ray_tracer::light::SpotLight::default();
+/// assert_eq!(light.direction, ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0));
+///
+/// let below = ray_tracer::vector3d::Vector3::new(0.0, -1e-4, 0.0);
+/// assert_eq!(light.attenuation(&below), 1.0);
+///
+/// let up = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+/// assert_eq!(light.diffuse(&light.position, &up), 0.0);
+/// ```
+impl Default for SpotLight {
+    fn default() -> Self {
+        SpotLight {
+            position: Vector3::default(),
+            direction: Vector3::new(0.0, -1.0, 0.0),
+            color: Color::default(),
+            intensity: 0.0,
+            inner_angle: 0.0,
+            outer_angle: 0.0,
+            constant: 1.0,
+            linear: 0.0,
+            quadratic: 0.0,
+        }
+    }
+}
+
+impl SpotLight {
+    /// Creates a new spotlight at the given position pointing in the given direction, with the
+    /// given color, intensity, and inner and outer cone angles in radians
+    ///
+    /// The direction does not need to be a unit vector, it is normalized here. The light falls
+    /// off with the inverse square of the distance from it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let light = ray_tracer::light::SpotLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 5.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, -2.0, 0.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     10.0,
+    ///     0.5,
+    ///     0.75,
+    /// );
+    ///
+    /// assert_eq!(light.direction.y, -1.0);
+    /// assert_eq!(light.outer_angle, 0.75);
+    /// assert_eq!(light.quadratic, 1.0);
+    /// ```
+    pub fn new(
+        position: Vector3,
+        direction: Vector3,
+        color: Color,
+        intensity: f64,
+        inner_angle: f64,
+        outer_angle: f64,
+    ) -> SpotLight {
+        SpotLight {
+            position,
+            direction: direction.into_unit(),
+            color,
+            intensity,
+            inner_angle,
+            outer_angle,
+            constant: 0.0,
+            linear: 0.0,
+            quadratic: 1.0,
+        }
+    }
+
+    /// Returns the fraction of this light that the cone lets through to the given point, from
+    /// 1.0 inside the inner cone to 0.0 outside the outer cone
+    ///
+    /// Between the cones, the fraction follows a smoothstep curve of the cosine of the angle
+    /// from the direction the light points in. The light's own position is fully lit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let light = ray_tracer::light::SpotLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     1.0,
+    ///     30f64.to_radians(),
+    ///     60f64.to_radians(),
+    /// );
+    ///
+    /// // Straight below the light, inside the inner cone
+    /// let inside = ray_tracer::vector3d::Vector3::new(0.1, 0.0, 0.0);
+    /// assert_eq!(light.cone_falloff(&inside), 1.0);
+    ///
+    /// // 45 degrees from the direction, between the cones
+    /// let between = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let falloff = light.cone_falloff(&between);
+    /// assert!(falloff > 0.0 && falloff < 1.0);
+    ///
+    /// // Almost level with the light, outside the outer cone
+    /// let outside = ray_tracer::vector3d::Vector3::new(5.0, 0.0, 0.0);
+    /// assert_eq!(light.cone_falloff(&outside), 0.0);
+    ///
+    /// assert_eq!(light.cone_falloff(&light.position), 1.0);
+    /// ```
+    pub fn cone_falloff(&self, point: &Vector3) -> f64 {
+        let cos_angle = match point.sub(&self.position).try_into_unit() {
+            Some(towards) => towards.dot(&self.direction),
+            None => return 1.0,
+        };
+        let (cos_inner, cos_outer) = (self.inner_angle.cos(), self.outer_angle.cos());
+        if cos_inner <= cos_outer {
+            // The cones are the same, so there is a hard edge
+            return if cos_angle >= cos_outer { 1.0 } else { 0.0 };
+        }
+        let t = ((cos_angle - cos_outer) / (cos_inner - cos_outer)).clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
+impl Light for SpotLight {
+    /// Returns the unit vector pointing from the given point towards this light
+    fn direction_from(&self, point: &Vector3) -> Vector3 {
+        self.position.sub(point).into_unit()
+    }
+
+    /// Returns the distance from the given point to this light's position
+    fn distance_from(&self, point: &Vector3) -> f64 {
+        self.position.distance(point)
+    }
+
+    /// Returns this light's color
+    fn color(&self) -> &Color {
+        &self.color
+    }
+
+    /// Returns this light's intensity
+    fn intensity(&self) -> f64 {
+        self.intensity
+    }
+
+    /// Returns the fraction of this light's intensity that reaches the given point, falling off
+    /// with the distance like a point light and across the edge of the cone
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::light::Light;
+    ///
+    /// let light = ray_tracer::light::SpotLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 2.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     4.0,
+    ///     0.2,
+    ///     0.4,
+    /// );
+    /// let up = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    ///
+    /// let below = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// assert_eq!(light.diffuse(&below, &up), 1.0);
+    ///
+    /// let aside = ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0);
+    /// assert_eq!(light.diffuse(&aside, &up), 0.0);
+    /// ```
+    fn attenuation(&self, point: &Vector3) -> f64 {
+        let distance_squared = self.position.distance_squared(point);
+        self.cone_falloff(point)
+            * distance_falloff(distance_squared, self.constant, self.linear, self.quadratic)
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"SpotLight");
+        hasher.write_vector(&self.position);
+        hasher.write_vector(&self.direction);
+        hasher.write_color(&self.color);
+        hasher.write_f64(self.intensity);
+        hasher.write_f64(self.inner_angle);
+        hasher.write_f64(self.outer_angle);
+        hasher.write_f64(self.constant);
+        hasher.write_f64(self.linear);
+        hasher.write_f64(self.quadratic);
+    }
+}
+
+/// The struct representing a rectangular area light
+///
+/// Contains a Vector3 for one corner of the rectangle, two Vector3 edges
+/// running from that corner to the adjacent corners, a Color for its color
+/// (with red, green, and blue components from 0.0 to 1.0), a f64 intensity
+/// that scales the color, and the number of shadow ray samples taken along
+/// each edge.
+///
+/// Shadows are found by tracing a shadow ray towards each of a grid of
+/// _samples * samples_ points across the rectangle, so objects that block
+/// only part of the light cast a soft penumbra. More samples make smoother
+/// shadows, but take longer to render. Otherwise the light acts like a
+/// point light at the center of the rectangle, falling off with the distance
+/// _d_ from it as _1 / (constant + linear * d + quadratic * d^2)_.
+#[derive(Debug)]
+pub struct AreaLight {
+    pub corner: Vector3,
+    pub edge_u: Vector3,
+    pub edge_v: Vector3,
+    pub color: Color,
+    pub intensity: f64,
+    pub samples: u32,
+    pub constant: f64,
+    pub linear: f64,
+    pub quadratic: f64,
+}
+
+/// The default area light does not fall off with distance, so it stays finite even at its own
+/// center
+///
+/// # Example
+///
+/// ```
+/// use ray_tracer::light::Light;
+///
+/// let light = ray_tracer::light::AreaLight::default();
+/// assert_eq!(light.attenuation(&light.center()), 1.0);
+/// ```
+impl Default for AreaLight {
+    fn default() -> Self {
+        AreaLight {
+            corner: Vector3::default(),
+            edge_u: Vector3::default(),
+            edge_v: Vector3::default(),
+            color: Color::default(),
+            intensity: 0.0,
+            samples: 0,
+            constant: 1.0,
+            linear: 0.0,
+            quadratic: 0.0,
+        }
+    }
+}
+
+impl AreaLight {
+    /// Creates a new rectangular area light with the given corner, edges, color, intensity, and
+    /// number of samples along each edge
+    ///
+    /// The light falls off with the inverse square of the distance from its center.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let light = ray_tracer::light::AreaLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(-1.0, 5.0, -1.0),
+    ///     ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 2.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     10.0,
+    ///     4,
+    /// );
+    ///
+    /// assert_eq!(light.center(), ray_tracer::vector3d::Vector3::new(0.0, 5.0, 0.0));
+    /// assert_eq!(light.quadratic, 1.0);
+    /// ```
+    pub fn new(
+        corner: Vector3,
+        edge_u: Vector3,
+        edge_v: Vector3,
+        color: Color,
+        intensity: f64,
+        samples: u32,
+    ) -> AreaLight {
+        AreaLight {
+            corner,
+            edge_u,
+            edge_v,
+            color,
+            intensity,
+            samples,
+            constant: 0.0,
+            linear: 0.0,
+            quadratic: 1.0,
+        }
+    }
+
+    /// Returns the point at the center of this light's rectangle
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let light = ray_tracer::light::AreaLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 2.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(4.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 2.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     1.0,
+    ///     2,
+    /// );
+    ///
+    /// assert_eq!(light.center(), ray_tracer::vector3d::Vector3::new(2.0, 2.0, 1.0));
+    /// ```
+    pub fn center(&self) -> Vector3 {
+        self.corner
+            .add(&self.edge_u.mul(0.5))
+            .add(&self.edge_v.mul(0.5))
+    }
+}
+
+impl Light for AreaLight {
+    /// Returns the unit vector pointing from the given point towards the center of this light
+    fn direction_from(&self, point: &Vector3) -> Vector3 {
+        self.center().sub(point).into_unit()
+    }
+
+    /// Returns the distance from the given point to the center of this light
+    fn distance_from(&self, point: &Vector3) -> f64 {
+        self.center().distance(point)
+    }
+
+    /// Returns this light's color
+    fn color(&self) -> &Color {
+        &self.color
+    }
+
+    /// Returns this light's intensity
+    fn intensity(&self) -> f64 {
+        self.intensity
+    }
+
+    /// Returns the fraction of this light's intensity that reaches the given point, which falls
+    /// off with the distance from the center of the light like a point light
+    fn attenuation(&self, point: &Vector3) -> f64 {
+        distance_falloff(
+            self.center().distance_squared(point),
+            self.constant,
+            self.linear,
+            self.quadratic,
+        )
+    }
+
+    /// Returns the centers of a grid of _samples * samples_ cells across this light
+    ///
+    /// At least one sample is always taken.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::light::Light;
+    ///
+    /// let light = ray_tracer::light::AreaLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 2.0, 0.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     1.0,
+    ///     2,
+    /// );
+    ///
+    /// let points = light.sample_points().unwrap();
+    /// assert_eq!(points.len(), 4);
+    /// assert_eq!(points[0], ray_tracer::vector3d::Vector3::new(0.5, 0.5, 0.0));
+    /// assert_eq!(points[3], ray_tracer::vector3d::Vector3::new(1.5, 1.5, 0.0));
+    /// ```
+    fn sample_points(&self) -> Option<Vec<Vector3>> {
+        let samples = self.samples.max(1);
+        let step = 1.0 / f64::from(samples);
+        let points = (0..samples)
+            .flat_map(|i| (0..samples).map(move |j| (i, j)))
+            .map(|(i, j)| {
+                let s = (f64::from(i) + 0.5) * step;
+                let t = (f64::from(j) + 0.5) * step;
+                self.corner
+                    .add(&self.edge_u.mul(s))
+                    .add(&self.edge_v.mul(t))
+            })
+            .collect();
+        Some(points)
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"AreaLight");
+        hasher.write_vector(&self.corner);
+        hasher.write_vector(&self.edge_u);
+        hasher.write_vector(&self.edge_v);
+        hasher.write_color(&self.color);
+        hasher.write_f64(self.intensity);
+        hasher.write_u32(self.samples);
+        hasher.write_f64(self.constant);
+        hasher.write_f64(self.linear);
+        hasher.write_f64(self.quadratic);
+    }
+}
+
+/// The struct representing ambient light
+///
+/// Ambient light approximates the light bounced around the scene, and reaches
+/// every surface equally regardless of shadows. Contains a Color for its
+/// color (with red, green, and blue components from 0.0 to 1.0), and a f64
+/// intensity that scales the color.
+#[derive(Debug, Default)]
+pub struct AmbientLight {
+    pub color: Color,
+    pub intensity: f64,
+}
+
+impl AmbientLight {
+    /// Creates a new ambient light with the given color and intensity
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let color = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let ambient = ray_tracer::light::AmbientLight::new(color, 0.1);
+    ///
+    /// assert_eq!(ambient.intensity, 0.1);
+    /// ```
+    pub fn new(color: Color, intensity: f64) -> AmbientLight {
+        AmbientLight { color, intensity }
+    }
+
+    /// Returns the ambient light reflected by a surface with the given color
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let color = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let ambient = ray_tracer::light::AmbientLight::new(color, 0.5);
+    ///
+    /// let surface = ray_tracer::color::Color::new(1.0, 0.5, 0.0);
+    /// let reflected = ambient.reflected_by(&surface);
+    /// assert_eq!(reflected, ray_tracer::color::Color::new(0.5, 0.25, 0.0));
+    /// ```
+    pub fn reflected_by(&self, surface_color: &Color) -> Color {
+        self.color.component_mul(surface_color).mul(self.intensity)
+    }
+}
+
+/// Returns the fraction of a light's intensity that reaches a point the given squared distance
+/// away, given the constant, linear, and quadratic attenuation coefficients
+fn distance_falloff(distance_squared: f64, constant: f64, linear: f64, quadratic: f64) -> f64 {
+    1.0 / (constant + linear * distance_squared.sqrt() + quadratic * distance_squared)
+}
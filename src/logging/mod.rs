@@ -0,0 +1,132 @@
+//! This module is a small logging facade, so that the ray tracer's messages can be silenced,
+//! filtered, or redirected by whoever is running it
+//!
+//! Messages are logged with the `error!`, `warn!`, `info!`, and `debug!` macros, and are
+//! passed to the logger installed with `set_logger`. Nothing is logged until a logger is
+//! installed.
+
+use std::fmt;
+use std::sync::RwLock;
+
+/// The importance of a logged message, from most to least important
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A logged message, with its level
+#[derive(Clone, Copy, Debug)]
+pub struct Record<'a> {
+    pub level: Level,
+    pub args: fmt::Arguments<'a>,
+}
+
+/// Something that logged messages can be passed to
+pub trait Logger: Send + Sync {
+    /// Handles the given logged message
+    fn log(&self, record: &Record);
+}
+
+/// A logger that writes every message to standard error, with its level
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StderrLogger;
+
+impl Logger for StderrLogger {
+    fn log(&self, record: &Record) {
+        eprintln!("[{}] {}", record.level, record.args);
+    }
+}
+
+/// The installed logger, and the least important level passed to it
+static LOGGER: RwLock<Option<(Box<dyn Logger>, Level)>> = RwLock::new(None);
+
+/// Installs the given logger, which is passed every message at least as important as the given
+/// level, replacing any logger installed before
+///
+/// # Example
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+///
+/// struct Capture(Arc<Mutex<Vec<String>>>);
+///
+/// impl ray_tracer::logging::Logger for Capture {
+///     fn log(&self, record: &ray_tracer::logging::Record) {
+///         let message = format!("{}: {}", record.level, record.args);
+///         self.0.lock().unwrap().push(message);
+///     }
+/// }
+///
+/// let messages = Arc::new(Mutex::new(vec![]));
+/// let logger = Capture(messages.clone());
+/// ray_tracer::logging::set_logger(Box::new(logger), ray_tracer::logging::Level::Info);
+///
+/// ray_tracer::info!("found {} spheres", 3);
+/// ray_tracer::debug!("too detailed to be logged");
+///
+/// assert_eq!(*messages.lock().unwrap(), vec!["INFO: found 3 spheres".to_string()]);
+/// ```
+pub fn set_logger(logger: Box<dyn Logger>, max_level: Level) {
+    let mut installed = LOGGER.write().unwrap_or_else(|e| e.into_inner());
+    *installed = Some((logger, max_level));
+}
+
+/// Passes the given message to the installed logger, if there is one and the message is
+/// important enough
+///
+/// This is normally called through the logging macros.
+pub fn log(level: Level, args: fmt::Arguments) {
+    let installed = LOGGER.read().unwrap_or_else(|e| e.into_inner());
+    if let Some((logger, max_level)) = installed.as_ref() {
+        if level <= *max_level {
+            logger.log(&Record { level, args });
+        }
+    }
+}
+
+/// Logs a message about an error, formatted like `format!`
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)*) => {
+        $crate::logging::log($crate::logging::Level::Error, format_args!($($arg)*))
+    };
+}
+
+/// Logs a warning, formatted like `format!`
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        $crate::logging::log($crate::logging::Level::Warn, format_args!($($arg)*))
+    };
+}
+
+/// Logs an informative message, formatted like `format!`
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        $crate::logging::log($crate::logging::Level::Info, format_args!($($arg)*))
+    };
+}
+
+/// Logs a detailed message for debugging, formatted like `format!`
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::logging::log($crate::logging::Level::Debug, format_args!($($arg)*))
+    };
+}
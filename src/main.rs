@@ -2,5 +2,16 @@ const SCREEN_WIDTH: u32 = 800;
 const SCREEN_HEIGHT: u32 = 600;
 
 pub fn main() {
-    ray_tracer::run(SCREEN_WIDTH, SCREEN_HEIGHT);
+    let config = ray_tracer::window::WindowConfig {
+        resizable: true,
+        ..ray_tracer::window::WindowConfig::new(SCREEN_WIDTH, SCREEN_HEIGHT)
+    };
+    ray_tracer::logging::set_logger(
+        Box::new(ray_tracer::logging::StderrLogger),
+        ray_tracer::logging::Level::Debug,
+    );
+    if let Err(e) = ray_tracer::run(config) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
 }
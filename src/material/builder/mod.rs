@@ -0,0 +1,210 @@
+//! This module defines a builder for Materials with many properties
+
+use std::sync::Arc;
+
+use super::Material;
+use crate::color::Color;
+use crate::texture::Texture;
+
+/// The struct representing a Material being built
+///
+/// Contains the material built so far. Every property starts at its default,
+/// as in `Material::default`: a white, matte surface with no highlights,
+/// reflections, transparency, or emission, a shininess of 32, and an index of
+/// refraction of 1.0.
+#[derive(Clone, Debug, Default)]
+pub struct MaterialBuilder {
+    material: Material,
+}
+
+impl MaterialBuilder {
+    /// Creates a new builder, with every property at its default
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let material = ray_tracer::material::builder::MaterialBuilder::new().build();
+    ///
+    /// assert_eq!(material.color, ray_tracer::material::Material::default().color);
+    /// ```
+    pub fn new() -> MaterialBuilder {
+        MaterialBuilder::default()
+    }
+
+    /// Sets the color of the surface
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let red = ray_tracer::color::Color::new(1.0, 0.0, 0.0);
+    /// let material = ray_tracer::material::Material::builder().color(red.clone()).build();
+    ///
+    /// assert_eq!(material.color, red);
+    /// ```
+    pub fn color(mut self, color: Color) -> MaterialBuilder {
+        self.material.color = color;
+        self
+    }
+
+    /// Sets the texture replacing the color of the surface
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let black = ray_tracer::color::Color::new(0.0, 0.0, 0.0);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let checker = ray_tracer::texture::Checker::new(black, white);
+    /// let material = ray_tracer::material::Material::builder()
+    ///     .texture(std::sync::Arc::new(checker))
+    ///     .build();
+    ///
+    /// assert!(material.texture.is_some());
+    /// ```
+    pub fn texture(mut self, texture: Arc<dyn Texture>) -> MaterialBuilder {
+        self.material.texture = Some(texture);
+        self
+    }
+
+    /// Sets the strength of the specular highlights
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let material = ray_tracer::material::Material::builder().specular(0.5).build();
+    ///
+    /// assert_eq!(material.specular, 0.5);
+    /// ```
+    pub fn specular(mut self, specular: f64) -> MaterialBuilder {
+        self.material.specular = specular;
+        self
+    }
+
+    /// Sets the shininess exponent of the specular highlights
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let material = ray_tracer::material::Material::builder().shininess(128.0).build();
+    ///
+    /// assert_eq!(material.shininess, 128.0);
+    /// ```
+    pub fn shininess(mut self, shininess: f64) -> MaterialBuilder {
+        self.material.shininess = shininess;
+        self
+    }
+
+    /// Sets how much of the color comes from reflecting the surroundings
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let material = ray_tracer::material::Material::builder().reflectivity(0.8).build();
+    ///
+    /// assert_eq!(material.reflectivity, 0.8);
+    /// ```
+    pub fn reflectivity(mut self, reflectivity: f64) -> MaterialBuilder {
+        self.material.reflectivity = reflectivity;
+        self
+    }
+
+    /// Sets how rough the reflections are
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let material = ray_tracer::material::Material::builder().fuzz(0.2).build();
+    ///
+    /// assert_eq!(material.fuzz, 0.2);
+    /// ```
+    pub fn fuzz(mut self, fuzz: f64) -> MaterialBuilder {
+        self.material.fuzz = fuzz;
+        self
+    }
+
+    /// Sets how much of the color comes from light passing through
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let material = ray_tracer::material::Material::builder().transparency(0.9).build();
+    ///
+    /// assert_eq!(material.transparency, 0.9);
+    /// ```
+    pub fn transparency(mut self, transparency: f64) -> MaterialBuilder {
+        self.material.transparency = transparency;
+        self
+    }
+
+    /// Sets the index of refraction
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let material = ray_tracer::material::Material::builder().index_of_refraction(1.5).build();
+    ///
+    /// assert_eq!(material.index_of_refraction, 1.5);
+    /// ```
+    pub fn index_of_refraction(mut self, index_of_refraction: f64) -> MaterialBuilder {
+        self.material.index_of_refraction = index_of_refraction;
+        self
+    }
+
+    /// Sets the color of the light the surface emits
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let glow = ray_tracer::color::Color::new(4.0, 4.0, 4.0);
+    /// let material = ray_tracer::material::Material::builder().emission(glow.clone()).build();
+    ///
+    /// assert_eq!(material.emission, glow);
+    /// ```
+    pub fn emission(mut self, emission: Color) -> MaterialBuilder {
+        self.material.emission = emission;
+        self
+    }
+
+    /// Sets the normal map adding bumps to the surface, as described for
+    /// `Material::shading_normal`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let bumps = ray_tracer::texture::UvTexture::new("bumps", |u, _| {
+    ///     ray_tracer::color::Color::new(0.5 + 0.25 * (u * 50.0).sin(), 0.5, 1.0)
+    /// });
+    /// let material = ray_tracer::material::Material::builder()
+    ///     .normal_map(std::sync::Arc::new(bumps))
+    ///     .build();
+    ///
+    /// assert!(material.normal_map.is_some());
+    /// ```
+    pub fn normal_map(mut self, normal_map: Arc<dyn Texture>) -> MaterialBuilder {
+        self.material.normal_map = Some(normal_map);
+        self
+    }
+
+    /// Returns the built material
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let blue = ray_tracer::color::Color::new(0.0, 0.0, 1.0);
+    /// let material = ray_tracer::material::Material::builder().color(blue.clone()).build();
+    ///
+    /// // Everything but the color is left at its default
+    /// assert_eq!(material.color, blue);
+    /// assert!(material.texture.is_none());
+    /// assert_eq!(material.specular, 0.0);
+    /// assert_eq!(material.shininess, 32.0);
+    /// assert_eq!(material.reflectivity, 0.0);
+    /// assert_eq!(material.fuzz, 0.0);
+    /// assert_eq!(material.transparency, 0.0);
+    /// assert_eq!(material.index_of_refraction, 1.0);
+    /// assert_eq!(material.emission, ray_tracer::color::Color::new(0.0, 0.0, 0.0));
+    /// assert!(material.normal_map.is_none());
+    /// ```
+    pub fn build(self) -> Material {
+        self.material
+    }
+}
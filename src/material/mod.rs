@@ -1,14 +1,411 @@
-use sdl2::pixels;
+use rand::Rng;
 
-#[derive(Debug)]
+use std::sync::Arc;
+
+use super::color::Color;
+use super::object::HitRecord;
+use super::random::RenderRng;
+use super::ray::Ray;
+use super::texture::{Texture, UvTexture};
+use super::vector3d::Vector3;
+
+use self::builder::MaterialBuilder;
+
+/// A trait for surfaces that scatter the light reaching them in random directions
+///
+/// Rather than lighting a surface directly from each light, a path tracer
+/// follows the light backwards, scattering the ray at each surface it hits
+/// until it finds something emitting light.
+pub trait Scatter {
+    /// Returns the ray that the given ray hitting the surface at the given hit scatters into, and
+    /// the attenuation, the fraction of the light from that ray that is passed back along the
+    /// given ray. "None" means that the ray is absorbed.
+    ///
+    /// Every random choice is taken from the given generator.
+    fn scatter(&self, ray: &Ray, hit: &HitRecord, rng: &mut RenderRng) -> Option<(Ray, Color)>;
+}
+
+/// The struct representing the surface properties of an object
+///
+/// Contains the color of the surface, and an optional texture that replaces that color, shared
+/// between every copy of the material.
+///
+/// The specular strength and shininess exponent control how bright and how tight its highlights
+/// are. The reflectivity is how much of its color comes from reflecting its surroundings like a
+/// mirror, and the fuzz is how rough that mirror is, from 0.0 for a perfect mirror. The
+/// transparency is how much comes from light passing through it, bent by the index of
+/// refraction. Reflectivity and transparency both range from 0.0 to 1.0.
+///
+/// The emission is the color of the light the surface emits itself, and the optional normal map
+/// adds bumps to the surface when it is lit.
+#[derive(Clone, Debug)]
 pub struct Material {
-    pub color: pixels::Color,
+    pub color: Color,
+    pub texture: Option<Arc<dyn Texture>>,
+    pub specular: f64,
+    pub shininess: f64,
+    pub reflectivity: f64,
+    pub fuzz: f64,
+    pub transparency: f64,
+    pub index_of_refraction: f64,
+    pub emission: Color,
+    pub normal_map: Option<Arc<dyn Texture>>,
 }
 
 impl Default for Material {
     fn default() -> Self {
         Material {
-            color: pixels::Color::RGBA(255, 255, 255, 255),
+            color: Color::new(1.0, 1.0, 1.0),
+            texture: None,
+            specular: 0.0,
+            shininess: 32.0,
+            reflectivity: 0.0,
+            fuzz: 0.0,
+            transparency: 0.0,
+            index_of_refraction: 1.0,
+            emission: Color::default(),
+            normal_map: None,
+        }
+    }
+}
+
+impl Material {
+    /// Returns a builder for a material, starting from the default material
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let glass = ray_tracer::material::Material::builder()
+    ///     .transparency(1.0)
+    ///     .index_of_refraction(1.5)
+    ///     .specular(0.5)
+    ///     .build();
+    ///
+    /// assert_eq!(glass.transparency, 1.0);
+    /// assert_eq!(glass.index_of_refraction, 1.5);
+    /// assert_eq!(glass.specular, 0.5);
+    /// ```
+    pub fn builder() -> MaterialBuilder {
+        MaterialBuilder::new()
+    }
+
+    /// Creates a material whose color is the given function of the texture coordinates, named by
+    /// the given key as described for `UvTexture`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// // Ten stripes of alternating color along the lines of latitude
+    /// let material = ray_tracer::material::Material::uv_mapped("latitude stripes", |_, v| {
+    ///     if (v * 10.0).floor() as i64 % 2 == 0 {
+    ///         ray_tracer::color::Color::new(1.0, 0.0, 0.0)
+    ///     } else {
+    ///         ray_tracer::color::Color::new(1.0, 1.0, 1.0)
+    ///     }
+    /// });
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let red = material.color_at(0.5, 0.05, &point);
+    /// let white = material.color_at(0.5, 0.15, &point);
+    /// assert_eq!(red, ray_tracer::color::Color::new(1.0, 0.0, 0.0));
+    /// assert_eq!(white, ray_tracer::color::Color::new(1.0, 1.0, 1.0));
+    ///
+    /// // On a sphere, the stripes follow its texture coordinates
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     1.0,
+    ///     material,
+    /// );
+    /// let north = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let equator = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let color_at = |point: &ray_tracer::vector3d::Vector3| {
+    ///     let (u, v) = sphere.uv_at(point);
+    ///     sphere.material().color_at(u, v, point)
+    /// };
+    /// assert_ne!(color_at(&north), color_at(&equator));
+    /// ```
+    pub fn uv_mapped<F>(key: &str, function: F) -> Material
+    where
+        F: Fn(f64, f64) -> Color + Send + Sync + 'static,
+    {
+        Material {
+            texture: Some(Arc::new(UvTexture::new(key, function))),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a metal material of the given color, which reflects its surroundings like a
+    /// mirror, blurred by the given fuzz
+    ///
+    /// A fuzz of 0.0 is a polished, perfect mirror, and larger values look more brushed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let gold = ray_tracer::color::Color::new(0.8, 0.6, 0.2);
+    /// let metal = ray_tracer::material::Material::metal(gold.clone(), 0.3);
+    ///
+    /// assert_eq!(metal.color, gold);
+    /// assert_eq!(metal.reflectivity, 1.0);
+    /// assert_eq!(metal.fuzz, 0.3);
+    /// ```
+    pub fn metal(color: Color, fuzz: f64) -> Material {
+        Material {
+            color,
+            reflectivity: 1.0,
+            fuzz,
+            ..Default::default()
         }
     }
+
+    /// Returns the unit direction of a ray with the given direction after reflecting off a
+    /// surface of this material with the given unit normal
+    ///
+    /// The mirror reflection is moved by a random vector within a sphere whose radius is the
+    /// fuzz, so that rough surfaces blur their reflections. A direction moved below the surface
+    /// is replaced by the mirror reflection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let normal = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let dir = ray_tracer::vector3d::Vector3::new(1.0, -1.0, 0.0);
+    /// let mirror = ray_tracer::vector3d::Vector3::new(1.0, 1.0, 0.0).into_unit();
+    /// let silver = ray_tracer::color::Color::new(0.9, 0.9, 0.9);
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(0);
+    ///
+    /// // Without fuzz, the reflection is exact
+    /// let polished = ray_tracer::material::Material::metal(silver.clone(), 0.0);
+    /// assert_eq!(polished.reflect(&dir, &normal, &mut rng), mirror);
+    ///
+    /// // With fuzz, reflections stay within the cone the fuzz sphere covers
+    /// let fuzz = 0.2;
+    /// let brushed = ray_tracer::material::Material::metal(silver, fuzz);
+    /// let reflections: Vec<_> = (0..100)
+    ///     .map(|_| brushed.reflect(&dir, &normal, &mut rng))
+    ///     .collect();
+    /// for reflection in &reflections {
+    ///     assert!((reflection.length() - 1.0).abs() < 1e-10);
+    ///     assert!(reflection.angle_between(&mirror) <= fuzz.asin() + 1e-10);
+    /// }
+    /// assert!(reflections.iter().any(|reflection| !reflection.approx_eq(&mirror, 1e-6)));
+    /// ```
+    pub fn reflect<R: Rng>(&self, dir: &Vector3, normal: &Vector3, rng: &mut R) -> Vector3 {
+        let mirror = dir.reflect(normal).into_unit();
+        if self.fuzz <= 0.0 {
+            return mirror;
+        }
+        let fuzzed = mirror.add(&Vector3::random_in_unit_sphere(rng).mul(self.fuzz));
+        match fuzzed.try_into_unit() {
+            Some(fuzzed) if fuzzed.dot(normal) > 0.0 => fuzzed,
+            _ => mirror,
+        }
+    }
+
+    /// Returns the color of the surface at the given texture coordinates and point
+    ///
+    /// This is the texture's color there if there is a texture, and the flat
+    /// color otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let red = ray_tracer::color::Color::new(1.0, 0.0, 0.0);
+    /// let material = ray_tracer::material::Material {
+    ///     color: red.clone(),
+    ///     ..Default::default()
+    /// };
+    /// let point = ray_tracer::vector3d::Vector3::new(0.5, 0.5, 0.5);
+    /// assert_eq!(material.color_at(0.0, 0.0, &point), red);
+    ///
+    /// let black = ray_tracer::color::Color::new(0.0, 0.0, 0.0);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let material = ray_tracer::material::Material {
+    ///     color: red,
+    ///     texture: Some(std::sync::Arc::new(ray_tracer::texture::Checker::new(black.clone(), white))),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(material.color_at(0.0, 0.0, &point), black);
+    /// ```
+    pub fn color_at(&self, u: f64, v: f64, point: &Vector3) -> Color {
+        match self.texture {
+            Some(ref texture) => texture.color_at(u, v, point),
+            None => self.color.clone(),
+        }
+    }
+
+    /// Returns the unit normal to light the surface with at the given texture coordinates and
+    /// point, given the unit normal of the surface there
+    ///
+    /// Without a normal map, this is the surface's own normal. Otherwise the normal map's color
+    /// there is a normal in tangent space, with each of red, green, and blue running from 0.0
+    /// for -1.0 to 1.0 for 1.0. Blue is along the surface normal, and red and green are along
+    /// the tangent and bitangent from `Vector3::build_orthonormal_basis`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let up = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    ///
+    /// // A flat normal map, pointing straight out of the surface, leaves the normal unchanged
+    /// let flat = ray_tracer::material::Material::builder()
+    ///     .normal_map(std::sync::Arc::new(ray_tracer::texture::UvTexture::new("flat", |_, _| {
+    ///         ray_tracer::color::Color::new(0.5, 0.5, 1.0)
+    ///     })))
+    ///     .build();
+    /// assert!(flat.shading_normal(0.0, 0.0, &point, &up).approx_eq(&up, 1e-12));
+    ///
+    /// // A map tilted 30 degrees towards the tangent turns the normal by 30 degrees
+    /// let angle = std::f64::consts::PI / 6.0;
+    /// let tilted = ray_tracer::material::Material::builder()
+    ///     .normal_map(std::sync::Arc::new(ray_tracer::texture::UvTexture::new("tilted", move |_, _| {
+    ///         ray_tracer::color::Color::new(0.5 * angle.sin() + 0.5, 0.5, 0.5 * angle.cos() + 0.5)
+    ///     })))
+    ///     .build();
+    /// let normal = tilted.shading_normal(0.0, 0.0, &point, &up);
+    /// let (tangent, _) = up.build_orthonormal_basis();
+    /// assert!((normal.length() - 1.0).abs() < 1e-12);
+    /// assert!((normal.dot(&up) - angle.cos()).abs() < 1e-12);
+    /// assert!((normal.dot(&tangent) - angle.sin()).abs() < 1e-12);
+    /// ```
+    pub fn shading_normal(&self, u: f64, v: f64, point: &Vector3, normal: &Vector3) -> Vector3 {
+        let normal_map = match self.normal_map {
+            Some(ref normal_map) => normal_map,
+            None => return normal.clone(),
+        };
+        let encoded = normal_map.color_at(u, v, point);
+        let (tangent, bitangent) = normal.build_orthonormal_basis();
+        tangent
+            .mul(2.0 * encoded.r - 1.0)
+            .add(&bitangent.mul(2.0 * encoded.g - 1.0))
+            .add(&normal.mul(2.0 * encoded.b - 1.0))
+            .try_into_unit()
+            .unwrap_or_else(|| normal.clone())
+    }
+}
+
+impl Scatter for Material {
+    /// Scatters the given ray like a diffuse, metal, or dielectric surface, choosing at random
+    /// in proportion to this material's transparency and reflectivity.
+    ///
+    /// A dielectric refracts the ray through the surface, or reflects it with the probability
+    /// given by `schlick`, or always when it cannot refract. A metal reflects it, blurred by the
+    /// fuzz. A diffuse surface scatters it with a cosine-weighted direction around the normal,
+    /// found by adding a random unit vector to the normal. Every ray is attenuated by the
+    /// surface color.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::material::Scatter;
+    ///
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(0);
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(1.0, -1.0, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// let point = ray.at(1.0);
+    /// let normal = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    ///
+    /// // Diffuse scattering always leaves through the hemisphere around the normal
+    /// let red = ray_tracer::color::Color::new(1.0, 0.0, 0.0);
+    /// let diffuse = ray_tracer::material::Material {
+    ///     color: red.clone(),
+    ///     ..Default::default()
+    /// };
+    /// let hit =
+    ///     ray_tracer::object::HitRecord::new(&ray, 1.0, point.clone(), normal.clone(), &diffuse);
+    /// for _ in 0..1000 {
+    ///     let (scattered, attenuation) = diffuse.scatter(&ray, &hit, &mut rng).unwrap();
+    ///     assert!(scattered.dir.dot(&normal) >= 0.0);
+    ///     assert!((scattered.dir.length() - 1.0).abs() < 1e-10);
+    ///     assert_eq!(attenuation, red);
+    /// }
+    ///
+    /// // A polished metal reflects like a mirror
+    /// let metal = ray_tracer::material::Material::metal(red, 0.0);
+    /// let hit = ray_tracer::object::HitRecord::new(&ray, 1.0, point, normal.clone(), &metal);
+    /// let (scattered, _) = metal.scatter(&ray, &hit, &mut rng).unwrap();
+    /// let mirror = ray_tracer::vector3d::Vector3::new(1.0, 1.0, 0.0).into_unit();
+    /// assert!(scattered.dir.approx_eq(&mirror, 1e-10));
+    ///
+    /// // Glass looked at straight on mostly lets the ray through
+    /// let glass = ray_tracer::material::Material {
+    ///     transparency: 1.0,
+    ///     index_of_refraction: 1.5,
+    ///     ..Default::default()
+    /// };
+    /// let down = ray_tracer::ray::Ray::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0),
+    /// );
+    /// let hit = ray_tracer::object::HitRecord::new(&down, 1.0, down.at(1.0), normal, &glass);
+    /// let through = (0..100)
+    ///     .filter(|_| glass.scatter(&down, &hit, &mut rng).unwrap().0.dir.y < 0.0)
+    ///     .count();
+    /// assert!(through > 80);
+    /// ```
+    fn scatter(&self, ray: &Ray, hit: &HitRecord, rng: &mut RenderRng) -> Option<(Ray, Color)> {
+        let attenuation = self.color_at(hit.u, hit.v, &hit.point);
+        let dir = ray.dir.into_unit();
+
+        let choice = rng.gen::<f64>();
+        let scattered_dir = if choice < self.transparency {
+            let eta_ratio = if hit.front_face {
+                1.0 / self.index_of_refraction
+            } else {
+                self.index_of_refraction
+            };
+            let cosine = -dir.dot(&hit.normal);
+            match dir.refract(&hit.normal, eta_ratio) {
+                Some(refracted) if schlick(cosine, self.index_of_refraction) < rng.gen::<f64>() => {
+                    refracted.into_unit()
+                }
+                _ => self.reflect(&dir, &hit.normal, rng),
+            }
+        } else if choice < self.transparency + (1.0 - self.transparency) * self.reflectivity {
+            self.reflect(&dir, &hit.normal, rng)
+        } else {
+            // A random vector almost opposite the normal would cancel it out, leaving no
+            // direction to scatter in
+            let diffuse_dir = hit.normal.add(&Vector3::random_unit_vector(rng));
+            if diffuse_dir.near_zero() {
+                hit.normal.clone()
+            } else {
+                diffuse_dir.into_unit()
+            }
+        };
+        Some((hit.secondary_ray(scattered_dir), attenuation))
+    }
+}
+
+/// Approximates the fraction of light reflected by a transparent surface, using Schlick's
+/// approximation of the Fresnel equations.
+///
+/// `cosine` is the cosine of the angle between the incoming light and the surface normal, and
+/// `ref_idx` is the ratio of the indices of refraction on either side of the surface. The rest
+/// of the light is refracted through the surface.
+///
+/// # Example
+///
+/// ```
+/// let ref_idx: f64 = 1.5;
+/// let base_reflectance = ((1.0 - ref_idx) / (1.0 + ref_idx)).powi(2);
+///
+/// // Looking straight at the surface, only the base reflectance is reflected
+/// let normal_incidence = ray_tracer::material::schlick(1.0, ref_idx);
+/// assert!((normal_incidence - base_reflectance).abs() < 1e-10);
+///
+/// // At grazing angles, nearly all of the light is reflected
+/// let grazing = ray_tracer::material::schlick(0.001, ref_idx);
+/// assert!(grazing > 0.99);
+/// assert_eq!(ray_tracer::material::schlick(0.0, ref_idx), 1.0);
+/// ```
+pub fn schlick(cosine: f64, ref_idx: f64) -> f64 {
+    let r0 = ((1.0 - ref_idx) / (1.0 + ref_idx)).powi(2);
+    r0 + (1.0 - r0) * (1.0 - cosine).powi(5)
 }
+
+pub mod builder;
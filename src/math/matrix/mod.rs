@@ -0,0 +1,312 @@
+//! This module defines a struct representing a 4x4 matrix of an affine transform
+
+use crate::vector3d::Vector3;
+
+/// The struct representing a 4x4 matrix
+///
+/// Contains the entries of the matrix, row by row. Points and vectors are
+/// treated as columns, with a fourth component of 1 for points and 0 for
+/// vectors, so the last column holds a transform's translation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix4 {
+    pub rows: [[f64; 4]; 4],
+}
+
+impl Default for Matrix4 {
+    fn default() -> Self {
+        Matrix4::identity()
+    }
+}
+
+impl Matrix4 {
+    /// Creates a new matrix with the given rows
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let matrix = ray_tracer::math::matrix::Matrix4::new([
+    ///     [1.0, 2.0, 3.0, 4.0],
+    ///     [5.0, 6.0, 7.0, 8.0],
+    ///     [9.0, 10.0, 11.0, 12.0],
+    ///     [0.0, 0.0, 0.0, 1.0],
+    /// ]);
+    ///
+    /// assert_eq!(matrix.rows[1][2], 7.0);
+    /// ```
+    pub fn new(rows: [[f64; 4]; 4]) -> Matrix4 {
+        Matrix4 { rows }
+    }
+
+    /// Creates the identity matrix, which leaves everything where it is
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let identity = ray_tracer::math::matrix::Matrix4::identity();
+    /// let point = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0);
+    ///
+    /// assert_eq!(identity.transform_point(&point), point);
+    /// ```
+    pub fn identity() -> Matrix4 {
+        Matrix4::scale(1.0, 1.0, 1.0)
+    }
+
+    /// Creates a matrix that moves points by the given offset
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let offset = ray_tracer::vector3d::Vector3::new(1.0, -2.0, 3.0);
+    /// let translation = ray_tracer::math::matrix::Matrix4::translation(&offset);
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(1.0, 1.0, 1.0);
+    /// assert_eq!(translation.transform_point(&point), ray_tracer::vector3d::Vector3::new(2.0, -1.0, 4.0));
+    ///
+    /// // Vectors have no position, so they are not moved
+    /// assert_eq!(translation.transform_vector(&point), point);
+    ///
+    /// // The inverse moves the point back
+    /// let inverse = translation.inverse().unwrap();
+    /// let moved = translation.transform_point(&point);
+    /// assert_eq!(inverse.transform_point(&moved), point);
+    /// ```
+    pub fn translation(offset: &Vector3) -> Matrix4 {
+        Matrix4::new([
+            [1.0, 0.0, 0.0, offset.x],
+            [0.0, 1.0, 0.0, offset.y],
+            [0.0, 0.0, 1.0, offset.z],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Creates a matrix that scales about the origin by the given factor along each axis
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let scale = ray_tracer::math::matrix::Matrix4::scale(2.0, 3.0, -1.0);
+    /// let point = ray_tracer::vector3d::Vector3::new(1.0, 1.0, 1.0);
+    ///
+    /// assert_eq!(scale.transform_point(&point), ray_tracer::vector3d::Vector3::new(2.0, 3.0, -1.0));
+    /// ```
+    pub fn scale(x: f64, y: f64, z: f64) -> Matrix4 {
+        Matrix4::new([
+            [x, 0.0, 0.0, 0.0],
+            [0.0, y, 0.0, 0.0],
+            [0.0, 0.0, z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Creates a matrix that rotates about the given axis through the origin by the given
+    /// angle, in radians, following the right hand rule
+    ///
+    /// The axis does not need to be a unit vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let axis = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 2.0);
+    /// let rotation = ray_tracer::math::matrix::Matrix4::rotation(&axis, std::f64::consts::PI / 2.0);
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let expected = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// assert!(rotation.transform_point(&point).approx_eq(&expected, 1e-10));
+    /// ```
+    pub fn rotation(axis: &Vector3, angle_radians: f64) -> Matrix4 {
+        let axis = axis.into_unit();
+        // The columns are where the rotation takes each of the axes
+        let x = Vector3::new(1.0, 0.0, 0.0).rotate_around(&axis, angle_radians);
+        let y = Vector3::new(0.0, 1.0, 0.0).rotate_around(&axis, angle_radians);
+        let z = Vector3::new(0.0, 0.0, 1.0).rotate_around(&axis, angle_radians);
+        Matrix4::new([
+            [x.x, y.x, z.x, 0.0],
+            [x.y, y.y, z.y, 0.0],
+            [x.z, y.z, z.z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Creates a matrix that rotates about the x axis by the given angle, in radians
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let rotation = ray_tracer::math::matrix::Matrix4::rotation_x(std::f64::consts::PI / 2.0);
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    ///
+    /// let expected = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0);
+    /// assert!(rotation.transform_point(&point).approx_eq(&expected, 1e-10));
+    /// ```
+    pub fn rotation_x(angle_radians: f64) -> Matrix4 {
+        Matrix4::rotation(&Vector3::new(1.0, 0.0, 0.0), angle_radians)
+    }
+
+    /// Creates a matrix that rotates about the y axis by the given angle, in radians
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let rotation = ray_tracer::math::matrix::Matrix4::rotation_y(std::f64::consts::PI / 2.0);
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0);
+    ///
+    /// let expected = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// assert!(rotation.transform_point(&point).approx_eq(&expected, 1e-10));
+    /// ```
+    pub fn rotation_y(angle_radians: f64) -> Matrix4 {
+        Matrix4::rotation(&Vector3::new(0.0, 1.0, 0.0), angle_radians)
+    }
+
+    /// Creates a matrix that rotates about the z axis by the given angle, in radians
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let rotation = ray_tracer::math::matrix::Matrix4::rotation_z(std::f64::consts::PI / 2.0);
+    /// let point = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    ///
+    /// let expected = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// assert!(rotation.transform_point(&point).approx_eq(&expected, 1e-10));
+    /// ```
+    pub fn rotation_z(angle_radians: f64) -> Matrix4 {
+        Matrix4::rotation(&Vector3::new(0.0, 0.0, 1.0), angle_radians)
+    }
+
+    /// Multiplies this matrix by the given matrix, giving the transform that applies the given
+    /// matrix first, then this one
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let offset = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let translation = ray_tracer::math::matrix::Matrix4::translation(&offset);
+    /// let scale = ray_tracer::math::matrix::Matrix4::scale(2.0, 2.0, 2.0);
+    /// let point = ray_tracer::vector3d::Vector3::new(1.0, 1.0, 1.0);
+    ///
+    /// // Scaling then moving
+    /// let transform = translation.mul(&scale);
+    /// assert_eq!(transform.transform_point(&point), ray_tracer::vector3d::Vector3::new(3.0, 2.0, 2.0));
+    ///
+    /// // Moving then scaling
+    /// let transform = scale.mul(&translation);
+    /// assert_eq!(transform.transform_point(&point), ray_tracer::vector3d::Vector3::new(4.0, 2.0, 2.0));
+    /// ```
+    pub fn mul(&self, other: &Matrix4) -> Matrix4 {
+        let mut rows = [[0.0; 4]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, entry) in row.iter_mut().enumerate() {
+                *entry = (0..4).map(|k| self.rows[i][k] * other.rows[k][j]).sum();
+            }
+        }
+        Matrix4::new(rows)
+    }
+
+    /// Returns the transpose of this matrix, with its rows and columns swapped
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let offset = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0);
+    /// let transpose = ray_tracer::math::matrix::Matrix4::translation(&offset).transpose();
+    ///
+    /// assert_eq!(transpose.rows[3], [1.0, 2.0, 3.0, 1.0]);
+    /// ```
+    pub fn transpose(&self) -> Matrix4 {
+        let mut rows = [[0.0; 4]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, entry) in row.iter_mut().enumerate() {
+                *entry = self.rows[j][i];
+            }
+        }
+        Matrix4::new(rows)
+    }
+
+    /// Returns the inverse of this matrix, which undoes its transform, or "None" if it has no
+    /// inverse
+    ///
+    /// This uses Gauss-Jordan elimination with partial pivoting.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let offset = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0);
+    /// let axis = ray_tracer::vector3d::Vector3::new(1.0, 1.0, 0.0);
+    /// let transform = ray_tracer::math::matrix::Matrix4::translation(&offset)
+    ///     .mul(&ray_tracer::math::matrix::Matrix4::rotation(&axis, 0.5))
+    ///     .mul(&ray_tracer::math::matrix::Matrix4::scale(2.0, 3.0, 4.0));
+    ///
+    /// let inverse = transform.inverse().unwrap();
+    /// let point = ray_tracer::vector3d::Vector3::new(-1.0, 5.0, 0.5);
+    /// let moved = transform.transform_point(&point);
+    /// assert!(inverse.transform_point(&moved).approx_eq(&point, 1e-10));
+    ///
+    /// let flattened = ray_tracer::math::matrix::Matrix4::scale(1.0, 0.0, 1.0);
+    /// assert!(flattened.inverse().is_none());
+    /// ```
+    pub fn inverse(&self) -> Option<Matrix4> {
+        let mut left = self.rows;
+        let mut right = Matrix4::identity().rows;
+        for column in 0..4 {
+            // Swap the row with the largest entry in this column into place, for stability
+            let pivot = (column..4)
+                .max_by(|&a, &b| left[a][column].abs().total_cmp(&left[b][column].abs()))
+                .unwrap_or(column);
+            if left[pivot][column].abs() < 1e-12 {
+                return None;
+            }
+            left.swap(column, pivot);
+            right.swap(column, pivot);
+
+            let scale = 1.0 / left[column][column];
+            for j in 0..4 {
+                left[column][j] *= scale;
+                right[column][j] *= scale;
+            }
+            for row in 0..4 {
+                if row != column {
+                    let factor = left[row][column];
+                    for j in 0..4 {
+                        left[row][j] -= factor * left[column][j];
+                        right[row][j] -= factor * right[column][j];
+                    }
+                }
+            }
+        }
+        Some(Matrix4::new(right))
+    }
+
+    /// Transforms the given point, including the translation
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let offset = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let transform = ray_tracer::math::matrix::Matrix4::translation(&offset)
+    ///     .mul(&ray_tracer::math::matrix::Matrix4::scale(2.0, 1.0, 1.0));
+    /// let point = ray_tracer::vector3d::Vector3::new(1.0, 1.0, 0.0);
+    ///
+    /// assert_eq!(transform.transform_point(&point), ray_tracer::vector3d::Vector3::new(2.0, 2.0, 0.0));
+    /// ```
+    pub fn transform_point(&self, p: &Vector3) -> Vector3 {
+        self.transform_vector(p).add(&Vector3::new(
+            self.rows[0][3],
+            self.rows[1][3],
+            self.rows[2][3],
+        ))
+    }
+
+    /// Transforms the given vector, ignoring the translation
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let scale = ray_tracer::math::matrix::Matrix4::scale(2.0, 1.0, 1.0);
+    /// let vector = ray_tracer::vector3d::Vector3::new(1.0, 1.0, 0.0);
+    ///
+    /// assert_eq!(scale.transform_vector(&vector), ray_tracer::vector3d::Vector3::new(2.0, 1.0, 0.0));
+    /// ```
+    pub fn transform_vector(&self, v: &Vector3) -> Vector3 {
+        let row = |i: usize| self.rows[i][0] * v.x + self.rows[i][1] * v.y + self.rows[i][2] * v.z;
+        Vector3::new(row(0), row(1), row(2))
+    }
+}
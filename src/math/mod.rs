@@ -0,0 +1,54 @@
+//! This module contains the mathematical types used to position things in a scene
+
+/// The tolerance below which small values, such as lengths and distances along a ray, are
+/// treated as zero
+///
+/// # Example
+///
+/// ```
+/// use ray_tracer::object::Object;
+///
+/// assert!(ray_tracer::math::EPSILON > 0.0);
+/// assert!(ray_tracer::math::EPSILON < 1e-6);
+///
+/// // A ray starting on a sphere does not hit it again within EPSILON of its start
+/// let center = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+/// let material = ray_tracer::material::Material::default();
+/// let sphere = ray_tracer::object::sphere::Sphere::new(center, 1.0, material);
+/// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -4.0);
+/// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+/// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+/// assert!(sphere.ray_intersection(&ray).is_none());
+///
+/// // Leaving through the sphere, it is only hit on the far side
+/// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -4.0);
+/// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+/// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+/// assert!((sphere.ray_intersection(&ray).unwrap() - 2.0).abs() < ray_tracer::math::EPSILON);
+/// ```
+pub const EPSILON: f64 = 1e-9;
+
+/// How far from a surface shadow rays and other secondary rays start, so that the surface does
+/// not shadow or intersect itself
+///
+/// This is larger than `EPSILON`, to stay clear of the error in where a hit point is computed.
+///
+/// # Example
+///
+/// ```
+/// assert!(ray_tracer::math::SHADOW_EPSILON > ray_tracer::math::EPSILON);
+/// assert!(ray_tracer::math::SHADOW_EPSILON < 1e-3);
+/// ```
+pub const SHADOW_EPSILON: f64 = 1e-6;
+
+/// Vectors with every component smaller than this are treated as having no direction by
+/// `Vector3::near_zero`
+///
+/// # Example
+///
+/// ```
+/// assert!(ray_tracer::math::NEAR_ZERO > ray_tracer::math::EPSILON);
+/// ```
+pub const NEAR_ZERO: f64 = 1e-8;
+
+pub mod matrix;
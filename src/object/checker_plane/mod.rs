@@ -0,0 +1,188 @@
+//! This module defines an infinite checkered ground that fades into the background
+
+use super::plane::Plane;
+use super::Object;
+use crate::aabb::Aabb;
+use crate::color::Color;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene::hash::SceneHasher;
+use crate::texture::Checker;
+use crate::vector3d::Vector3;
+use std::hash::Hasher;
+use std::sync::Arc;
+
+/// The distance from the ray's start at which a new checker plane begins to fade
+pub const DEFAULT_FADE_START: f64 = 20.0;
+
+/// How quickly a new checker plane fades once past the start of its fade
+pub const DEFAULT_FADE_LENGTH: f64 = 50.0;
+
+/// The struct representing a checkered plane
+///
+/// Contains the Plane being drawn, with a checker texture, the distance at
+/// which it starts fading into the background, and the length over which it
+/// fades.
+///
+/// Far away, the checks become smaller than a pixel and alias into noise near
+/// the horizon, so the plane is blended into the background with distance.
+#[derive(Debug)]
+pub struct CheckerPlane {
+    pub plane: Plane,
+    pub fade_start: f64,
+    pub fade_length: f64,
+}
+
+impl CheckerPlane {
+    /// Creates a new checker plane through the given point with the given normal, alternating
+    /// between the given colors, with the default fade
+    ///
+    /// The normal does not need to be a unit vector, it is normalized here.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let normal = ray_tracer::vector3d::Vector3::new(0.0, 2.0, 0.0);
+    /// let black = ray_tracer::color::Color::new(0.0, 0.0, 0.0);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let ground = ray_tracer::object::checker_plane::CheckerPlane::new(point, normal, black, white);
+    ///
+    /// assert_eq!(ground.plane.normal.y, 1.0);
+    /// assert!(ground.plane.material.texture.is_some());
+    /// assert_eq!(ground.fade_start, ray_tracer::object::checker_plane::DEFAULT_FADE_START);
+    /// ```
+    pub fn new(point: Vector3, normal: Vector3, even: Color, odd: Color) -> CheckerPlane {
+        let material = Material {
+            texture: Some(Arc::new(Checker::new(even, odd))),
+            ..Default::default()
+        };
+        CheckerPlane {
+            plane: Plane::new(point, normal, material),
+            fade_start: DEFAULT_FADE_START,
+            fade_length: DEFAULT_FADE_LENGTH,
+        }
+    }
+
+    /// Sets the distance at which this plane starts to fade, and the length over which it fades
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let normal = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let black = ray_tracer::color::Color::new(0.0, 0.0, 0.0);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let ground = ray_tracer::object::checker_plane::CheckerPlane::new(point, normal, black, white)
+    ///     .with_fade(5.0, 10.0);
+    ///
+    /// assert_eq!(ground.fade_start, 5.0);
+    /// assert_eq!(ground.fade_length, 10.0);
+    /// ```
+    pub fn with_fade(mut self, start: f64, length: f64) -> CheckerPlane {
+        self.fade_start = start;
+        self.fade_length = length;
+        self
+    }
+
+    /// Returns how much this plane has faded into the background at the given distance, from
+    /// 0.0 to 1.0
+    ///
+    /// Nothing fades up to the fade start. Past it, the fade is _1 - e^(-(d - start) / length)_,
+    /// which approaches the background without a visible edge.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let normal = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let black = ray_tracer::color::Color::new(0.0, 0.0, 0.0);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let ground = ray_tracer::object::checker_plane::CheckerPlane::new(point, normal, black, white)
+    ///     .with_fade(20.0, 50.0);
+    ///
+    /// // Near hits are unfaded
+    /// assert_eq!(ground.fade_factor(1.0), 0.0);
+    /// assert_eq!(ground.fade_factor(20.0), 0.0);
+    ///
+    /// // The fade grows with distance
+    /// assert!(ground.fade_factor(50.0) > 0.0);
+    /// assert!(ground.fade_factor(100.0) > ground.fade_factor(50.0));
+    ///
+    /// // Very distant hits are almost entirely background
+    /// assert!(ground.fade_factor(1.0e4) > 0.99);
+    /// assert!(ground.fade_factor(1.0e4) <= 1.0);
+    /// ```
+    pub fn fade_factor(&self, distance: f64) -> f64 {
+        if distance <= self.fade_start {
+            return 0.0;
+        }
+        if self.fade_length <= 0.0 {
+            return 1.0;
+        }
+        1.0 - (-(distance - self.fade_start) / self.fade_length).exp()
+    }
+}
+
+impl Object for CheckerPlane {
+    fn ray_intersection(&self, r: &Ray) -> Option<f64> {
+        self.plane.ray_intersection(r)
+    }
+
+    fn normal_at(&self, point: &Vector3) -> Vector3 {
+        self.plane.normal_at(point)
+    }
+
+    fn uv_at(&self, point: &Vector3) -> (f64, f64) {
+        self.plane.uv_at(point)
+    }
+
+    /// Returns this plane's fade factor at the given distance
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let normal = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let black = ray_tracer::color::Color::new(0.0, 0.0, 0.0);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let ground = ray_tracer::object::checker_plane::CheckerPlane::new(point, normal, black, white)
+    ///     .with_fade(2.0, 1.0);
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let straight_down = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let near = ground.hit(&ray_tracer::ray::Ray::new(ray_position.clone(), straight_down));
+    /// assert_eq!(near.unwrap().background_fade, 0.0);
+    ///
+    /// let towards_horizon = ray_tracer::vector3d::Vector3::new(0.0, -0.01, -1.0);
+    /// let far = ground.hit(&ray_tracer::ray::Ray::new(ray_position, towards_horizon));
+    /// assert!(far.unwrap().background_fade > 0.99);
+    /// ```
+    fn background_fade(&self, distance: f64) -> f64 {
+        self.fade_factor(distance)
+    }
+
+    /// Planes are infinite, so they cannot be bounded.
+    fn bounding_box(&self) -> Option<Aabb> {
+        None
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"CheckerPlane");
+        self.plane.hash_into(hasher);
+        hasher.write_f64(self.fade_start);
+        hasher.write_f64(self.fade_length);
+    }
+
+    /// Returns this plane's material
+    fn material(&self) -> &Material {
+        &self.plane.material
+    }
+
+    /// Returns the point on this plane
+    fn position(&self) -> &Vector3 {
+        &self.plane.point
+    }
+}
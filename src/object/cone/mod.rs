@@ -0,0 +1,331 @@
+//! This module defines a struct representing a finite circular Cone
+
+use super::Object;
+use crate::aabb::Aabb;
+use crate::material::Material;
+use crate::math::EPSILON;
+use crate::ray::Ray;
+use crate::scene::hash::SceneHasher;
+use crate::vector3d::Vector3;
+use std::hash::Hasher;
+
+/// The struct representing a cone
+///
+/// Contains a Vector3 for the cone's apex, a unit Vector3 axis pointing from
+/// the apex towards the base, the angle between the axis and the side, in
+/// radians, the height of the cone along its axis, and whether the base is
+/// closed by a flat cap.
+#[derive(Debug, Default)]
+pub struct Cone {
+    pub apex: Vector3,
+    pub axis: Vector3,
+    pub half_angle: f64,
+    pub height: f64,
+    pub capped: bool,
+    pub material: Material,
+}
+
+impl Cone {
+    /// Creates a new capped cone with the given apex, axis, half-angle in radians, and height
+    ///
+    /// The axis does not need to be a unit vector, it is normalized here.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let apex = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let axis = ray_tracer::vector3d::Vector3::new(0.0, -2.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let cone = ray_tracer::object::cone::Cone::new(apex, axis, 0.5, 2.0, material);
+    ///
+    /// assert_eq!(cone.axis.y, -1.0);
+    /// assert_eq!(cone.height, 2.0);
+    /// assert!(cone.capped);
+    /// ```
+    pub fn new(
+        apex: Vector3,
+        axis: Vector3,
+        half_angle: f64,
+        height: f64,
+        material: Material,
+    ) -> Cone {
+        Cone {
+            apex,
+            axis: axis.into_unit(),
+            half_angle,
+            height,
+            capped: true,
+            material,
+        }
+    }
+
+    /// Returns the center of the cone's base
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let apex = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let axis = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let cone = ray_tracer::object::cone::Cone::new(apex, axis, 0.5, 2.0, material);
+    ///
+    /// assert_eq!(cone.base_center(), ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0));
+    /// ```
+    pub fn base_center(&self) -> Vector3 {
+        self.apex.add(&self.axis.mul(self.height))
+    }
+
+    /// Returns the radius of the cone's base
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let apex = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let axis = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let half_angle = std::f64::consts::PI / 4.0;
+    /// let cone = ray_tracer::object::cone::Cone::new(apex, axis, half_angle, 2.0, material);
+    ///
+    /// assert!((cone.base_radius() - 2.0).abs() < 1e-10);
+    /// ```
+    pub fn base_radius(&self) -> f64 {
+        self.height * self.half_angle.tan()
+    }
+
+    /// Returns the _t_ of each crossing of the line of the given ray with the side of the cone,
+    /// in increasing order
+    ///
+    /// The side is the set of points _X_, relative to the apex, where
+    /// _(X·v)² = |X|² cos²θ_, with _v_ the axis and _θ_ the half-angle. This also describes
+    /// a mirrored cone above the apex, so only points between the apex and the base count.
+    fn side_crossings(&self, r: &Ray) -> Vec<f64> {
+        let cos_squared = self.half_angle.cos().powi(2);
+        let apex_to_ray = r.pos.sub(&self.apex);
+        let dir_dot_axis = r.dir.dot(&self.axis);
+        let offset_dot_axis = apex_to_ray.dot(&self.axis);
+
+        let a = dir_dot_axis * dir_dot_axis - cos_squared * r.dir.length_squared();
+        let half_b = dir_dot_axis * offset_dot_axis - cos_squared * r.dir.dot(&apex_to_ray);
+        let c = offset_dot_axis * offset_dot_axis - cos_squared * apex_to_ray.length_squared();
+
+        let roots = if a == 0.0 {
+            // The ray is parallel to the side, so it crosses the cone at most once
+            if half_b == 0.0 {
+                return vec![];
+            }
+            vec![-c / (2.0 * half_b)]
+        } else {
+            let discriminant = half_b * half_b - a * c;
+            if discriminant < 0.0 {
+                return vec![];
+            }
+            let sqrt_discriminant = discriminant.sqrt();
+            let t0 = (-half_b - sqrt_discriminant) / a;
+            let t1 = (-half_b + sqrt_discriminant) / a;
+            vec![t0.min(t1), t0.max(t1)]
+        };
+
+        roots
+            .into_iter()
+            .filter(|&t| (0.0..=self.height).contains(&(offset_dot_axis + t * dir_dot_axis)))
+            .collect()
+    }
+
+    /// Returns the _t_ at which the line of the given ray crosses the cap over the cone's base,
+    /// if it does
+    fn cap_crossing(&self, r: &Ray) -> Option<f64> {
+        let dir_dot_axis = r.dir.dot(&self.axis);
+        if dir_dot_axis == 0.0 {
+            return None;
+        }
+
+        let center = self.base_center();
+        let t = center.sub(&r.pos).dot(&self.axis) / dir_dot_axis;
+        let radius = self.base_radius();
+        if r.at(t).distance_squared(&center) <= radius * radius {
+            Some(t)
+        } else {
+            None
+        }
+    }
+}
+
+impl Object for Cone {
+    /// Calculates if and where the given ray intersects with this cone.
+    ///
+    /// The side is found by solving a quadratic in _t_, keeping only the roots between the apex
+    /// and the base. If the cone is capped, the ray is also tested against the disk closing the
+    /// base, and the nearest of all the intersections is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// // A cone pointing up, with its apex at y = 1 and its base at y = -1
+    /// let apex = ray_tracer::vector3d::Vector3::new(0.0, 1.0, -5.0);
+    /// let axis = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let half_angle = std::f64::consts::PI / 4.0;
+    /// let material = ray_tracer::material::Material::default();
+    /// let mut cone = ray_tracer::object::cone::Cone::new(apex, axis, half_angle, 2.0, material);
+    ///
+    /// // At y = 0 the cone has a radius of 1, so the side is hit 1 unit before its axis
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let side_ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert!((cone.ray_intersection(&side_ray).unwrap() - 4.0).abs() < 1e-10);
+    ///
+    /// // Below the base, the ray passes the mirrored cone beyond the height, and misses
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, -1.5, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let below_ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert!(cone.ray_intersection(&below_ray).is_none());
+    ///
+    /// // From below, the cap is hit first, and without it the ray hits the inside of the side
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.5, -5.0, -5.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let up_ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert!((cone.ray_intersection(&up_ray).unwrap() - 4.0).abs() < 1e-10);
+    ///
+    /// cone.capped = false;
+    /// assert!((cone.ray_intersection(&up_ray).unwrap() - 5.5).abs() < 1e-10);
+    /// ```
+    fn ray_intersection(&self, r: &Ray) -> Option<f64> {
+        let side = self.side_crossings(r).into_iter().find(|&t| r.in_bounds(t));
+        let cap = if self.capped {
+            self.cap_crossing(r).filter(|&t| r.in_bounds(t))
+        } else {
+            None
+        };
+        match (side, cap) {
+            (Some(side), Some(cap)) => Some(side.min(cap)),
+            (side, cap) => side.or(cap),
+        }
+    }
+
+    /// Returns the outward facing unit normal of this cone at the given point.
+    ///
+    /// On the cap, this is the axis. On the side, it is perpendicular to the line from the apex
+    /// through the point, tilted back towards the apex by the half-angle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let apex = ray_tracer::vector3d::Vector3::new(0.0, 1.0, -5.0);
+    /// let axis = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let half_angle = std::f64::consts::PI / 4.0;
+    /// let material = ray_tracer::material::Material::default();
+    /// let cone = ray_tracer::object::cone::Cone::new(apex, axis, half_angle, 2.0, material);
+    ///
+    /// let side_point = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -4.0);
+    /// let side_normal = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 1.0).into_unit();
+    /// assert!(cone.normal_at(&side_point).approx_eq(&side_normal, 1e-10));
+    ///
+    /// let cap_point = ray_tracer::vector3d::Vector3::new(0.5, -1.0, -5.0);
+    /// let cap_normal = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// assert_eq!(cone.normal_at(&cap_point), cap_normal);
+    /// ```
+    fn normal_at(&self, point: &Vector3) -> Vector3 {
+        let apex_to_point = point.sub(&self.apex);
+        let height = apex_to_point.dot(&self.axis);
+        // Points this close to the plane of the base lie on the cap
+        if self.capped && (height - self.height).abs() < EPSILON * self.height.max(1.0) {
+            return self.axis.clone();
+        }
+
+        // The gradient of the cone's equation, negated to point away from the axis
+        let cos_squared = self.half_angle.cos().powi(2);
+        apex_to_point
+            .mul(cos_squared)
+            .sub(&self.axis.mul(height))
+            .try_into_unit()
+            .unwrap_or_else(|| self.axis.negate())
+    }
+
+    /// Returns the range of _t_ in which the line of the given ray is inside this cone.
+    ///
+    /// A capped cone is convex, so the line is inside it between its first and last crossing
+    /// of the surface. Without its cap the cone has no inside, so there are no ranges.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let apex = ray_tracer::vector3d::Vector3::new(0.0, 1.0, -5.0);
+    /// let axis = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let half_angle = std::f64::consts::PI / 4.0;
+    /// let material = ray_tracer::material::Material::default();
+    /// let mut cone = ray_tracer::object::cone::Cone::new(apex, axis, half_angle, 2.0, material);
+    ///
+    /// // From below, the ray enters through the cap and leaves through the side
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.5, -5.0, -5.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let up_ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// let (entry, exit) = cone.intervals(&up_ray)[0];
+    /// assert!((entry - 4.0).abs() < 1e-10 && (exit - 5.5).abs() < 1e-10);
+    ///
+    /// // At y = 0 the cone has a radius of 1, and it counts even behind the start of the ray
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// let (entry, exit) = cone.intervals(&ray)[0];
+    /// assert!((entry + 6.0).abs() < 1e-10 && (exit + 4.0).abs() < 1e-10);
+    ///
+    /// cone.capped = false;
+    /// assert!(cone.intervals(&up_ray).is_empty());
+    /// ```
+    fn intervals(&self, r: &Ray) -> Vec<(f64, f64)> {
+        if !self.capped {
+            return vec![];
+        }
+        let mut crossings = self.side_crossings(r);
+        crossings.extend(self.cap_crossing(r));
+        let entry = crossings.iter().copied().fold(f64::INFINITY, f64::min);
+        let exit = crossings.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        if entry < exit {
+            vec![(entry, exit)]
+        } else {
+            vec![]
+        }
+    }
+
+    /// Returns the box bounding the apex and the disk of the base
+    fn bounding_box(&self) -> Option<Aabb> {
+        let center = self.base_center();
+        let radius = self.base_radius();
+        // The disk reaches furthest along the world axes least aligned with its own axis
+        let extent = Vector3::new(
+            radius * (1.0 - self.axis.x * self.axis.x).max(0.0).sqrt(),
+            radius * (1.0 - self.axis.y * self.axis.y).max(0.0).sqrt(),
+            radius * (1.0 - self.axis.z * self.axis.z).max(0.0).sqrt(),
+        );
+        Some(Aabb::from_points(&[
+            self.apex.clone(),
+            center.sub(&extent),
+            center.add(&extent),
+        ]))
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"Cone");
+        hasher.write_vector(&self.apex);
+        hasher.write_vector(&self.axis);
+        hasher.write_f64(self.half_angle);
+        hasher.write_f64(self.height);
+        hasher.write_u8(u8::from(self.capped));
+        hasher.write_material(&self.material);
+    }
+
+    /// Returns this cone's material
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    /// Returns the apex of this cone
+    fn position(&self) -> &Vector3 {
+        &self.apex
+    }
+}
@@ -0,0 +1,356 @@
+//! This module defines constructive solid geometry, which combines two Objects into one
+
+use super::{HitRecord, Object};
+use crate::aabb::Aabb;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene::hash::SceneHasher;
+use crate::vector3d::Vector3;
+use std::hash::Hasher;
+
+/// How close to a point, in multiples of the distance from the origin, a child's surface must be
+/// for the point to be treated as lying on it
+const SURFACE_EPSILON: f64 = 1e-6;
+
+/// The boolean operations that can combine two objects
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsgOperation {
+    /// Everything inside either object
+    Union,
+    /// Everything inside both objects
+    Intersection,
+    /// Everything inside the left object but not the right one
+    Difference,
+}
+
+impl CsgOperation {
+    /// Returns whether a point is inside the combined object, given whether it is inside the
+    /// left and right objects
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::csg::CsgOperation;
+    ///
+    /// assert!(CsgOperation::Union.contains(false, true));
+    /// assert!(!CsgOperation::Intersection.contains(false, true));
+    /// assert!(CsgOperation::Difference.contains(true, false));
+    /// assert!(!CsgOperation::Difference.contains(true, true));
+    /// ```
+    pub fn contains(self, in_left: bool, in_right: bool) -> bool {
+        match self {
+            CsgOperation::Union => in_left || in_right,
+            CsgOperation::Intersection => in_left && in_right,
+            CsgOperation::Difference => in_left && !in_right,
+        }
+    }
+}
+
+/// The struct representing two objects combined by a boolean operation
+///
+/// Contains the left and right objects, and the operation combining them.
+///
+/// Both objects must have an inside, reported by `Object::intervals`. Each
+/// ray finds the ranges of _t_ in which it is inside each object, combines
+/// them, and hits the surface of whichever object bounds the first combined
+/// range.
+pub struct Csg {
+    pub left: Box<dyn Object>,
+    pub right: Box<dyn Object>,
+    pub operation: CsgOperation,
+}
+
+impl Csg {
+    /// Combines the given objects with the given operation
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let left = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let right = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -4.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let csg = ray_tracer::object::csg::Csg::new(
+    ///     Box::new(left),
+    ///     Box::new(right),
+    ///     ray_tracer::object::csg::CsgOperation::Union,
+    /// );
+    ///
+    /// assert_eq!(csg.position(), &ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0));
+    /// ```
+    pub fn new(left: Box<dyn Object>, right: Box<dyn Object>, operation: CsgOperation) -> Csg {
+        Csg {
+            left,
+            right,
+            operation,
+        }
+    }
+
+    /// Returns the first boundary of the combined object along the given ray, within its
+    /// bounds, and the child whose surface it lies on
+    fn first_boundary(&self, r: &Ray) -> Option<(f64, &dyn Object, bool)> {
+        let left = self.left.intervals(r);
+        let right = self.right.intervals(r);
+        let t = combine(&left, &right, self.operation)
+            .into_iter()
+            .flat_map(|(start, end)| vec![start, end])
+            .find(|&t| r.in_bounds(t))?;
+
+        // The combined boundaries are copied from the children's, so they match exactly
+        let on_left = left.iter().any(|&(start, end)| t == start || t == end);
+        if on_left {
+            Some((t, self.left.as_ref(), false))
+        } else {
+            Some((
+                t,
+                self.right.as_ref(),
+                self.operation == CsgOperation::Difference,
+            ))
+        }
+    }
+}
+
+impl Object for Csg {
+    /// Calculates if and where the given ray intersects with the combined object.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// // The right sphere bites into the front of the left sphere
+    /// let left = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let right = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -4.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let csg = ray_tracer::object::csg::Csg::new(
+    ///     Box::new(left),
+    ///     Box::new(right),
+    ///     ray_tracer::object::csg::CsgOperation::Difference,
+    /// );
+    ///
+    /// // Through the middle, the ray hits the bottom of the bite, the back of the right sphere
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction.clone());
+    /// assert_eq!(csg.ray_intersection(&ray), Some(5.0));
+    ///
+    /// let record = csg.hit(&ray).unwrap();
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// assert!(record.front_face);
+    ///
+    /// // Near the edge, where the spheres do not overlap, the left sphere is hit as usual
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, -0.9, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert!((csg.ray_intersection(&ray).unwrap() - (5.0 - 0.19f64.sqrt())).abs() < 1e-10);
+    ///
+    /// // Across the part of the right sphere outside of the left one, nothing is hit
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(-5.0, 0.0, -3.2);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert!(csg.ray_intersection(&ray).is_none());
+    /// ```
+    fn ray_intersection(&self, r: &Ray) -> Option<f64> {
+        self.first_boundary(r).map(|(t, _, _)| t)
+    }
+
+    /// Returns the outward facing unit normal of the combined object at the given point.
+    ///
+    /// This is the normal of whichever child's surface the point lies on. Where the right
+    /// object is cut out of the left one, its normal is turned to face out of the hole.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let left = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let right = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -4.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let csg = ray_tracer::object::csg::Csg::new(
+    ///     Box::new(left),
+    ///     Box::new(right),
+    ///     ray_tracer::object::csg::CsgOperation::Difference,
+    /// );
+    ///
+    /// let back = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -6.0);
+    /// assert_eq!(csg.normal_at(&back), ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0));
+    ///
+    /// let bite = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// assert_eq!(csg.normal_at(&bite), ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// ```
+    fn normal_at(&self, point: &Vector3) -> Vector3 {
+        let on_right = on_surface(self.right.as_ref(), point);
+        if on_right && !on_surface(self.left.as_ref(), point) {
+            let normal = self.right.normal_at(point);
+            if self.operation == CsgOperation::Difference {
+                normal.negate()
+            } else {
+                normal
+            }
+        } else {
+            self.left.normal_at(point)
+        }
+    }
+
+    /// Records the intersection with the surface of the child that was hit, using that child's
+    /// own hit record, so that its normal, texture coordinates, and material are kept.
+    ///
+    /// Where the right object is cut out of the left one, the ray hits the inside of the right
+    /// object's surface from the outside of the combined object, so the front face is swapped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let red = ray_tracer::material::Material {
+    ///     color: ray_tracer::color::Color::new(1.0, 0.0, 0.0),
+    ///     ..Default::default()
+    /// };
+    /// let blue = ray_tracer::material::Material {
+    ///     color: ray_tracer::color::Color::new(0.0, 0.0, 1.0),
+    ///     ..Default::default()
+    /// };
+    /// let left = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     red,
+    /// );
+    /// let right = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -4.0),
+    ///     1.0,
+    ///     blue,
+    /// );
+    /// let csg = ray_tracer::object::csg::Csg::new(
+    ///     Box::new(left),
+    ///     Box::new(right),
+    ///     ray_tracer::object::csg::CsgOperation::Difference,
+    /// );
+    ///
+    /// // The bottom of the bite is the back of the right sphere, so it is blue
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let record = csg.hit(&ray).unwrap();
+    /// assert_eq!(record.t, 5.0);
+    /// assert_eq!(record.material.color, ray_tracer::color::Color::new(0.0, 0.0, 1.0));
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// assert!(record.front_face);
+    /// ```
+    fn hit(&self, r: &Ray) -> Option<HitRecord<'_>> {
+        let (t, child, flip) = self.first_boundary(r)?;
+        // Only let the child find the boundary that was already found from its ranges
+        let tolerance = SURFACE_EPSILON * t.abs().max(1.0);
+        let around = Ray::new_bounded(r.pos.clone(), r.dir.clone(), t - tolerance, t + tolerance)
+            .at_time(r.time);
+        let record = child.hit(&around)?;
+        Some(HitRecord {
+            front_face: record.front_face != flip,
+            ..record
+        })
+    }
+
+    fn intervals(&self, r: &Ray) -> Vec<(f64, f64)> {
+        combine(
+            &self.left.intervals(r),
+            &self.right.intervals(r),
+            self.operation,
+        )
+    }
+
+    /// Returns the box bounding everything the operation can keep
+    ///
+    /// A union is bounded by both children's boxes, while an intersection or difference can only
+    /// keep parts of the left object.
+    fn bounding_box(&self) -> Option<Aabb> {
+        let left = self.left.bounding_box()?;
+        match self.operation {
+            CsgOperation::Union => Some(left.union(&self.right.bounding_box()?)),
+            CsgOperation::Intersection | CsgOperation::Difference => Some(left),
+        }
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"Csg");
+        self.left.hash_into(hasher);
+        self.right.hash_into(hasher);
+        hasher.write_u8(match self.operation {
+            CsgOperation::Union => 0,
+            CsgOperation::Intersection => 1,
+            CsgOperation::Difference => 2,
+        });
+    }
+
+    /// Returns the left object's material
+    fn material(&self) -> &Material {
+        self.left.material()
+    }
+
+    /// Returns the left object's position
+    fn position(&self) -> &Vector3 {
+        self.left.position()
+    }
+}
+
+/// Combines the given sorted ranges of _t_ inside the left and right objects with the given
+/// operation, returning the sorted ranges inside the combined object
+///
+/// Between neighbouring boundaries of either object, a point is either inside or outside of
+/// each, so each span between them is tested at its middle.
+fn combine(left: &[(f64, f64)], right: &[(f64, f64)], operation: CsgOperation) -> Vec<(f64, f64)> {
+    let mut boundaries: Vec<f64> = left
+        .iter()
+        .chain(right)
+        .flat_map(|&(start, end)| vec![start, end])
+        .collect();
+    boundaries.sort_by(|a, b| a.total_cmp(b));
+    boundaries.dedup();
+
+    let inside = |ranges: &[(f64, f64)], t: f64| ranges.iter().any(|&(s, e)| s <= t && t <= e);
+    let mut combined: Vec<(f64, f64)> = vec![];
+    for span in boundaries.windows(2) {
+        let middle = 0.5 * (span[0] + span[1]);
+        if !operation.contains(inside(left, middle), inside(right, middle)) {
+            continue;
+        }
+        match combined.last_mut() {
+            Some(last) if last.1 == span[0] => last.1 = span[1],
+            _ => combined.push((span[0], span[1])),
+        }
+    }
+    combined
+}
+
+/// Returns whether the given point lies on the surface of the given object, by checking whether
+/// the line through the point along the object's normal crosses its surface there
+fn on_surface(object: &dyn Object, point: &Vector3) -> bool {
+    let line = Ray::new(point.clone(), object.normal_at(point));
+    let tolerance = SURFACE_EPSILON * point.length().max(1.0);
+    object
+        .intervals(&line)
+        .iter()
+        .any(|&(start, end)| start.abs() < tolerance || end.abs() < tolerance)
+}
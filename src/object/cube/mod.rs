@@ -0,0 +1,174 @@
+//! This module defines a struct representing an axis-aligned box
+
+use super::Object;
+use crate::aabb::Aabb;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene::hash::SceneHasher;
+use crate::vector3d::Vector3;
+use std::hash::Hasher;
+
+/// The struct representing an axis-aligned box
+///
+/// Contains a Vector3 for the corner of the box with the smallest coordinates,
+/// and a Vector3 for the opposite corner, with the largest.
+#[derive(Debug, Default)]
+pub struct BoxObject {
+    pub min: Vector3,
+    pub max: Vector3,
+    pub material: Material,
+}
+
+impl BoxObject {
+    /// Creates a new box between the given corners
+    ///
+    /// The corners may be given in any order, each coordinate is sorted here.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let corner1 = ray_tracer::vector3d::Vector3::new(1.0, -1.0, -4.0);
+    /// let corner2 = ray_tracer::vector3d::Vector3::new(-1.0, 1.0, -6.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let cube = ray_tracer::object::cube::BoxObject::new(corner1, corner2, material);
+    ///
+    /// assert_eq!(cube.min, ray_tracer::vector3d::Vector3::new(-1.0, -1.0, -6.0));
+    /// assert_eq!(cube.max, ray_tracer::vector3d::Vector3::new(1.0, 1.0, -4.0));
+    /// ```
+    pub fn new(corner1: Vector3, corner2: Vector3, material: Material) -> BoxObject {
+        BoxObject {
+            min: Vector3::new(
+                corner1.x.min(corner2.x),
+                corner1.y.min(corner2.y),
+                corner1.z.min(corner2.z),
+            ),
+            max: Vector3::new(
+                corner1.x.max(corner2.x),
+                corner1.y.max(corner2.y),
+                corner1.z.max(corner2.z),
+            ),
+            material,
+        }
+    }
+
+    /// Returns the range of _t_ in which the line of the given ray is inside this box, or "None"
+    /// if it misses
+    fn line_interval(&self, r: &Ray) -> Option<(f64, f64)> {
+        Aabb::new(self.min.clone(), self.max.clone()).slab_interval(
+            r,
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+        )
+    }
+}
+
+impl Object for BoxObject {
+    /// Calculates if and where the given ray intersects with this box.
+    ///
+    /// The ray enters the box at the start of the range found by the slab method, and leaves at
+    /// its end. The first of these within the ray's bounds is returned, so rays starting inside
+    /// the box hit the face they leave through.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let min = ray_tracer::vector3d::Vector3::new(-1.0, -1.0, -6.0);
+    /// let max = ray_tracer::vector3d::Vector3::new(1.0, 1.0, -4.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let cube = ray_tracer::object::cube::BoxObject::new(min, max, material);
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert_eq!(cube.ray_intersection(&ray), Some(4.0));
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let inside_ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert_eq!(cube.ray_intersection(&inside_ray), Some(1.0));
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let missing_ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert!(cube.ray_intersection(&missing_ray).is_none());
+    /// ```
+    fn ray_intersection(&self, r: &Ray) -> Option<f64> {
+        let (enter, exit) = self.line_interval(r)?;
+        [enter, exit].iter().copied().find(|&t| r.in_bounds(t))
+    }
+
+    /// Returns the outward facing unit normal of the face of this box nearest the given point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let min = ray_tracer::vector3d::Vector3::new(-1.0, -1.0, -6.0);
+    /// let max = ray_tracer::vector3d::Vector3::new(1.0, 1.0, -4.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let cube = ray_tracer::object::cube::BoxObject::new(min, max, material);
+    ///
+    /// // A ray along -x hits the +x face
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(5.0, 0.5, -5.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(-1.0, 0.0, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// let record = cube.hit(&ray).unwrap();
+    /// assert_eq!(record.point, ray_tracer::vector3d::Vector3::new(1.0, 0.5, -5.0));
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0));
+    ///
+    /// // A ray going up hits the -y face
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.2, -3.0, -4.5);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// let record = cube.hit(&ray).unwrap();
+    /// assert_eq!(record.t, 2.0);
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0));
+    /// assert!(record.front_face);
+    /// ```
+    fn normal_at(&self, point: &Vector3) -> Vector3 {
+        let p = point.to_array();
+        let (min, max) = (self.min.to_array(), self.max.to_array());
+        let mut normal = [0.0; 3];
+        let mut nearest = f64::INFINITY;
+        let mut face = (0, 0.0);
+        for axis in 0..3 {
+            for &(distance, sign) in &[(p[axis] - min[axis], -1.0), (max[axis] - p[axis], 1.0)] {
+                if distance.abs() < nearest {
+                    nearest = distance.abs();
+                    face = (axis, sign);
+                }
+            }
+        }
+        normal[face.0] = face.1;
+        Vector3::new(normal[0], normal[1], normal[2])
+    }
+
+    fn intervals(&self, r: &Ray) -> Vec<(f64, f64)> {
+        self.line_interval(r).into_iter().collect()
+    }
+
+    /// Returns the box itself
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(Aabb::new(self.min.clone(), self.max.clone()))
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"BoxObject");
+        hasher.write_vector(&self.min);
+        hasher.write_vector(&self.max);
+        hasher.write_material(&self.material);
+    }
+
+    /// Returns this box's material
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    /// Returns the corner of this box with the smallest coordinates
+    fn position(&self) -> &Vector3 {
+        &self.min
+    }
+}
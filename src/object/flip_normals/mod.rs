@@ -0,0 +1,178 @@
+//! This module defines a wrapper that turns another Object inside out
+
+use super::{HitRecord, Object};
+use crate::aabb::Aabb;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene::hash::SceneHasher;
+use crate::vector3d::Vector3;
+use std::hash::Hasher;
+
+/// The struct representing an object with its normals flipped
+///
+/// Contains the object being flipped. Rays hit it in the same places, and hit
+/// records still face their normals back along the ray, so only which side of
+/// the surface counts as its front changes. Transparent objects then bend
+/// light as if rays leaving them were entering them, so that, for example, a
+/// glass sphere becomes a bubble of air.
+pub struct FlipNormals {
+    pub inner: Box<dyn Object>,
+}
+
+impl FlipNormals {
+    /// Wraps the given object, flipping its normals
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let object = ray_tracer::object::flip_normals::FlipNormals::new(Box::new(sphere));
+    ///
+    /// assert_eq!(object.position(), &ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0));
+    /// ```
+    pub fn new(inner: Box<dyn Object>) -> FlipNormals {
+        FlipNormals { inner }
+    }
+}
+
+impl Object for FlipNormals {
+    fn ray_intersection(&self, r: &Ray) -> Option<f64> {
+        self.inner.ray_intersection(r)
+    }
+
+    /// Returns the inner object's normal at the given point, negated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let inner = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     2.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let point = ray_tracer::vector3d::Vector3::new(1.0, 1.0, -5.0 + 2.0f64.sqrt());
+    /// let inner_normal = inner.normal_at(&point);
+    ///
+    /// let object = ray_tracer::object::flip_normals::FlipNormals::new(Box::new(inner));
+    /// assert_eq!(object.normal_at(&point), inner_normal.negate());
+    ///
+    /// // From inside the sphere, the ray now hits the front of its surface
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let record = object.hit(&ray).unwrap();
+    /// assert_eq!(record.t, 2.0);
+    /// assert!(record.front_face);
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// ```
+    fn normal_at(&self, point: &Vector3) -> Vector3 {
+        self.inner.normal_at(point).negate()
+    }
+
+    /// Returns the inner object's hit record with its front face swapped.
+    ///
+    /// Negating the outward normal leaves the normal facing back along the ray unchanged, so
+    /// only `front_face` differs from the inner object's hit, which keeps its own normal and
+    /// material.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let start = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let end = ray_tracer::vector3d::Vector3::new(2.0, 0.0, -5.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::moving_sphere::MovingSphere::new(start, end, 0.5, material);
+    /// let object = ray_tracer::object::flip_normals::FlipNormals::new(Box::new(sphere));
+    ///
+    /// // At the end, the sphere is hit where it has moved to, on the back of its flipped surface
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction).at_time(1.0);
+    ///
+    /// let record = object.hit(&ray).unwrap();
+    /// assert_eq!(record.t, 4.5);
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// assert!(!record.front_face);
+    /// ```
+    fn hit(&self, r: &Ray) -> Option<HitRecord<'_>> {
+        let record = self.inner.hit(r)?;
+        Some(HitRecord {
+            front_face: !record.front_face,
+            ..record
+        })
+    }
+
+    fn uv_at(&self, point: &Vector3) -> (f64, f64) {
+        self.inner.uv_at(point)
+    }
+
+    fn barycentric_at(&self, point: &Vector3) -> Option<[f64; 3]> {
+        self.inner.barycentric_at(point)
+    }
+
+    fn background_fade(&self, distance: f64) -> f64 {
+        self.inner.background_fade(distance)
+    }
+
+    fn intervals(&self, r: &Ray) -> Vec<(f64, f64)> {
+        self.inner.intervals(r)
+    }
+
+    /// Returns the inner object's bounding box
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.inner.bounding_box()
+    }
+
+    /// Returns the inner object's bounding sphere
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     2.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let object = ray_tracer::object::flip_normals::FlipNormals::new(Box::new(sphere));
+    ///
+    /// let center = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// assert_eq!(object.bounding_sphere(), Some((center, 2.0)));
+    ///
+    /// // Its inside is the inner object's too, so it can still be combined in CSG
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert_eq!(object.intervals(&ray), vec![(3.0, 7.0)]);
+    /// ```
+    fn bounding_sphere(&self) -> Option<(Vector3, f64)> {
+        self.inner.bounding_sphere()
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"FlipNormals");
+        self.inner.hash_into(hasher);
+    }
+
+    /// Returns the inner object's material
+    fn material(&self) -> &Material {
+        self.inner.material()
+    }
+
+    /// Returns the inner object's position
+    fn position(&self) -> &Vector3 {
+        self.inner.position()
+    }
+}
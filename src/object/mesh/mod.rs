@@ -0,0 +1,179 @@
+//! This module loads triangle meshes from Wavefront OBJ files
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+
+use super::triangle::Triangle;
+use crate::material::Material;
+use crate::vector3d::Vector3;
+
+/// An error from loading an OBJ file
+#[derive(Debug)]
+pub enum ObjError {
+    /// The file could not be read
+    Io(io::Error),
+    /// The line with the given number (counting from 1) could not be parsed
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ObjError::Io(e) => write!(f, "could not read OBJ file: {}", e),
+            ObjError::Parse { line, message } => write!(f, "OBJ line {}: {}", line, message),
+        }
+    }
+}
+
+impl Error for ObjError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ObjError::Io(e) => Some(e),
+            ObjError::Parse { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for ObjError {
+    fn from(e: io::Error) -> ObjError {
+        ObjError::Io(e)
+    }
+}
+
+/// Loads the triangles of the mesh in the OBJ file at the given path, each with the given
+/// material
+///
+/// See `parse_obj` for the parts of the format that are supported.
+///
+/// # Example
+///
+/// ```
+/// let material = ray_tracer::material::Material::default();
+/// let result = ray_tracer::object::mesh::load_obj("does_not_exist.obj", material);
+///
+/// match result {
+///     Err(ray_tracer::object::mesh::ObjError::Io(_)) => {}
+///     _ => panic!("expected a missing file to be an IO error"),
+/// }
+/// ```
+pub fn load_obj(path: &str, material: Material) -> Result<Vec<Triangle>, ObjError> {
+    let source = fs::read_to_string(path)?;
+    parse_obj(&source, material)
+}
+
+/// Parses the triangles of the mesh in the given OBJ source, each with the given material
+///
+/// Vertex positions (`v`) and faces (`f`) are read, and everything else, like texture
+/// coordinates, normals, groups, and materials, is ignored. Faces with more than three vertices
+/// are split into a fan of triangles around their first vertex. Face indices count from 1, or
+/// backwards from the latest vertex if they are negative.
+///
+/// # Example
+///
+/// ```
+/// let cube = "
+/// # A unit cube
+/// v 0 0 0
+/// v 1 0 0
+/// v 1 1 0
+/// v 0 1 0
+/// v 0 0 1
+/// v 1 0 1
+/// v 1 1 1
+/// v 0 1 1
+/// f 1 4 3 2
+/// f 5 6 7 8
+/// f 1 2 6 5
+/// f 2 3 7 6
+/// f 3 4 8 7
+/// f 4 1 5 8
+/// ";
+/// let material = ray_tracer::material::Material::default();
+/// let triangles = ray_tracer::object::mesh::parse_obj(cube, material).unwrap();
+///
+/// assert_eq!(triangles.len(), 12);
+/// assert_eq!(triangles[2].v0, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+/// assert_eq!(triangles[3].v2, ray_tracer::vector3d::Vector3::new(0.0, 1.0, 1.0));
+///
+/// // Texture and normal indices are skipped, and negative indices count backwards
+/// let source = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nf 1/1/1 2//1 -1";
+/// let material = ray_tracer::material::Material::default();
+/// let triangles = ray_tracer::object::mesh::parse_obj(source, material).unwrap();
+/// assert_eq!(triangles[0].v2, ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0));
+///
+/// // Malformed lines are reported with their line number
+/// let source = "v 0 0 0\nv 1 zero 0";
+/// let material = ray_tracer::material::Material::default();
+/// let error = ray_tracer::object::mesh::parse_obj(source, material).unwrap_err();
+/// assert_eq!(error.to_string(), "OBJ line 2: invalid number \"zero\"");
+///
+/// let source = "v 0 0 0\nf 1 2 3";
+/// let material = ray_tracer::material::Material::default();
+/// assert!(ray_tracer::object::mesh::parse_obj(source, material).is_err());
+/// ```
+pub fn parse_obj(source: &str, material: Material) -> Result<Vec<Triangle>, ObjError> {
+    let mut vertices: Vec<Vector3> = vec![];
+    let mut triangles = vec![];
+
+    for (i, line) in source.lines().enumerate() {
+        let error = |message: String| ObjError::Parse {
+            line: i + 1,
+            message,
+        };
+
+        // Strip comments
+        let line = line.split('#').next().unwrap_or("");
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("v") => {
+                let coordinates = words
+                    .take(3)
+                    .map(|word| {
+                        word.parse::<f64>()
+                            .map_err(|_| error(format!("invalid number {:?}", word)))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                if coordinates.len() < 3 {
+                    return Err(error("vertex needs three coordinates".to_string()));
+                }
+                vertices.push(Vector3::new(coordinates[0], coordinates[1], coordinates[2]));
+            }
+            Some("f") => {
+                let corners = words
+                    .map(|word| {
+                        // Only the position index before any slash is needed
+                        let index = word.split('/').next().unwrap_or("");
+                        let index = index
+                            .parse::<i64>()
+                            .map_err(|_| error(format!("invalid index {:?}", word)))?;
+                        let resolved = if index < 0 {
+                            vertices.len() as i64 + index
+                        } else {
+                            index - 1
+                        };
+                        vertices
+                            .get(resolved as usize)
+                            .filter(|_| resolved >= 0)
+                            .ok_or_else(|| error(format!("no vertex with index {}", index)))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                if corners.len() < 3 {
+                    return Err(error("face needs at least three vertices".to_string()));
+                }
+                for pair in corners[1..].windows(2) {
+                    triangles.push(Triangle::new(
+                        corners[0].clone(),
+                        pair[0].clone(),
+                        pair[1].clone(),
+                        material.clone(),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(triangles)
+}
@@ -1,6 +1,117 @@
+use super::aabb::Aabb;
 use super::material::Material;
+use super::math::SHADOW_EPSILON;
 use super::ray::Ray;
+use super::scene::hash::SceneHasher;
 use super::vector3d::Vector3;
+use std::hash::Hasher;
+
+/// A record of where a ray intersected an object
+///
+/// Contains the value of _t_ along the ray where the intersection occurs, the
+/// point of intersection, the surface normal at that point, the texture
+/// coordinates _u_ and _v_ of that point, its barycentric coordinates if a
+/// triangle was hit, how far the surface has faded into the background there,
+/// the material of the object that was hit, and the time of the ray that hit
+/// it.
+///
+/// The normal always faces against the ray, so `front_face` records whether the
+/// ray hit the outside of the surface.
+#[derive(Debug)]
+pub struct HitRecord<'a> {
+    pub t: f64,
+    pub point: Vector3,
+    pub normal: Vector3,
+    pub front_face: bool,
+    pub u: f64,
+    pub v: f64,
+    pub barycentric: Option<[f64; 3]>,
+    pub background_fade: f64,
+    pub material: &'a Material,
+    pub time: f64,
+}
+
+impl<'a> HitRecord<'a> {
+    /// Creates a new hit record for the given ray, turning the outward normal to face the ray
+    ///
+    /// The texture coordinates and background fade start at zero, there are no barycentric
+    /// coordinates, and the time is the ray's.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// let material = ray_tracer::material::Material::default();
+    ///
+    /// let point = ray.at(2.0);
+    /// let outward_normal = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let record = ray_tracer::object::HitRecord::new(&ray, 2.0, point, outward_normal, &material);
+    ///
+    /// assert!(!record.front_face);
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// ```
+    pub fn new(
+        r: &Ray,
+        t: f64,
+        point: Vector3,
+        outward_normal: Vector3,
+        material: &'a Material,
+    ) -> HitRecord<'a> {
+        let front_face = r.dir.dot(&outward_normal) <= 0.0;
+        let normal = if front_face {
+            outward_normal
+        } else {
+            outward_normal.negate()
+        };
+        HitRecord {
+            t,
+            point,
+            normal,
+            front_face,
+            u: 0.0,
+            v: 0.0,
+            barycentric: None,
+            background_fade: 0.0,
+            material,
+            time: r.time,
+        }
+    }
+
+    /// Creates a ray leaving the surface at this hit in the given direction.
+    ///
+    /// The ray starts slightly off the surface, on the side it is travelling towards, at the
+    /// same time as the ray that hit it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction).at_time(0.5);
+    /// let material = ray_tracer::material::Material::default();
+    ///
+    /// let point = ray.at(2.0);
+    /// let outward_normal = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0);
+    /// let record = ray_tracer::object::HitRecord::new(&ray, 2.0, point, outward_normal, &material);
+    ///
+    /// let back = record.secondary_ray(ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// assert!(back.pos.z > -2.0);
+    /// assert_eq!(back.time, 0.5);
+    ///
+    /// let through = record.secondary_ray(ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0));
+    /// assert!(through.pos.z < -2.0);
+    /// ```
+    pub fn secondary_ray(&self, dir: Vector3) -> Ray {
+        let offset = if dir.dot(&self.normal) >= 0.0 {
+            self.normal.mul(SHADOW_EPSILON)
+        } else {
+            self.normal.mul(-SHADOW_EPSILON)
+        };
+        Ray::new(self.point.add(&offset), dir).at_time(self.time)
+    }
+}
 
 pub trait Object {
     /// Calculates if and where the given ray intersects with this object.
@@ -11,11 +122,150 @@ pub trait Object {
     /// and _d_ is the direction vector.
     ///
     /// If the ray does intersect with this object, the return value will be a "Some" value with
-    /// the smallest non negative _t_.
+    /// the smallest _t_ that lies within the ray's `t_min` and `t_max` bounds.
     ///
     /// If the ray does not intersect, then "None" is returned.
     fn ray_intersection(&self, r: &Ray) -> Option<f64>;
 
+    /// Returns the outward facing unit normal of this object's surface at the given point.
+    ///
+    /// The point is assumed to lie on the surface of this object.
+    fn normal_at(&self, point: &Vector3) -> Vector3;
+
+    /// Calculates if and where the given ray intersects with this object, and records the
+    /// details of the intersection needed to shade it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let sphere_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(sphere_position, 1.0, material);
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let record = sphere.hit(&ray).unwrap();
+    /// assert_eq!(record.t, 4.0);
+    /// assert_eq!(record.point, ray.at(record.t));
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// assert!(record.front_face);
+    /// ```
+    fn hit(&self, r: &Ray) -> Option<HitRecord<'_>> {
+        let t = self.ray_intersection(r)?;
+        let point = r.at(t);
+        let normal = self.normal_at(&point);
+        let (u, v) = self.uv_at(&point);
+        let barycentric = self.barycentric_at(&point);
+        let background_fade = self.background_fade(t * r.dir.length());
+        Some(HitRecord {
+            u,
+            v,
+            barycentric,
+            background_fade,
+            ..HitRecord::new(r, t, point, normal, self.material())
+        })
+    }
+
+    /// Returns the texture coordinates _(u, v)_ of the given point on this object's surface,
+    /// each from 0.0 to 1.0.
+    ///
+    /// The point is assumed to lie on the surface of this object. By default every point maps
+    /// to _(0, 0)_.
+    fn uv_at(&self, _point: &Vector3) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+
+    /// Returns the barycentric coordinates of the given point on this object's surface, the
+    /// weights of the vertices of the triangle it lies in, which sum to 1.0.
+    ///
+    /// The point is assumed to lie on the surface of this object. Objects that are not made of
+    /// triangles return "None", which is the default.
+    fn barycentric_at(&self, _point: &Vector3) -> Option<[f64; 3]> {
+        None
+    }
+
+    /// Returns how much of the background shows through this object's surface when it is hit
+    /// the given distance away, from 0.0 to 1.0.
+    ///
+    /// Surfaces that stretch to the horizon can fade into the background with distance, to
+    /// hide the aliasing of tiny, distant details. By default objects never fade.
+    fn background_fade(&self, _distance: f64) -> f64 {
+        0.0
+    }
+
+    /// Returns the ranges of _t_ in which the line of the given ray is inside this object, in
+    /// increasing order.
+    ///
+    /// The ray's bounds are ignored, so that the ranges of several objects can be combined, as
+    /// in constructive solid geometry. Objects without an inside, like planes, have no ranges,
+    /// which is the default.
+    fn intervals(&self, _r: &Ray) -> Vec<(f64, f64)> {
+        vec![]
+    }
+
+    /// Returns the axis-aligned box bounding this object.
+    ///
+    /// Objects that extend infinitely in some direction, like planes, cannot be bounded and
+    /// return "None". Anything that uses bounding boxes to skip objects must always test
+    /// these objects.
+    fn bounding_box(&self) -> Option<Aabb>;
+
+    /// Returns the center and radius of a sphere enclosing this object.
+    ///
+    /// Testing a ray against a sphere is cheaper than against a box, so this can quickly reject
+    /// rays that cannot hit the object. By default the sphere passes through the corners of the
+    /// bounding box, and unbounded objects return "None".
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let cube = ray_tracer::object::cube::BoxObject::new(
+    ///     ray_tracer::vector3d::Vector3::new(-1.0, -1.0, -1.0),
+    ///     ray_tracer::vector3d::Vector3::new(1.0, 1.0, 1.0),
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let (center, radius) = cube.bounding_sphere().unwrap();
+    /// assert_eq!(center, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0));
+    /// assert!((radius - 3.0f64.sqrt()).abs() < 1e-12);
+    ///
+    /// let plane = ray_tracer::object::plane::Plane::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0),
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// assert!(plane.bounding_sphere().is_none());
+    /// ```
+    fn bounding_sphere(&self) -> Option<(Vector3, f64)> {
+        let bounds = self.bounding_box()?;
+        let radius = 0.5 * bounds.min.distance(&bounds.max);
+        Some((bounds.centroid(), radius))
+    }
+
+    /// Feeds every parameter of this object that changes how it looks, including its material,
+    /// to the given hasher, so that `scene::hash::scene_hash` can tell when a scene has changed.
+    ///
+    /// By default this feeds the object's position, material, and bounding box, which changes
+    /// with most of its other parameters. Objects should feed all of their parameters instead,
+    /// where they can.
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write_vector(self.position());
+        hasher.write_material(self.material());
+        match self.bounding_box() {
+            Some(bounds) => {
+                hasher.write_u8(1);
+                hasher.write_vector(&bounds.min);
+                hasher.write_vector(&bounds.max);
+            }
+            None => hasher.write_u8(0),
+        }
+    }
+
     /// Returns this object's Material
     fn material(&self) -> &Material;
 
@@ -23,4 +273,15 @@ pub trait Object {
     fn position(&self) -> &Vector3;
 }
 
+pub mod checker_plane;
+pub mod cone;
+pub mod csg;
+pub mod cube;
+pub mod flip_normals;
+pub mod mesh;
+pub mod moving_sphere;
+pub mod plane;
 pub mod sphere;
+pub mod torus;
+pub mod transform;
+pub mod triangle;
@@ -0,0 +1,203 @@
+//! This module defines a struct representing a Sphere that moves while the shutter is open
+
+use super::sphere;
+use super::{HitRecord, Object};
+use crate::aabb::Aabb;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene::hash::SceneHasher;
+use crate::vector3d::Vector3;
+use std::hash::Hasher;
+
+/// The struct representing a moving sphere
+///
+/// Contains a Vector3 for the sphere's center when the shutter opens, at time
+/// 0.0, a Vector3 for its center when the shutter closes, at time 1.0, and a
+/// f64 radius.
+///
+/// The center moves in a straight line at a constant speed between the two,
+/// so a camera with its shutter open blurs the sphere along that line.
+#[derive(Debug, Default)]
+pub struct MovingSphere {
+    pub start: Vector3,
+    pub end: Vector3,
+    pub radius: f64,
+    pub material: Material,
+}
+
+impl MovingSphere {
+    /// Creates a new sphere moving from the given start center to the given end center
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let start = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let end = ray_tracer::vector3d::Vector3::new(1.0, 0.0, -5.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::moving_sphere::MovingSphere::new(start, end, 0.5, material);
+    ///
+    /// assert_eq!(sphere.end.x, 1.0);
+    /// assert_eq!(sphere.radius, 0.5);
+    /// ```
+    pub fn new(start: Vector3, end: Vector3, radius: f64, material: Material) -> MovingSphere {
+        MovingSphere {
+            start,
+            end,
+            radius,
+            material,
+        }
+    }
+
+    /// Returns the center of this sphere at the given time
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let start = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let end = ray_tracer::vector3d::Vector3::new(1.0, 0.0, -5.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::moving_sphere::MovingSphere::new(
+    ///     start.clone(),
+    ///     end.clone(),
+    ///     0.5,
+    ///     material,
+    /// );
+    ///
+    /// assert_eq!(sphere.center_at(0.0), start);
+    /// assert_eq!(sphere.center_at(1.0), end);
+    /// assert_eq!(sphere.center_at(0.5), ray_tracer::vector3d::Vector3::new(0.5, 0.0, -5.0));
+    /// ```
+    pub fn center_at(&self, time: f64) -> Vector3 {
+        self.start.lerp(&self.end, time)
+    }
+}
+
+impl Object for MovingSphere {
+    /// Calculates if and where the given ray intersects with this sphere, where it is at the
+    /// ray's time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let start = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let end = ray_tracer::vector3d::Vector3::new(2.0, 0.0, -5.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::moving_sphere::MovingSphere::new(start, end, 0.5, material);
+    ///
+    /// // A ray straight ahead only hits the sphere before it has moved away
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// assert_eq!(sphere.ray_intersection(&ray), Some(4.5));
+    /// let ray = ray.at_time(1.0);
+    /// assert!(sphere.ray_intersection(&ray).is_none());
+    ///
+    /// // At the end, the sphere is hit where it has moved to, with the normal facing from there
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction).at_time(1.0);
+    ///
+    /// let record = sphere.hit(&ray).unwrap();
+    /// assert_eq!(record.t, 4.5);
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// assert_eq!(record.time, 1.0);
+    /// ```
+    fn ray_intersection(&self, r: &Ray) -> Option<f64> {
+        sphere::intersect(&self.center_at(r.time), self.radius, r)
+    }
+
+    /// Returns the outward facing unit normal of this sphere at the given point, where it is
+    /// when the shutter opens.
+    ///
+    /// A point alone does not say when it was hit, so this is only correct at time 0.0. Hits
+    /// use the normal where the sphere is at the time of the ray instead, and the objects
+    /// wrapping other objects hit them through `Object::hit` so that they do too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let start = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let end = ray_tracer::vector3d::Vector3::new(2.0, 0.0, -5.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::moving_sphere::MovingSphere::new(start, end, 0.5, material);
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -4.5);
+    /// assert_eq!(sphere.normal_at(&point), ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    ///
+    /// // Wrapped in an identity transform, the sphere is still hit where it is at the ray's time
+    /// let object = ray_tracer::object::transform::TransformedObject::new(Box::new(sphere));
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction).at_time(1.0);
+    ///
+    /// let record = object.hit(&ray).unwrap();
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// ```
+    fn normal_at(&self, point: &Vector3) -> Vector3 {
+        point.sub(&self.start).into_unit()
+    }
+
+    fn hit(&self, r: &Ray) -> Option<HitRecord<'_>> {
+        let t = self.ray_intersection(r)?;
+        let point = r.at(t);
+        let normal = point.sub(&self.center_at(r.time)).into_unit();
+        Some(HitRecord::new(r, t, point, normal, &self.material))
+    }
+
+    /// Returns the range of _t_ in which the line of the given ray is inside this sphere, where
+    /// it is at the ray's time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let start = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let end = ray_tracer::vector3d::Vector3::new(2.0, 0.0, -5.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::moving_sphere::MovingSphere::new(start, end, 0.5, material);
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// assert_eq!(sphere.intervals(&ray), vec![(4.5, 5.5)]);
+    /// assert!(sphere.intervals(&ray.at_time(1.0)).is_empty());
+    /// ```
+    fn intervals(&self, r: &Ray) -> Vec<(f64, f64)> {
+        sphere::interval(&self.center_at(r.time), self.radius, r)
+            .into_iter()
+            .collect()
+    }
+
+    /// Returns the box bounding the sphere along its whole path
+    fn bounding_box(&self) -> Option<Aabb> {
+        let extent = Vector3::new(self.radius, self.radius, self.radius);
+        let start = Aabb::new(self.start.sub(&extent), self.start.add(&extent));
+        let end = Aabb::new(self.end.sub(&extent), self.end.add(&extent));
+        Some(start.union(&end))
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"MovingSphere");
+        hasher.write_vector(&self.start);
+        hasher.write_vector(&self.end);
+        hasher.write_f64(self.radius);
+        hasher.write_material(&self.material);
+    }
+
+    /// Returns this sphere's material
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    /// Returns this sphere's center when the shutter opens
+    fn position(&self) -> &Vector3 {
+        &self.start
+    }
+}
@@ -0,0 +1,127 @@
+//! This module defines a struct representing an infinite Plane
+
+use super::Object;
+use crate::aabb::Aabb;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene::hash::SceneHasher;
+use crate::vector3d::Vector3;
+use std::hash::Hasher;
+
+/// The struct representing a plane
+///
+/// Contains a Vector3 for a point on the plane, and a
+/// unit Vector3 normal to the plane
+#[derive(Debug, Default)]
+pub struct Plane {
+    pub point: Vector3,
+    pub normal: Vector3,
+    pub material: Material,
+}
+
+impl Plane {
+    /// Creates a new plane through the given point with the given normal
+    ///
+    /// The normal does not need to be a unit vector, it is normalized here.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let normal = ray_tracer::vector3d::Vector3::new(0.0, 2.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let plane = ray_tracer::object::plane::Plane::new(point, normal, material);
+    ///
+    /// assert_eq!(plane.point.y, -1.0);
+    /// assert_eq!(plane.normal.y, 1.0);
+    /// ```
+    pub fn new(point: Vector3, normal: Vector3, material: Material) -> Plane {
+        Plane {
+            point,
+            normal: normal.into_unit(),
+            material,
+        }
+    }
+}
+
+impl Object for Plane {
+    /// Calculates if and where the given ray intersects with this plane.
+    ///
+    /// The intersection is at _t = (q - p)·n / (d·n)_, where _q_ is the point on the plane,
+    /// _n_ is the plane's normal, and _p_ and _d_ are the ray's starting point and direction.
+    ///
+    /// If the ray is parallel to the plane, or the intersection is outside of the ray's bounds,
+    /// then "None" is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let normal = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let plane = ray_tracer::object::plane::Plane::new(point, normal, material);
+    ///
+    /// let ray1_position = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let ray1_direction = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let ray1 = ray_tracer::ray::Ray::new(ray1_position, ray1_direction);
+    ///
+    /// assert_eq!(plane.ray_intersection(&ray1), Some(2.0));
+    ///
+    /// let ray2_position = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let ray2_direction = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let ray2 = ray_tracer::ray::Ray::new(ray2_position, ray2_direction);
+    ///
+    /// assert!(plane.ray_intersection(&ray2).is_none());
+    ///
+    /// let ray3_position = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let ray3_direction = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let ray3 = ray_tracer::ray::Ray::new(ray3_position, ray3_direction);
+    ///
+    /// assert!(plane.ray_intersection(&ray3).is_none());
+    /// ```
+    fn ray_intersection(&self, r: &Ray) -> Option<f64> {
+        let dir_dot_normal = r.dir.dot(&self.normal);
+        if dir_dot_normal == 0.0 {
+            return None;
+        }
+
+        let t = self.point.sub(&r.pos).dot(&self.normal) / dir_dot_normal;
+        if r.in_bounds(t) {
+            Some(t)
+        } else {
+            None
+        }
+    }
+
+    /// Returns this plane's normal, which is the same at every point.
+    ///
+    /// A plane has no inside, so a ray hitting the back of it is shaded using the normal turned
+    /// to face the ray, as with any other object.
+    fn normal_at(&self, _point: &Vector3) -> Vector3 {
+        self.normal.clone()
+    }
+
+    /// Planes are infinite, so they cannot be bounded.
+    fn bounding_box(&self) -> Option<Aabb> {
+        None
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"Plane");
+        hasher.write_vector(&self.point);
+        hasher.write_vector(&self.normal);
+        hasher.write_material(&self.material);
+    }
+
+    /// Returns this plane's material
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    /// Returns the point on this plane
+    fn position(&self) -> &Vector3 {
+        &self.point
+    }
+}
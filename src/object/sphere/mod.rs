@@ -1,10 +1,16 @@
 //! This module defines a struct representing a mathematical Sphere
 
 use super::Object;
+use crate::aabb::Aabb;
 use crate::material::Material;
+use crate::math::EPSILON;
 use crate::ray::Ray;
+use crate::scene::hash::SceneHasher;
 use crate::vector3d::Vector3;
 
+use std::f64::consts::PI;
+use std::hash::Hasher;
+
 /// The struct representing a sphere
 ///
 /// Contains a Vector3 for the sphere's position, and a
@@ -23,7 +29,8 @@ impl Sphere {
     ///
     /// ```
     /// let pos = ray_tracer::vector3d::Vector3::new(1.0, 1.0, 1.0);
-    /// let sphere = ray_tracer::sphere::Sphere::new(pos, 3.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(pos, 3.0, material);
     ///
     /// assert_eq!(sphere.pos.x, 1.0);
     /// assert_eq!(sphere.pos.y, 1.0);
@@ -37,6 +44,44 @@ impl Sphere {
             material,
         }
     }
+
+    /// Returns the values of _t_ at which the line of the given ray enters and leaves this
+    /// sphere, in increasing order, or "None" if it misses
+    ///
+    /// The ray's bounds are ignored, so the entry is behind the ray when it starts inside the
+    /// sphere. The normals at both points are found with `normal_at`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let sphere_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(sphere_position, 2.0, material);
+    ///
+    /// // Through the center, the interval spans the distance to the center plus or minus the
+    /// // radius
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// let (entry, exit) = sphere.intersect_interval(&ray).unwrap();
+    /// assert_eq!((entry, exit), (5.0 - 2.0, 5.0 + 2.0));
+    ///
+    /// // The normals face back along the ray where it enters, and along it where it leaves
+    /// let entry_normal = sphere.normal_at(&ray.at(entry));
+    /// let exit_normal = sphere.normal_at(&ray.at(exit));
+    /// assert_eq!(entry_normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// assert_eq!(exit_normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0));
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 3.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert!(sphere.intersect_interval(&ray).is_none());
+    /// ```
+    pub fn intersect_interval(&self, r: &Ray) -> Option<(f64, f64)> {
+        interval(&self.pos, self.radius, r)
+    }
 }
 
 impl Object for Sphere {
@@ -47,16 +92,24 @@ impl Object for Sphere {
     /// The ray is represented mathematically as _p + t*d_, where _p_ is the starting point of the ray,
     /// and _d_ is the direction vector.
     ///
-    /// If the ray does intersect with this sphere, the return value will be a "Some" value with two
-    /// floats, representing two values of _t_ in the above equation. The two floats are equal if
-    /// there is only one intersection point.
+    /// If the ray does intersect with this sphere, the return value will be a "Some" value with
+    /// the smallest value of _t_ in the above equation that is within the ray's bounds. If the
+    /// ray starts inside the sphere, this is where it leaves the sphere. The direction does not
+    /// need to be a unit vector.
     ///
     /// If the ray does not intersect, then "None" is returned.
+    ///
+    /// Rays that pass within a tiny distance of touching the sphere, relative to its radius, are
+    /// treated as tangent, meeting the sphere at a single point.
+    ///
     /// # Example
     ///
     /// ```
+    /// use ray_tracer::object::Object;
+    ///
     /// let sphere_position = ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0);
-    /// let sphere = ray_tracer::sphere::Sphere::new(sphere_position, 1.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(sphere_position, 1.0, material);
     ///
     /// let ray1_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
     /// let ray1_direction = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
@@ -65,7 +118,7 @@ impl Object for Sphere {
     /// let ray1_intersection = sphere.ray_intersection(&ray1);
     ///
     /// assert!(ray1_intersection.is_some());
-    /// assert_eq!(ray1_intersection.unwrap(), (3.0, 1.0));
+    /// assert_eq!(ray1_intersection.unwrap(), 1.0);
     ///
     /// let ray2_position = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
     /// let ray2_direction = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
@@ -74,7 +127,7 @@ impl Object for Sphere {
     /// let ray2_intersection = sphere.ray_intersection(&ray2);
     ///
     /// assert!(ray2_intersection.is_some());
-    /// assert_eq!(ray2_intersection.unwrap(), (2.0, 2.0));
+    /// assert_eq!(ray2_intersection.unwrap(), 2.0);
     ///
     /// let ray3_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
     /// let ray3_direction = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
@@ -83,35 +136,181 @@ impl Object for Sphere {
     /// let ray3_intersection = sphere.ray_intersection(&ray3);
     ///
     /// assert!(ray3_intersection.is_none());
+    ///
+    /// // A ray grazing just inside the tangent ray is treated as tangent
+    /// let ray4_position = ray_tracer::vector3d::Vector3::new(0.0, 1.0 - 1e-12, 0.0);
+    /// let ray4_direction = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let ray4 = ray_tracer::ray::Ray::new(ray4_position, ray4_direction);
+    ///
+    /// assert_eq!(sphere.ray_intersection(&ray4), Some(2.0));
+    ///
+    /// // A ray further inside the tangent ray hits the near side of the sphere
+    /// let ray5_position = ray_tracer::vector3d::Vector3::new(0.0, 0.5, 0.0);
+    /// let ray5_direction = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let ray5 = ray_tracer::ray::Ray::new(ray5_position, ray5_direction);
+    ///
+    /// let ray5_intersection = sphere.ray_intersection(&ray5).unwrap();
+    /// assert!((ray5_intersection - (2.0 - 0.75f64.sqrt())).abs() < 1e-10);
+    ///
+    /// // A ray starting inside the sphere hits it where it leaves
+    /// let ray6_position = ray_tracer::vector3d::Vector3::new(1.5, 0.0, 0.0);
+    /// let ray6_direction = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let ray6 = ray_tracer::ray::Ray::new(ray6_position, ray6_direction);
+    ///
+    /// assert_eq!(sphere.ray_intersection(&ray6), Some(1.5));
+    ///
+    /// // A sphere entirely behind the ray is not hit
+    /// let ray7_position = ray_tracer::vector3d::Vector3::new(4.0, 0.0, 0.0);
+    /// let ray7_direction = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let ray7 = ray_tracer::ray::Ray::new(ray7_position, ray7_direction);
+    ///
+    /// assert!(sphere.ray_intersection(&ray7).is_none());
+    ///
+    /// // _t_ is measured in multiples of the direction's length
+    /// let ray8_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray8_direction = ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0);
+    /// let ray8 = ray_tracer::ray::Ray::new(ray8_position, ray8_direction);
+    ///
+    /// assert_eq!(sphere.ray_intersection(&ray8), Some(0.5));
     /// ```
     fn ray_intersection(&self, r: &Ray) -> Option<f64> {
-        let o_sub_c = r.pos.sub(&self.pos);
-        let len_sq_o_sub_c = o_sub_c.dot(&o_sub_c);
-        let dir_dot_o_sub_c = r.dir.dot(&o_sub_c);
-        let dir_dot_o_sub_c_sq = dir_dot_o_sub_c.powi(2);
-        let radius_sq = self.radius.powi(2);
-        let discrimant = dir_dot_o_sub_c_sq - len_sq_o_sub_c + radius_sq;
-
-        match discrimant {
-            x if x < 0.0 => None,
-            x if x == 0.0 => {
-                if -dir_dot_o_sub_c > 0.0 {
-                    Some(-dir_dot_o_sub_c)
-                } else {
-                    None
-                }
-            }
-            x => {
-                let (t0, t1) = (-dir_dot_o_sub_c + x.sqrt(), -dir_dot_o_sub_c - x.sqrt());
-                if t1 > 0.0 {
-                    Some(t1)
-                } else if t0 > 0.0 {
-                    Some(t0)
-                } else {
-                    None
-                }
-            }
-        }
+        intersect(&self.pos, self.radius, r)
+    }
+
+    /// Returns the outward facing unit normal of this sphere at the given point.
+    ///
+    /// When a ray hits the sphere from inside, `hit` turns this normal to face back into the
+    /// sphere, against the ray, and records that the ray hit the back face.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let sphere_position = ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(sphere_position, 1.0, material);
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(3.0, 0.0, 0.0);
+    /// let normal = sphere.normal_at(&point);
+    /// assert_eq!(normal, ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0));
+    ///
+    /// // A ray starting inside the sphere hits its far side, with the normal facing inwards
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(2.0, 0.5, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let record = sphere.hit(&ray).unwrap();
+    /// assert_eq!(record.t, 0.5);
+    /// assert_eq!(record.point, ray_tracer::vector3d::Vector3::new(2.0, 1.0, 0.0));
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0));
+    /// assert!(!record.front_face);
+    /// ```
+    fn normal_at(&self, point: &Vector3) -> Vector3 {
+        point.sub(&self.pos).into_unit()
+    }
+
+    /// Returns the range of _t_ between where the line of the given ray enters this sphere and
+    /// where it leaves, if it passes through it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let sphere_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(sphere_position, 1.0, material);
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction.clone());
+    ///
+    /// // The range is the same even though the ray starts inside the sphere
+    /// assert_eq!(sphere.intervals(&ray), vec![(-1.0, 1.0)]);
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 2.0, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert!(sphere.intervals(&ray).is_empty());
+    /// ```
+    fn intervals(&self, r: &Ray) -> Vec<(f64, f64)> {
+        self.intersect_interval(r).into_iter().collect()
+    }
+
+    /// Returns the spherical texture coordinates of the given point on this sphere.
+    ///
+    /// _u_ is the angle around the vertical axis, starting from the -x direction and passing
+    /// through +z, +x, and -z. _v_ is the angle from the bottom of the sphere up to the top.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let sphere_position = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(sphere_position, 2.0, material);
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(2.0, 1.0, 0.0);
+    /// assert_eq!(sphere.uv_at(&point), (0.5, 0.5));
+    ///
+    /// let top = ray_tracer::vector3d::Vector3::new(0.0, 3.0, 0.0);
+    /// assert_eq!(sphere.uv_at(&top).1, 1.0);
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, 1.0 + 2f64.sqrt(), 2f64.sqrt());
+    /// let (u, v) = sphere.uv_at(&point);
+    /// assert!((u - 0.25).abs() < 1e-10);
+    /// assert!((v - 0.75).abs() < 1e-10);
+    /// ```
+    fn uv_at(&self, point: &Vector3) -> (f64, f64) {
+        let p = self.normal_at(point);
+        let theta = (-p.y).clamp(-1.0, 1.0).acos();
+        let phi = (-p.z).atan2(p.x) + PI;
+        (phi / (2.0 * PI), theta / PI)
+    }
+
+    /// Returns the box bounding this sphere, which extends the radius around its center.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let sphere_position = ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(sphere_position, 1.0, material);
+    ///
+    /// let aabb = sphere.bounding_box().unwrap();
+    /// assert_eq!(aabb.min, ray_tracer::vector3d::Vector3::new(1.0, -1.0, -1.0));
+    /// assert_eq!(aabb.max, ray_tracer::vector3d::Vector3::new(3.0, 1.0, 1.0));
+    /// ```
+    fn bounding_box(&self) -> Option<Aabb> {
+        let extent = Vector3::new(self.radius, self.radius, self.radius);
+        Some(Aabb::new(self.pos.sub(&extent), self.pos.add(&extent)))
+    }
+
+    /// Returns this sphere itself
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let pos = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(pos.clone(), 0.5, material);
+    ///
+    /// assert_eq!(sphere.bounding_sphere(), Some((pos, 0.5)));
+    /// ```
+    fn bounding_sphere(&self) -> Option<(Vector3, f64)> {
+        Some((self.pos.clone(), self.radius))
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"Sphere");
+        hasher.write_vector(&self.pos);
+        hasher.write_f64(self.radius);
+        hasher.write_material(&self.material);
     }
 
     /// Returns this sphere's material
@@ -124,3 +323,55 @@ impl Object for Sphere {
         &self.pos
     }
 }
+
+/// Returns the range of _t_ in which the line of the given ray is inside the sphere with the
+/// given center and radius, as described for `Sphere::intersect_interval`
+pub(crate) fn interval(center: &Vector3, radius: f64, r: &Ray) -> Option<(f64, f64)> {
+    let o_sub_c = r.pos.sub(center);
+    let a = r.dir.length_squared();
+    let half_b = r.dir.dot(&o_sub_c);
+    let c = o_sub_c.length_squared() - radius.powi(2);
+    let discrimant = half_b.powi(2) - a * c;
+    if discrimant < 0.0 {
+        return None;
+    }
+    let root = discrimant.sqrt();
+    Some(((-half_b - root) / a, (-half_b + root) / a))
+}
+
+/// Returns the smallest _t_ within the given ray's bounds at which it intersects the sphere with
+/// the given center and radius, as described for `Sphere::ray_intersection`
+pub(crate) fn intersect(center: &Vector3, radius: f64, r: &Ray) -> Option<f64> {
+    // Solve |p + t*d - c|^2 = radius^2, a quadratic a*t^2 + 2*half_b*t + c = 0 in t
+    let o_sub_c = r.pos.sub(center);
+    let a = r.dir.length_squared();
+    let half_b = r.dir.dot(&o_sub_c);
+    let c = o_sub_c.length_squared() - radius.powi(2);
+    let discrimant = half_b.powi(2) - a * c;
+    // Discriminants this close to zero, relative to the squared radius, touch at a single point
+    let tangent_band = EPSILON * radius.powi(2) * a;
+
+    match discrimant {
+        x if x < -tangent_band => None,
+        x if x <= tangent_band => {
+            let t = -half_b / a;
+            if r.in_bounds(t) {
+                Some(t)
+            } else {
+                None
+            }
+        }
+        x => {
+            // When the ray starts inside the sphere, the near root is behind it and the far
+            // root is where it leaves the sphere
+            let (near, far) = ((-half_b - x.sqrt()) / a, (-half_b + x.sqrt()) / a);
+            if r.in_bounds(near) {
+                Some(near)
+            } else if r.in_bounds(far) {
+                Some(far)
+            } else {
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! This module defines a struct representing a Torus
+
+use super::sphere;
+use super::Object;
+use crate::aabb::Aabb;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene::hash::SceneHasher;
+use crate::vector3d::Vector3;
+use std::hash::Hasher;
+
+/// The number of times an interval containing a root is halved while searching for it
+const BISECTION_STEPS: u32 = 100;
+
+/// The struct representing a torus
+///
+/// Contains a Vector3 for the torus's center, a unit Vector3 axis through the
+/// hole, the major radius from the center to the middle of the tube, and the
+/// minor radius of the tube itself.
+#[derive(Debug, Default)]
+pub struct Torus {
+    pub center: Vector3,
+    pub axis: Vector3,
+    pub major_radius: f64,
+    pub minor_radius: f64,
+    pub material: Material,
+}
+
+impl Torus {
+    /// Creates a new torus with the given center, axis, and major and minor radii
+    ///
+    /// The axis does not need to be a unit vector, it is normalized here.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let center = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let axis = ray_tracer::vector3d::Vector3::new(0.0, 3.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let torus = ray_tracer::object::torus::Torus::new(center, axis, 2.0, 0.5, material);
+    ///
+    /// assert_eq!(torus.axis.y, 1.0);
+    /// assert_eq!(torus.major_radius, 2.0);
+    /// assert_eq!(torus.minor_radius, 0.5);
+    /// ```
+    pub fn new(
+        center: Vector3,
+        axis: Vector3,
+        major_radius: f64,
+        minor_radius: f64,
+        material: Material,
+    ) -> Torus {
+        Torus {
+            center,
+            axis: axis.into_unit(),
+            major_radius,
+            minor_radius,
+            material,
+        }
+    }
+
+    /// Returns the range of _t_ in which the line of the given ray is inside the sphere
+    /// enclosing this torus, or "None" if it never is
+    fn enclosing_interval(&self, r: &Ray) -> Option<(f64, f64)> {
+        sphere::interval(&self.center, self.major_radius + self.minor_radius, r)
+    }
+
+    /// Returns the _t_ of each crossing of the line of the given ray with this torus between
+    /// `start` and `end`, in increasing order
+    ///
+    /// A point _X_, relative to the center, is on the torus when
+    /// _(|X|² + R² - r²)² = 4R²(|X|² - (X·a)²)_, where _a_ is the axis and _R_ and _r_ are
+    /// the major and minor radii. Substituting the ray gives a quartic in _t_, whose real roots
+    /// are found numerically.
+    fn crossings(&self, r: &Ray, start: f64, end: f64) -> Vec<f64> {
+        let center_to_ray = r.pos.sub(&self.center);
+        let dd = r.dir.length_squared();
+        let od = center_to_ray.dot(&r.dir);
+        let oo = center_to_ray.length_squared();
+        let da = r.dir.dot(&self.axis);
+        let oa = center_to_ray.dot(&self.axis);
+        let major_squared = self.major_radius * self.major_radius;
+        let k = oo + major_squared - self.minor_radius * self.minor_radius;
+
+        // Coefficients of the quartic, from the constant term up
+        let coefficients = [
+            k * k - 4.0 * major_squared * (oo - oa * oa),
+            4.0 * od * k - 8.0 * major_squared * (od - da * oa),
+            4.0 * od * od + 2.0 * dd * k - 4.0 * major_squared * (dd - da * da),
+            4.0 * dd * od,
+            dd * dd,
+        ];
+
+        real_roots(&coefficients, start, end)
+    }
+}
+
+impl Object for Torus {
+    /// Calculates if and where the given ray intersects with this torus.
+    ///
+    /// The ray's crossings of the torus are searched for inside the sphere enclosing it, and
+    /// the smallest within the ray's bounds is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let center = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let axis = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let torus = ray_tracer::object::torus::Torus::new(center, axis, 2.0, 0.5, material);
+    ///
+    /// // A ray down the axis passes through the hole
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 5.0, -5.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let hole_ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert!(torus.ray_intersection(&hole_ray).is_none());
+    ///
+    /// // A ray down through the middle of the tube hits its top
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(2.0, 5.0, -5.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let ring_ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert!((torus.ray_intersection(&ring_ray).unwrap() - 4.5).abs() < 1e-9);
+    ///
+    /// // A ray across the torus hits the near side of the ring
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -2.0);
+    /// let across_ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert!((torus.ray_intersection(&across_ray).unwrap() - 1.25).abs() < 1e-9);
+    /// ```
+    fn ray_intersection(&self, r: &Ray) -> Option<f64> {
+        let (start, end) = self.enclosing_interval(r)?;
+        let (start, end) = (start.max(r.t_min), end.min(r.t_max));
+        if start > end {
+            return None;
+        }
+        self.crossings(r, start, end)
+            .into_iter()
+            .find(|&t| r.in_bounds(t))
+    }
+
+    /// Returns the ranges of _t_ in which the line of the given ray is inside the tube of this
+    /// torus.
+    ///
+    /// The line enters and leaves the tube at alternate crossings, so it can pass through it
+    /// twice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let center = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let axis = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let torus = ray_tracer::object::torus::Torus::new(center, axis, 2.0, 0.5, material);
+    ///
+    /// // A ray across the torus passes through the near and far sides of the ring
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// let intervals = torus.intervals(&ray);
+    /// let expected = [(2.5, 3.5), (6.5, 7.5)];
+    /// assert_eq!(intervals.len(), 2);
+    /// for (&(start, end), &(expected_start, expected_end)) in intervals.iter().zip(&expected) {
+    ///     assert!((start - expected_start).abs() < 1e-9 && (end - expected_end).abs() < 1e-9);
+    /// }
+    ///
+    /// // A ray down the axis passes through the hole
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 5.0, -5.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let hole_ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert!(torus.intervals(&hole_ray).is_empty());
+    /// ```
+    fn intervals(&self, r: &Ray) -> Vec<(f64, f64)> {
+        match self.enclosing_interval(r) {
+            Some((start, end)) => self
+                .crossings(r, start, end)
+                .chunks_exact(2)
+                .map(|pair| (pair[0], pair[1]))
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Returns the outward facing unit normal of this torus at the given point.
+    ///
+    /// This points from the nearest point on the circle running through the middle of the tube
+    /// out to the given point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let center = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0);
+    /// let axis = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let torus = ray_tracer::object::torus::Torus::new(center, axis, 2.0, 0.5, material);
+    ///
+    /// let top = ray_tracer::vector3d::Vector3::new(2.0, 0.5, -5.0);
+    /// let up = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// assert!(torus.normal_at(&top).approx_eq(&up, 1e-10));
+    ///
+    /// // Inside the hole, the normal points back towards the axis
+    /// let inner = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -6.5);
+    /// let towards_axis = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0);
+    /// assert!(torus.normal_at(&inner).approx_eq(&towards_axis, 1e-10));
+    /// ```
+    fn normal_at(&self, point: &Vector3) -> Vector3 {
+        let center_to_point = point.sub(&self.center);
+        let radial = center_to_point.reject_from(&self.axis);
+        let ring_point = match radial.try_into_unit() {
+            Some(direction) => self.center.add(&direction.mul(self.major_radius)),
+            None => self.center.clone(),
+        };
+        point.sub(&ring_point).into_unit()
+    }
+
+    /// Returns the box bounding the ring, widened by the radius of the tube
+    fn bounding_box(&self) -> Option<Aabb> {
+        // The ring reaches furthest along the world axes least aligned with its own axis
+        let reach = |component: f64| {
+            self.major_radius * (1.0 - component * component).max(0.0).sqrt() + self.minor_radius
+        };
+        let extent = Vector3::new(reach(self.axis.x), reach(self.axis.y), reach(self.axis.z));
+        Some(Aabb::new(
+            self.center.sub(&extent),
+            self.center.add(&extent),
+        ))
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"Torus");
+        hasher.write_vector(&self.center);
+        hasher.write_vector(&self.axis);
+        hasher.write_f64(self.major_radius);
+        hasher.write_f64(self.minor_radius);
+        hasher.write_material(&self.material);
+    }
+
+    /// Returns this torus's material
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    /// Returns the center of this torus
+    fn position(&self) -> &Vector3 {
+        &self.center
+    }
+}
+
+/// Returns the value of the polynomial with the given coefficients, from the constant term up,
+/// at _x_
+fn evaluate(coefficients: &[f64], x: f64) -> f64 {
+    coefficients
+        .iter()
+        .rev()
+        .fold(0.0, |value, coefficient| value * x + coefficient)
+}
+
+/// Returns the real roots of the polynomial with the given coefficients, from the constant term
+/// up, between `start` and `end`, in increasing order
+///
+/// Between neighbouring roots of the derivative the polynomial only rises or falls, so it
+/// crosses zero at most once there, and that crossing is found by bisection. The roots of the
+/// derivative are found the same way, down to a linear polynomial.
+fn real_roots(coefficients: &[f64], start: f64, end: f64) -> Vec<f64> {
+    let degree = match coefficients.iter().rposition(|&c| c != 0.0) {
+        Some(degree) => degree,
+        None => return vec![],
+    };
+    if degree == 0 {
+        return vec![];
+    }
+    if degree == 1 {
+        let root = -coefficients[0] / coefficients[1];
+        return if (start..=end).contains(&root) {
+            vec![root]
+        } else {
+            vec![]
+        };
+    }
+
+    let derivative: Vec<f64> = coefficients[1..=degree]
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (i + 1) as f64 * c)
+        .collect();
+    let mut bounds = vec![start];
+    bounds.extend(real_roots(&derivative, start, end));
+    bounds.push(end);
+
+    let mut roots: Vec<f64> = vec![];
+    for pair in bounds.windows(2) {
+        let (mut low, mut high) = (pair[0], pair[1]);
+        let (mut low_value, high_value) =
+            (evaluate(coefficients, low), evaluate(coefficients, high));
+        if low_value == 0.0 {
+            if roots.last() != Some(&low) {
+                roots.push(low);
+            }
+            continue;
+        }
+        if high_value == 0.0 {
+            roots.push(high);
+            continue;
+        }
+        if low_value.signum() == high_value.signum() {
+            continue;
+        }
+        for _ in 0..BISECTION_STEPS {
+            let middle = 0.5 * (low + high);
+            let middle_value = evaluate(coefficients, middle);
+            if middle_value.signum() == low_value.signum() {
+                low = middle;
+                low_value = middle_value;
+            } else {
+                high = middle;
+            }
+        }
+        roots.push(0.5 * (low + high));
+    }
+    roots
+}
@@ -0,0 +1,314 @@
+//! This module defines a wrapper that moves, turns, and resizes another Object
+
+use super::{HitRecord, Object};
+use crate::aabb::Aabb;
+use crate::material::Material;
+use crate::math::matrix::Matrix4;
+use crate::ray::Ray;
+use crate::scene::hash::SceneHasher;
+use crate::vector3d::Vector3;
+use std::hash::Hasher;
+
+/// The struct representing a transformed object
+///
+/// Contains the object being transformed, which is defined in its own local
+/// space, the transform from that local space into the world, and its
+/// inverse from the world back into local space.
+///
+/// Rays are moved into local space to be intersected with the inner object,
+/// and the normals found there are moved back into the world.
+pub struct TransformedObject {
+    pub inner: Box<dyn Object>,
+    to_world: Matrix4,
+    to_local: Matrix4,
+    position: Vector3,
+}
+
+impl TransformedObject {
+    /// Wraps the given object, without transforming it yet
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let sphere = ray_tracer::object::sphere::Sphere::default();
+    /// let object = ray_tracer::object::transform::TransformedObject::new(Box::new(sphere));
+    ///
+    /// assert_eq!(object.position(), &ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0));
+    /// ```
+    pub fn new(inner: Box<dyn Object>) -> TransformedObject {
+        let position = inner.position().clone();
+        TransformedObject {
+            inner,
+            to_world: Matrix4::identity(),
+            to_local: Matrix4::identity(),
+            position,
+        }
+    }
+
+    /// Applies the given transform after the existing one, given its inverse too
+    ///
+    /// The inverse is built up alongside the transform, rather than inverting the whole
+    /// transform, since each step's inverse is exact.
+    fn then(mut self, transform: Matrix4, inverse: Matrix4) -> TransformedObject {
+        self.to_world = transform.mul(&self.to_world);
+        self.to_local = self.to_local.mul(&inverse);
+        self.position = self.to_world.transform_point(self.inner.position());
+        self
+    }
+
+    /// Moves the object by the given offset
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let object = ray_tracer::object::transform::TransformedObject::new(Box::new(sphere))
+    ///     .translate(ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0));
+    ///
+    /// assert_eq!(object.position(), &ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0));
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let record = object.hit(&ray).unwrap();
+    /// assert_eq!(record.t, 4.0);
+    /// assert_eq!(record.point, ray_tracer::vector3d::Vector3::new(0.0, 0.0, -4.0));
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// ```
+    pub fn translate(self, offset: Vector3) -> TransformedObject {
+        let inverse = Matrix4::translation(&offset.negate());
+        self.then(Matrix4::translation(&offset), inverse)
+    }
+
+    /// Rotates the object about the given axis through the world origin by the given angle,
+    /// in radians, following the right hand rule
+    ///
+    /// The axis does not need to be a unit vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// // A quarter turn to the left moves the sphere from in front of the origin to its left
+    /// let object = ray_tracer::object::transform::TransformedObject::new(Box::new(sphere))
+    ///     .rotate(ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0), std::f64::consts::PI / 2.0);
+    ///
+    /// let left = ray_tracer::vector3d::Vector3::new(-5.0, 0.0, 0.0);
+    /// assert!(object.position().approx_eq(&left, 1e-10));
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(-1.0, 0.0, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let record = object.hit(&ray).unwrap();
+    /// assert!((record.t - 4.0).abs() < 1e-10);
+    /// let normal = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// assert!(record.normal.approx_eq(&normal, 1e-10));
+    /// ```
+    pub fn rotate(self, axis: Vector3, angle_radians: f64) -> TransformedObject {
+        let inverse = Matrix4::rotation(&axis, -angle_radians);
+        self.then(Matrix4::rotation(&axis, angle_radians), inverse)
+    }
+
+    /// Scales the object about the world origin by the given factor
+    ///
+    /// # Panics
+    ///
+    /// Panics if the factor is zero, since the object could not be scaled back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let object = ray_tracer::object::transform::TransformedObject::new(Box::new(sphere))
+    ///     .scale(2.0)
+    ///     .translate(ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0));
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let record = object.hit(&ray).unwrap();
+    /// assert_eq!(record.t, 3.0);
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    ///
+    /// let aabb = object.bounding_box().unwrap();
+    /// assert_eq!(aabb.min, ray_tracer::vector3d::Vector3::new(-2.0, -2.0, -7.0));
+    /// assert_eq!(aabb.max, ray_tracer::vector3d::Vector3::new(2.0, 2.0, -3.0));
+    /// ```
+    pub fn scale(self, factor: f64) -> TransformedObject {
+        assert!(factor != 0.0, "cannot scale an object by zero");
+        let inverse = Matrix4::scale(1.0 / factor, 1.0 / factor, 1.0 / factor);
+        self.then(Matrix4::scale(factor, factor, factor), inverse)
+    }
+
+    /// Moves the given ray from the world into the inner object's local space
+    ///
+    /// The direction is not normalized, so that _t_ is the same along both rays.
+    fn ray_to_local(&self, r: &Ray) -> Ray {
+        Ray::new_bounded(
+            self.to_local.transform_point(&r.pos),
+            self.to_local.transform_vector(&r.dir),
+            r.t_min,
+            r.t_max,
+        )
+        .at_time(r.time)
+    }
+}
+
+impl Object for TransformedObject {
+    fn ray_intersection(&self, r: &Ray) -> Option<f64> {
+        self.inner.ray_intersection(&self.ray_to_local(r))
+    }
+
+    /// Returns the outward facing unit normal at the given point, found by moving the point
+    /// into local space and the inner object's normal back out.
+    ///
+    /// Normals are moved with the transpose of the inverse transform, so that they stay
+    /// perpendicular to the surface.
+    fn normal_at(&self, point: &Vector3) -> Vector3 {
+        let local_normal = self.inner.normal_at(&self.to_local.transform_point(point));
+        self.to_local
+            .transpose()
+            .transform_vector(&local_normal)
+            .into_unit()
+    }
+
+    /// Moves the given ray into local space to be hit by the inner object, and the point and
+    /// normal of its hit record back into the world.
+    ///
+    /// Everything else is kept from the inner object's hit, so that objects which work out more
+    /// of their hits themselves, like constructive solid geometry, look the same once moved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let red = ray_tracer::material::Material {
+    ///     color: ray_tracer::color::Color::new(1.0, 0.0, 0.0),
+    ///     ..Default::default()
+    /// };
+    /// let blue = ray_tracer::material::Material {
+    ///     color: ray_tracer::color::Color::new(0.0, 0.0, 1.0),
+    ///     ..Default::default()
+    /// };
+    /// let left = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     1.0,
+    ///     red,
+    /// );
+    /// let right = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0),
+    ///     1.0,
+    ///     blue,
+    /// );
+    /// let csg = ray_tracer::object::csg::Csg::new(
+    ///     Box::new(left),
+    ///     Box::new(right),
+    ///     ray_tracer::object::csg::CsgOperation::Difference,
+    /// );
+    /// let object = ray_tracer::object::transform::TransformedObject::new(Box::new(csg))
+    ///     .translate(ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0));
+    ///
+    /// // The bottom of the bite keeps the right sphere's material
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let record = object.hit(&ray).unwrap();
+    /// assert_eq!(record.t, 5.0);
+    /// assert_eq!(record.point, ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0));
+    /// assert_eq!(record.material.color, ray_tracer::color::Color::new(0.0, 0.0, 1.0));
+    /// assert_eq!(record.normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// ```
+    fn hit(&self, r: &Ray) -> Option<HitRecord<'_>> {
+        let record = self.inner.hit(&self.ray_to_local(r))?;
+        // The normal still faces back along the ray, since moving both keeps their dot product
+        let normal = self
+            .to_local
+            .transpose()
+            .transform_vector(&record.normal)
+            .into_unit();
+        Some(HitRecord {
+            point: r.at(record.t),
+            normal,
+            ..record
+        })
+    }
+
+    fn uv_at(&self, point: &Vector3) -> (f64, f64) {
+        self.inner.uv_at(&self.to_local.transform_point(point))
+    }
+
+    fn barycentric_at(&self, point: &Vector3) -> Option<[f64; 3]> {
+        self.inner
+            .barycentric_at(&self.to_local.transform_point(point))
+    }
+
+    fn intervals(&self, r: &Ray) -> Vec<(f64, f64)> {
+        self.inner.intervals(&self.ray_to_local(r))
+    }
+
+    fn background_fade(&self, distance: f64) -> f64 {
+        self.inner.background_fade(distance)
+    }
+
+    /// Returns the box bounding the transformed corners of the inner object's bounding box
+    fn bounding_box(&self) -> Option<Aabb> {
+        let local = self.inner.bounding_box()?;
+        let corners: Vec<Vector3> = (0..8)
+            .map(|i| {
+                let pick = |bit: usize, min: f64, max: f64| if i & bit == 0 { min } else { max };
+                let corner = Vector3::new(
+                    pick(1, local.min.x, local.max.x),
+                    pick(2, local.min.y, local.max.y),
+                    pick(4, local.min.z, local.max.z),
+                );
+                self.to_world.transform_point(&corner)
+            })
+            .collect();
+        Some(Aabb::from_points(&corners))
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"TransformedObject");
+        for row in &self.to_world.rows {
+            for &value in row {
+                hasher.write_f64(value);
+            }
+        }
+        self.inner.hash_into(hasher);
+    }
+
+    /// Returns the inner object's material
+    fn material(&self) -> &Material {
+        self.inner.material()
+    }
+
+    /// Returns the inner object's position, moved into the world
+    fn position(&self) -> &Vector3 {
+        &self.position
+    }
+}
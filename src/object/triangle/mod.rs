@@ -0,0 +1,256 @@
+//! This module defines a struct representing a Triangle
+
+use super::Object;
+use crate::aabb::Aabb;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene::hash::SceneHasher;
+use crate::vector3d::Vector3;
+use std::hash::Hasher;
+
+/// Returns whether a point with the given barycentric coordinates lies within the given
+/// distance of an edge of its triangle, measured as a fraction of the triangle's height above
+/// that edge
+///
+/// # Example
+///
+/// ```
+/// use ray_tracer::object::triangle::near_edge;
+///
+/// // Points close to any edge are flagged
+/// assert!(near_edge(&[0.01, 0.49, 0.5], 0.05));
+/// assert!(near_edge(&[0.6, 0.0, 0.4], 0.05));
+///
+/// // The center and other interior points are not
+/// assert!(!near_edge(&[1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0], 0.05));
+/// assert!(!near_edge(&[0.8, 0.1, 0.1], 0.05));
+/// ```
+pub fn near_edge(barycentric: &[f64; 3], width: f64) -> bool {
+    barycentric.iter().any(|&weight| weight < width)
+}
+
+/// The struct representing a triangle
+///
+/// Contains a Vector3 for each of the triangle's three vertices.
+/// The front face of the triangle is the side from which the vertices
+/// appear in counter-clockwise order.
+#[derive(Debug, Default)]
+pub struct Triangle {
+    pub v0: Vector3,
+    pub v1: Vector3,
+    pub v2: Vector3,
+    pub material: Material,
+}
+
+impl Triangle {
+    /// Creates a new triangle with the given vertices
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let v0 = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let v1 = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let v2 = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let triangle = ray_tracer::object::triangle::Triangle::new(v0, v1, v2, material);
+    ///
+    /// assert_eq!(triangle.v1.x, 1.0);
+    /// assert_eq!(triangle.v2.y, 1.0);
+    /// ```
+    pub fn new(v0: Vector3, v1: Vector3, v2: Vector3, material: Material) -> Triangle {
+        Triangle {
+            v0,
+            v1,
+            v2,
+            material,
+        }
+    }
+}
+
+impl Object for Triangle {
+    /// Calculates if and where the given ray intersects with this triangle.
+    ///
+    /// This uses the Möller–Trumbore algorithm, which solves for _t_ and the barycentric
+    /// coordinates _u_ and _v_ of the intersection at the same time. The ray misses if the
+    /// barycentric coordinates are outside of the triangle, if it is parallel to the triangle,
+    /// or if _t_ is outside of the ray's bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let v0 = ray_tracer::vector3d::Vector3::new(-1.0, -1.0, -2.0);
+    /// let v1 = ray_tracer::vector3d::Vector3::new(1.0, -1.0, -2.0);
+    /// let v2 = ray_tracer::vector3d::Vector3::new(0.0, 2.0, -2.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let triangle = ray_tracer::object::triangle::Triangle::new(v0, v1, v2, material);
+    ///
+    /// // Through the centroid
+    /// let ray1_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray1_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray1 = ray_tracer::ray::Ray::new(ray1_position, ray1_direction);
+    ///
+    /// let t = triangle.ray_intersection(&ray1).unwrap();
+    /// assert!((t - 2.0).abs() < 1e-10);
+    ///
+    /// // Just below the bottom edge
+    /// let ray2_position = ray_tracer::vector3d::Vector3::new(0.0, -1.01, 0.0);
+    /// let ray2_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray2 = ray_tracer::ray::Ray::new(ray2_position, ray2_direction);
+    ///
+    /// assert!(triangle.ray_intersection(&ray2).is_none());
+    /// ```
+    fn ray_intersection(&self, r: &Ray) -> Option<f64> {
+        let edge1 = self.v1.sub(&self.v0);
+        let edge2 = self.v2.sub(&self.v0);
+        let p = r.dir.cross(&edge2);
+        let determinant = edge1.dot(&p);
+        if determinant == 0.0 {
+            // The ray is parallel to the triangle
+            return None;
+        }
+        let inverse_determinant = 1.0 / determinant;
+
+        let s = r.pos.sub(&self.v0);
+        let u = s.dot(&p) * inverse_determinant;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let q = s.cross(&edge1);
+        let v = r.dir.dot(&q) * inverse_determinant;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let t = edge2.dot(&q) * inverse_determinant;
+        if r.in_bounds(t) {
+            Some(t)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the geometric normal of this triangle, which is the same at every point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let v0 = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let v1 = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let v2 = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let triangle = ray_tracer::object::triangle::Triangle::new(v0, v1, v2, material);
+    ///
+    /// let normal = triangle.normal_at(&ray_tracer::vector3d::Vector3::new(0.25, 0.25, 0.0));
+    /// assert_eq!(normal, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// ```
+    fn normal_at(&self, _point: &Vector3) -> Vector3 {
+        self.v1
+            .sub(&self.v0)
+            .cross(&self.v2.sub(&self.v0))
+            .into_unit()
+    }
+
+    /// Returns the weights of this triangle's vertices that sum to the given point, or "None"
+    /// if the triangle has no area.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let v0 = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let v1 = ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0);
+    /// let v2 = ray_tracer::vector3d::Vector3::new(0.0, 2.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let triangle = ray_tracer::object::triangle::Triangle::new(v0, v1, v2, material);
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(0.5, 1.0, 0.0);
+    /// let [w0, w1, w2] = triangle.barycentric_at(&point).unwrap();
+    /// assert!((w0 - 0.25).abs() < 1e-12);
+    /// assert!((w1 - 0.25).abs() < 1e-12);
+    /// assert!((w2 - 0.5).abs() < 1e-12);
+    ///
+    /// // Hits on the triangle record them
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.5, 1.0, 1.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert_eq!(triangle.hit(&ray).unwrap().barycentric, triangle.barycentric_at(&point));
+    /// ```
+    fn barycentric_at(&self, point: &Vector3) -> Option<[f64; 3]> {
+        let edge1 = self.v1.sub(&self.v0);
+        let edge2 = self.v2.sub(&self.v0);
+        let to_point = point.sub(&self.v0);
+        let normal = edge1.cross(&edge2);
+        let area_squared = normal.length_squared();
+        if area_squared == 0.0 {
+            return None;
+        }
+        let w1 = to_point.cross(&edge2).dot(&normal) / area_squared;
+        let w2 = edge1.cross(&to_point).dot(&normal) / area_squared;
+        Some([1.0 - w1 - w2, w1, w2])
+    }
+
+    /// Returns the box bounding this triangle's vertices.
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(Aabb::from_points(&[
+            self.v0.clone(),
+            self.v1.clone(),
+            self.v2.clone(),
+        ]))
+    }
+
+    /// Returns a sphere around the centroid of the vertices, reaching the furthest vertex
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let v0 = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -2.0);
+    /// let v1 = ray_tracer::vector3d::Vector3::new(4.0, 0.0, -2.0);
+    /// let v2 = ray_tracer::vector3d::Vector3::new(0.0, 1.0, -3.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let triangle = ray_tracer::object::triangle::Triangle::new(
+    ///     v0.clone(),
+    ///     v1.clone(),
+    ///     v2.clone(),
+    ///     material,
+    /// );
+    ///
+    /// let (center, radius) = triangle.bounding_sphere().unwrap();
+    /// for vertex in [v0, v1, v2].iter() {
+    ///     assert!(center.distance(vertex) <= radius + 1e-12);
+    /// }
+    /// ```
+    fn bounding_sphere(&self) -> Option<(Vector3, f64)> {
+        let center = self.v0.add(&self.v1).add(&self.v2).mul(1.0 / 3.0);
+        let radius = [&self.v0, &self.v1, &self.v2]
+            .iter()
+            .map(|vertex| center.distance(vertex))
+            .fold(0.0, f64::max);
+        Some((center, radius))
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"Triangle");
+        hasher.write_vector(&self.v0);
+        hasher.write_vector(&self.v1);
+        hasher.write_vector(&self.v2);
+        hasher.write_material(&self.material);
+    }
+
+    /// Returns this triangle's material
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    /// Returns this triangle's first vertex
+    fn position(&self) -> &Vector3 {
+        &self.v0
+    }
+}
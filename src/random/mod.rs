@@ -0,0 +1,111 @@
+//! This module defines the seedable random number generator used while rendering
+
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+/// Returns the next output of the SplitMix64 generator with the given state, advancing it
+///
+/// This spreads similar seeds, like neighbouring pixel coordinates, into unrelated ones.
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// The struct representing a random number generator for rendering
+///
+/// Contains a small, fast generator, which is not suitable for cryptography.
+///
+/// The same seed always produces the same numbers, so renders that take all of
+/// their randomness from generators seeded the same way are repeatable.
+#[derive(Clone, Debug)]
+pub struct RenderRng {
+    inner: XorShiftRng,
+}
+
+impl RenderRng {
+    /// Creates a new generator from the given seed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::Rng;
+    ///
+    /// let mut first = ray_tracer::random::RenderRng::seeded(42);
+    /// let mut second = ray_tracer::random::RenderRng::seeded(42);
+    /// let mut other = ray_tracer::random::RenderRng::seeded(43);
+    ///
+    /// let numbers: Vec<f64> = (0..10).map(|_| first.gen()).collect();
+    /// assert_eq!(numbers, (0..10).map(|_| second.gen()).collect::<Vec<f64>>());
+    /// assert_ne!(numbers, (0..10).map(|_| other.gen()).collect::<Vec<f64>>());
+    /// ```
+    pub fn seeded(seed: u64) -> RenderRng {
+        let mut state = seed;
+        let (a, b) = (split_mix(&mut state), split_mix(&mut state));
+        let words = [a as u32, (a >> 32) as u32, b as u32, (b >> 32) as u32];
+        // The generator cannot start from all zeros
+        let words = if words.iter().all(|&word| word == 0) {
+            [1, 0, 0, 0]
+        } else {
+            words
+        };
+        RenderRng {
+            inner: XorShiftRng::from_seed(words),
+        }
+    }
+
+    /// Creates a new generator for the pixel at the given column and row of a render with the
+    /// given seed
+    ///
+    /// Each pixel has its own generator, so a pixel's randomness does not depend on which other
+    /// pixels were rendered before it, or whether they were rendered at all.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::Rng;
+    ///
+    /// let mut pixel = ray_tracer::random::RenderRng::for_pixel(7, 3, 4);
+    /// let mut same_pixel = ray_tracer::random::RenderRng::for_pixel(7, 3, 4);
+    /// let mut next_pixel = ray_tracer::random::RenderRng::for_pixel(7, 4, 4);
+    ///
+    /// let number: u64 = pixel.gen();
+    /// assert_eq!(number, same_pixel.gen::<u64>());
+    /// assert_ne!(number, next_pixel.gen::<u64>());
+    /// ```
+    pub fn for_pixel(seed: u64, x: u32, y: u32) -> RenderRng {
+        let mut state = seed;
+        let pixel = (u64::from(y) << 32) | u64::from(x);
+        RenderRng::seeded(split_mix(&mut state) ^ pixel)
+    }
+
+    /// Creates a new generator with a seed that is different every time
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::Rng;
+    ///
+    /// let mut rng = ray_tracer::random::RenderRng::from_entropy();
+    /// let number: f64 = rng.gen();
+    /// assert!((0.0..1.0).contains(&number));
+    /// ```
+    pub fn from_entropy() -> RenderRng {
+        RenderRng::seeded(rand::thread_rng().gen())
+    }
+}
+
+impl Rng for RenderRng {
+    fn next_u32(&mut self) -> u32 {
+        self.inner.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.inner.fill_bytes(dest)
+    }
+}
@@ -1,15 +1,35 @@
 //! This module defines a struct representing a mathematical Ray
 
+use super::math::EPSILON;
 use super::vector3d::Vector3;
 
+/// The smallest _t_ that a Ray created with `Ray::new` will report intersections at
+///
+/// This keeps rays that start on a surface from intersecting that same surface.
+pub const DEFAULT_T_MIN: f64 = EPSILON;
+
 /// The struct representing a Ray
 ///
 /// Contains a Vector3 for the Ray's starting position, and a
-/// Vector3 for the direction of the Ray
-#[derive(Debug, Default)]
+/// Vector3 for the direction of the Ray.
+///
+/// Only intersections with a _t_ strictly between `t_min` and `t_max` count as hits.
+///
+/// Each Ray also carries the moment it was cast at, from 0.0 when the camera's shutter opens to
+/// 1.0 when it closes, so that moving objects can be intersected where they are at that moment.
+#[derive(Debug)]
 pub struct Ray {
     pub pos: Vector3,
     pub dir: Vector3,
+    pub t_min: f64,
+    pub t_max: f64,
+    pub time: f64,
+}
+
+impl Default for Ray {
+    fn default() -> Self {
+        Ray::new(Vector3::default(), Vector3::default())
+    }
 }
 
 impl Ray {
@@ -29,8 +49,98 @@ impl Ray {
     /// assert_eq!(ray.dir.x, 0.0);
     /// assert_eq!(ray.dir.y, 1.0);
     /// assert_eq!(ray.dir.z, 2.0);
+    ///
+    /// assert_eq!(ray.t_min, ray_tracer::ray::DEFAULT_T_MIN);
+    /// assert_eq!(ray.t_max, f64::INFINITY);
+    /// assert_eq!(ray.time, 0.0);
     /// ```
     pub fn new(pos: Vector3, dir: Vector3) -> Ray {
-        Ray { pos, dir }
+        Ray::new_bounded(pos, dir, DEFAULT_T_MIN, f64::INFINITY)
+    }
+
+    /// Creates a new Ray which only reports intersections with _t_ between `t_min` and `t_max`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    ///
+    /// let sphere_position = ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0);
+    /// let material = ray_tracer::material::Material::default();
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(sphere_position, 1.0, material);
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    ///
+    /// // The near side of the sphere is at t = 1, which is before t_min, so the far side is hit
+    /// let ray = ray_tracer::ray::Ray::new_bounded(ray_position, ray_direction, 2.0, 10.0);
+    /// assert_eq!(sphere.ray_intersection(&ray), Some(3.0));
+    ///
+    /// // Neither side is within the bounds
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new_bounded(ray_position, ray_direction, 3.5, 10.0);
+    /// assert!(sphere.ray_intersection(&ray).is_none());
+    /// ```
+    pub fn new_bounded(pos: Vector3, dir: Vector3, t_min: f64, t_max: f64) -> Ray {
+        Ray {
+            pos,
+            dir,
+            t_min,
+            t_max,
+            time: 0.0,
+        }
+    }
+
+    /// Returns this Ray, cast at the given time instead
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction).at_time(0.25);
+    ///
+    /// assert_eq!(ray.time, 0.25);
+    /// assert_eq!(ray.dir.x, 1.0);
+    /// ```
+    pub fn at_time(mut self, time: f64) -> Ray {
+        self.time = time;
+        self
+    }
+
+    /// Returns the point along this Ray at the given value of _t_
+    ///
+    /// This is the point _p + t*d_, where _p_ is the Ray's starting position and _d_ is its
+    /// direction.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// assert_eq!(ray.at(2.0), ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0));
+    /// ```
+    pub fn at(&self, t: f64) -> Vector3 {
+        self.pos.add(&self.dir.mul(t))
+    }
+
+    /// Returns whether the given value of _t_ lies strictly between `t_min` and `t_max`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new_bounded(ray_position, ray_direction, 1.0, 2.0);
+    ///
+    /// assert!(!ray.in_bounds(0.5));
+    /// assert!(ray.in_bounds(1.5));
+    /// assert!(!ray.in_bounds(2.5));
+    /// ```
+    pub fn in_bounds(&self, t: f64) -> bool {
+        t > self.t_min && t < self.t_max
     }
 }
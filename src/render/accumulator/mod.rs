@@ -0,0 +1,141 @@
+//! This module defines an Accumulator, which averages successive frames of an unchanging view
+
+use crate::color::{Color, ToneMapping};
+use crate::framebuffer::Framebuffer;
+
+/// The struct representing an accumulation buffer
+///
+/// Contains the sum of the linear colors of each pixel over every frame added
+/// since the last reset, and the number of those frames.
+///
+/// While the view stays still, each frame can sample different points within
+/// the pixels, and the average of the frames refines the image. Once anything
+/// moves, the old frames no longer match, so the buffer must be reset.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Accumulator {
+    sum: Framebuffer,
+    frames: u32,
+}
+
+impl Accumulator {
+    /// Creates a new, empty accumulation buffer
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let accumulator = ray_tracer::render::accumulator::Accumulator::new();
+    ///
+    /// assert_eq!(accumulator.frames(), 0);
+    /// ```
+    pub fn new() -> Accumulator {
+        Accumulator::default()
+    }
+
+    /// Returns the number of frames added since the last reset
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut accumulator = ray_tracer::render::accumulator::Accumulator::new();
+    /// accumulator.add(&ray_tracer::framebuffer::Framebuffer::new(2, 2));
+    ///
+    /// assert_eq!(accumulator.frames(), 1);
+    /// ```
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// Clears every frame added so far
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut frame = ray_tracer::framebuffer::Framebuffer::new(1, 1);
+    /// frame.set(0, 0, ray_tracer::color::Color::new(1.0, 1.0, 1.0));
+    ///
+    /// let mut accumulator = ray_tracer::render::accumulator::Accumulator::new();
+    /// accumulator.add(&frame);
+    /// accumulator.reset();
+    ///
+    /// assert_eq!(accumulator.frames(), 0);
+    /// let tone_mapping = ray_tracer::color::ToneMapping::None;
+    /// assert_eq!(accumulator.average(tone_mapping).pixels().len(), 0);
+    /// ```
+    pub fn reset(&mut self) {
+        *self = Accumulator::default();
+    }
+
+    /// Adds the given frame of linear colors to the buffer
+    ///
+    /// If the frame is a different size from the frames added before it, the buffer is reset
+    /// first, and starts again from this frame.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut accumulator = ray_tracer::render::accumulator::Accumulator::new();
+    /// accumulator.add(&ray_tracer::framebuffer::Framebuffer::new(2, 2));
+    /// accumulator.add(&ray_tracer::framebuffer::Framebuffer::new(2, 2));
+    /// assert_eq!(accumulator.frames(), 2);
+    ///
+    /// accumulator.add(&ray_tracer::framebuffer::Framebuffer::new(4, 4));
+    /// assert_eq!(accumulator.frames(), 1);
+    /// ```
+    pub fn add(&mut self, frame: &Framebuffer) {
+        if self.frames == 0 || self.sum.width != frame.width || self.sum.height != frame.height {
+            self.sum = frame.clone();
+            self.frames = 1;
+            return;
+        }
+        for y in 0..frame.height {
+            for x in 0..frame.width {
+                let sum = self.sum.get(x, y).unwrap().add(frame.get(x, y).unwrap());
+                self.sum.set(x, y, sum);
+            }
+        }
+        self.frames += 1;
+    }
+
+    /// Returns the average of the frames added since the last reset, after the given tone
+    /// mapping
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let tone_mapping = ray_tracer::color::ToneMapping::None;
+    /// let mut frame = ray_tracer::framebuffer::Framebuffer::new(2, 1);
+    /// frame.set(0, 0, ray_tracer::color::Color::new(0.1, 0.2, 0.3));
+    /// frame.set(1, 0, ray_tracer::color::Color::new(0.7, 0.0, 1.0));
+    ///
+    /// // Averaging copies of the same frame gives back that frame
+    /// let mut accumulator = ray_tracer::render::accumulator::Accumulator::new();
+    /// for _ in 0..5 {
+    ///     accumulator.add(&frame);
+    /// }
+    /// let average = accumulator.average(tone_mapping);
+    /// for (averaged, original) in average.pixels().iter().zip(frame.pixels()) {
+    ///     assert!(averaged.approx_eq(original, 1e-12));
+    /// }
+    ///
+    /// // Different frames are blended evenly
+    /// let mut other = ray_tracer::framebuffer::Framebuffer::new(2, 1);
+    /// other.set(0, 0, ray_tracer::color::Color::new(0.7, 0.8, 0.9));
+    /// let mut accumulator = ray_tracer::render::accumulator::Accumulator::new();
+    /// accumulator.add(&frame);
+    /// accumulator.add(&other);
+    /// let average = accumulator.average(tone_mapping);
+    /// let expected = ray_tracer::color::Color::new(0.4, 0.5, 0.6);
+    /// assert!(average.get(0, 0).unwrap().approx_eq(&expected, 1e-12));
+    /// ```
+    pub fn average(&self, tone_mapping: ToneMapping) -> Framebuffer {
+        let mut average = Framebuffer::new(self.sum.width, self.sum.height);
+        let scale = 1.0 / self.frames.max(1) as f64;
+        for y in 0..self.sum.height {
+            for x in 0..self.sum.width {
+                let color: Color = self.sum.get(x, y).unwrap().mul(scale);
+                average.set(x, y, tone_mapping.apply(color));
+            }
+        }
+        average
+    }
+}
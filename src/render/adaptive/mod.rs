@@ -0,0 +1,169 @@
+//! This module defines an AdaptiveSampler, which spends more samples on pixels that need them
+
+use rand::Rng;
+
+use crate::color::Color;
+
+/// The struct representing an adaptive sampler
+///
+/// Contains the number of samples every pixel gets, the most samples any one
+/// pixel may get, and the color variance above which a pixel gets more
+/// samples.
+///
+/// Flat regions of the image agree after the first few samples and stop
+/// there, while pixels on edges, where the samples disagree, keep sampling
+/// until they settle or reach the limit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdaptiveSampler {
+    pub initial_samples: u32,
+    pub max_samples: u32,
+    pub variance_threshold: f64,
+}
+
+impl Default for AdaptiveSampler {
+    fn default() -> Self {
+        AdaptiveSampler {
+            initial_samples: 4,
+            max_samples: 32,
+            variance_threshold: 0.001,
+        }
+    }
+}
+
+impl AdaptiveSampler {
+    /// Creates a new adaptive sampler with the given initial and maximum samples per pixel and
+    /// variance threshold
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let sampler = ray_tracer::render::adaptive::AdaptiveSampler::new(4, 64, 0.01);
+    ///
+    /// assert_eq!(sampler.max_samples, 64);
+    /// assert_eq!(sampler.variance_threshold, 0.01);
+    /// ```
+    pub fn new(initial_samples: u32, max_samples: u32, variance_threshold: f64) -> AdaptiveSampler {
+        AdaptiveSampler {
+            initial_samples,
+            max_samples,
+            variance_threshold,
+        }
+    }
+
+    /// Returns whether a pixel with the given samples so far should be sampled again
+    ///
+    /// Every pixel gets the initial samples, and no pixel gets more than the maximum. In between,
+    /// a pixel is sampled again while the variance of its samples is above the threshold.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let sampler = ray_tracer::render::adaptive::AdaptiveSampler::new(4, 16, 0.001);
+    /// let grey = ray_tracer::color::Color::new(0.5, 0.5, 0.5);
+    /// let black = ray_tracer::color::Color::new(0.0, 0.0, 0.0);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    ///
+    /// assert!(sampler.needs_more_samples(&[grey.clone(), grey.clone()]));
+    ///
+    /// // A uniform pixel stops after the initial samples
+    /// let uniform = vec![grey; 4];
+    /// assert!(!sampler.needs_more_samples(&uniform));
+    ///
+    /// // A pixel on a high contrast edge asks for more
+    /// let edge = vec![black.clone(), white.clone(), black.clone(), white.clone()];
+    /// assert!(sampler.needs_more_samples(&edge));
+    ///
+    /// // ...until it reaches the limit
+    /// let limit: Vec<_> = edge.iter().cycle().take(16).cloned().collect();
+    /// assert!(!sampler.needs_more_samples(&limit));
+    /// ```
+    pub fn needs_more_samples(&self, samples: &[Color]) -> bool {
+        let count = samples.len() as u32;
+        if count >= self.max_samples.max(1) {
+            false
+        } else if count < self.initial_samples.max(1) {
+            true
+        } else {
+            variance(samples) > self.variance_threshold
+        }
+    }
+
+    /// Returns the average of samples of a pixel, taking them from the given function until no
+    /// more are needed
+    ///
+    /// The function is given a random offset into the pixel, each from 0.0 to 1.0 across it, and
+    /// the given generator, for any other randomness the sample needs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let sampler = ray_tracer::render::adaptive::AdaptiveSampler::new(4, 16, 0.001);
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(0);
+    ///
+    /// let mut calls = 0;
+    /// let color = sampler.sample_pixel(&mut rng, |_, _, _| {
+    ///     calls += 1;
+    ///     ray_tracer::color::Color::new(0.5, 0.5, 0.5)
+    /// });
+    /// assert_eq!(calls, 4);
+    /// assert_eq!(color, ray_tracer::color::Color::new(0.5, 0.5, 0.5));
+    ///
+    /// // Half of this pixel is covered by a white edge
+    /// let mut calls = 0;
+    /// sampler.sample_pixel(&mut rng, |x, _, _| {
+    ///     calls += 1;
+    ///     let brightness = if calls % 2 == 0 { 1.0 } else { 0.0 };
+    ///     assert!((0.0..1.0).contains(&x));
+    ///     ray_tracer::color::Color::new(brightness, brightness, brightness)
+    /// });
+    /// assert_eq!(calls, 16);
+    /// ```
+    pub fn sample_pixel<R: Rng>(
+        &self,
+        rng: &mut R,
+        mut sample: impl FnMut(f64, f64, &mut R) -> Color,
+    ) -> Color {
+        let mut samples = vec![];
+        while self.needs_more_samples(&samples) {
+            let (x, y) = (rng.gen::<f64>(), rng.gen::<f64>());
+            samples.push(sample(x, y, rng));
+        }
+        mean(&samples)
+    }
+}
+
+/// Returns the average of the given colors
+fn mean(samples: &[Color]) -> Color {
+    let sum = samples
+        .iter()
+        .fold(Color::default(), |sum, sample| sum.add(sample));
+    sum.mul(1.0 / samples.len() as f64)
+}
+
+/// Returns the variance of the given colors, averaged over the red, green, and blue channels
+///
+/// # Example
+///
+/// ```
+/// let grey = ray_tracer::color::Color::new(0.5, 0.5, 0.5);
+/// assert_eq!(ray_tracer::render::adaptive::variance(&[grey.clone(), grey]), 0.0);
+///
+/// let black = ray_tracer::color::Color::new(0.0, 0.0, 0.0);
+/// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+/// assert_eq!(ray_tracer::render::adaptive::variance(&[black, white]), 0.25);
+/// ```
+pub fn variance(samples: &[Color]) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let average = mean(samples);
+    let total: f64 = samples
+        .iter()
+        .map(|sample| {
+            (sample.r - average.r).powi(2)
+                + (sample.g - average.g).powi(2)
+                + (sample.b - average.b).powi(2)
+        })
+        .sum();
+    total / (3.0 * samples.len() as f64)
+}
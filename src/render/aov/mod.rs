@@ -0,0 +1,253 @@
+//! This module renders auxiliary buffers, or AOVs, which show what each pixel sees instead of
+//! its color
+//!
+//! These are useful for debugging scenes, and for compositing, where later steps need to know
+//! the depth or orientation of the surface under each pixel.
+
+use super::{center_ray, render_pixel, MAX_DEPTH};
+use crate::camera::frustum::Frustum;
+use crate::camera::Camera;
+use crate::color::{Color, ToneMapping};
+use crate::framebuffer::Framebuffer;
+use crate::object::triangle::near_edge;
+use crate::random::RenderRng;
+use crate::scene::Scene;
+
+/// How close to an edge of a triangle, as a fraction of its height above that edge, a point
+/// must be to be drawn as part of the edge in the wireframe buffer
+pub const WIREFRAME_EDGE_WIDTH: f64 = 0.05;
+
+/// The color the edges of triangles are drawn in the wireframe buffer
+pub const WIREFRAME_EDGE_COLOR: Color = Color {
+    r: 1.0,
+    g: 1.0,
+    b: 1.0,
+};
+
+/// The buffers a render can fill
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RenderBuffer {
+    /// The shaded color of each pixel, as rendered by `render::render`
+    #[default]
+    Color,
+    /// The distance along each pixel's ray to the nearest hit, in every component, or infinity
+    /// where nothing is hit
+    Depth,
+    /// The world space unit normal of the nearest hit, facing the camera, with each component
+    /// mapped from -1.0 to 1.0 onto 0.0 to 1.0, or black where nothing is hit
+    Normal,
+    /// The shaded color of each pixel, with the edges of any triangles drawn over it, for
+    /// debugging meshes
+    Wireframe,
+    /// The number of objects tested for an intersection with each pixel's ray, from blue for
+    /// none to red for the most tested by any pixel, for profiling the bounding volume hierarchy
+    Heatmap,
+}
+
+/// Renders the given buffer of the given scene seen by the given camera into a framebuffer of
+/// the given size, sampling each pixel at its center.
+///
+/// The tone mapping is only applied to the color and wireframe buffers. Any randomness comes
+/// from generators seeded with the given seed, as in `render::render`.
+///
+/// # Example
+///
+/// ```
+/// let mut scene = ray_tracer::scene::Scene::default();
+/// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+///     1.0,
+///     ray_tracer::material::Material::default(),
+/// )));
+/// let camera = ray_tracer::camera::Camera::default();
+/// let tone_mapping = ray_tracer::color::ToneMapping::None;
+///
+/// let depth = ray_tracer::render::aov::render_buffer(
+///     &scene,
+///     &camera,
+///     5,
+///     5,
+///     ray_tracer::render::aov::RenderBuffer::Depth,
+///     tone_mapping,
+///     0,
+/// );
+///
+/// // The nearest depth is the front of the sphere, in the center of the image
+/// let nearest = depth.pixels().iter().map(|color| color.r).fold(f64::INFINITY, f64::min);
+/// assert_eq!(nearest, 4.0);
+/// assert_eq!(depth.get(2, 2).unwrap().r, 4.0);
+/// assert_eq!(depth.get(0, 0).unwrap().r, f64::INFINITY);
+///
+/// let normals = ray_tracer::render::aov::render_buffer(
+///     &scene,
+///     &camera,
+///     5,
+///     5,
+///     ray_tracer::render::aov::RenderBuffer::Normal,
+///     tone_mapping,
+///     0,
+/// );
+///
+/// // The front of the sphere faces +z
+/// assert_eq!(normals.get(2, 2), Some(&ray_tracer::color::Color::new(0.5, 0.5, 1.0)));
+/// assert_eq!(normals.get(0, 0), Some(&ray_tracer::color::Color::new(0.0, 0.0, 0.0)));
+///
+/// // A triangle covering the image shows its edges over its shaded color
+/// let mut scene = ray_tracer::scene::Scene::default();
+/// scene.add(Box::new(ray_tracer::object::triangle::Triangle::new(
+///     ray_tracer::vector3d::Vector3::new(-1.0, -1.0, -1.0),
+///     ray_tracer::vector3d::Vector3::new(1.0, -1.0, -1.0),
+///     ray_tracer::vector3d::Vector3::new(-1.0, 1.0, -1.0),
+///     ray_tracer::material::Material::default(),
+/// )));
+/// let wireframe = ray_tracer::render::aov::render_buffer(
+///     &scene,
+///     &camera,
+///     11,
+///     11,
+///     ray_tracer::render::aov::RenderBuffer::Wireframe,
+///     tone_mapping,
+///     0,
+/// );
+/// let edge = ray_tracer::render::aov::WIREFRAME_EDGE_COLOR;
+/// assert_eq!(wireframe.get(0, 5), Some(&edge));
+/// assert_eq!(wireframe.get(5, 5), Some(&edge));
+/// assert_ne!(wireframe.get(3, 5), Some(&edge));
+/// ```
+pub fn render_buffer(
+    scene: &Scene,
+    camera: &Camera,
+    width: u32,
+    height: u32,
+    buffer: RenderBuffer,
+    tone_mapping: ToneMapping,
+    seed: u64,
+) -> Framebuffer {
+    let visible = Frustum::new(camera).cull(&scene.objects);
+    let mut framebuffer = Framebuffer::new(width, height);
+    // The heatmap's colors depend on the most tests of any pixel, so every pixel is counted first
+    let counts = if buffer == RenderBuffer::Heatmap {
+        intersection_counts(scene, camera, width, height, seed)
+    } else {
+        vec![]
+    };
+    let most = counts.iter().copied().max().unwrap_or(0);
+    for dy in 0..height {
+        for dx in 0..width {
+            let mut rng = RenderRng::for_pixel(seed, dx, dy);
+            let ray = center_ray(camera, dx, dy, width, height, &mut rng);
+            let color = match buffer {
+                RenderBuffer::Color => {
+                    tone_mapping.apply(render_pixel(scene, &visible, &ray, &mut rng))
+                }
+                RenderBuffer::Depth => {
+                    let depth = scene
+                        .closest_hit(&ray)
+                        .map_or(f64::INFINITY, |hit| hit.t * ray.dir.length());
+                    Color::new(depth, depth, depth)
+                }
+                RenderBuffer::Wireframe => {
+                    let hit = scene.closest_hit_among(&ray, &visible);
+                    match hit.as_ref().and_then(|hit| hit.barycentric) {
+                        Some(ref weights) if near_edge(weights, WIREFRAME_EDGE_WIDTH) => {
+                            WIREFRAME_EDGE_COLOR
+                        }
+                        _ => tone_mapping.apply(scene.shade_hit(&ray, hit, MAX_DEPTH, &mut rng)),
+                    }
+                }
+                RenderBuffer::Heatmap => heatmap_color(counts[(dy * width + dx) as usize], most),
+                RenderBuffer::Normal => match scene.closest_hit(&ray) {
+                    Some(hit) => {
+                        let n = hit.normal;
+                        Color::new(0.5 * (n.x + 1.0), 0.5 * (n.y + 1.0), 0.5 * (n.z + 1.0))
+                    }
+                    None => Color::default(),
+                },
+            };
+            framebuffer.set(dx, dy, color);
+        }
+    }
+    framebuffer
+}
+
+/// Returns the number of objects tested for an intersection with the ray through the center of
+/// each pixel of an image of the given size, row by row from the top left
+///
+/// Like the primary rays of a render, the rays are only tested against the objects in the
+/// camera's frustum, and those in the scene's bounding volume hierarchy, and any randomness in
+/// them comes from generators seeded with the given seed.
+///
+/// # Example
+///
+/// ```
+/// let camera = ray_tracer::camera::Camera::default();
+/// let sphere = |x: f64| {
+///     Box::new(ray_tracer::object::sphere::Sphere::new(
+///         ray_tracer::vector3d::Vector3::new(x, 0.0, -5.0),
+///         1.0,
+///         ray_tracer::material::Material::default(),
+///     ))
+/// };
+///
+/// // With one object, each pixel tests it once
+/// let mut scene = ray_tracer::scene::Scene::default();
+/// scene.add(sphere(0.0));
+/// let counts = ray_tracer::render::aov::intersection_counts(&scene, &camera, 4, 3, 0);
+/// assert_eq!(counts, vec![1; 12]);
+///
+/// // With more objects and no bounding volume hierarchy, each pixel tests every one
+/// for i in 1..5 {
+///     scene.add(sphere(i as f64 - 2.5));
+/// }
+/// let counts = ray_tracer::render::aov::intersection_counts(&scene, &camera, 4, 3, 0);
+/// assert_eq!(counts, vec![5; 12]);
+///
+/// // With one, pixels only test the objects along their rays
+/// scene.build_bvh();
+/// let counts = ray_tracer::render::aov::intersection_counts(&scene, &camera, 4, 3, 0);
+/// assert!(counts.iter().all(|&count| count < 5));
+/// ```
+pub fn intersection_counts(
+    scene: &Scene,
+    camera: &Camera,
+    width: u32,
+    height: u32,
+    seed: u64,
+) -> Vec<u32> {
+    let visible = Frustum::new(camera).cull(&scene.objects);
+    let mut counts = Vec::with_capacity((width * height) as usize);
+    for dy in 0..height {
+        for dx in 0..width {
+            let mut rng = RenderRng::for_pixel(seed, dx, dy);
+            let ray = center_ray(camera, dx, dy, width, height, &mut rng);
+            let mut tests = 0;
+            scene.closest_hit_counted(&ray, &visible, &mut tests);
+            counts.push(tests);
+        }
+    }
+    counts
+}
+
+/// Returns the color of the given number of intersection tests in a heatmap, blending from blue
+/// for none to red for the given most tests
+///
+/// # Example
+///
+/// ```
+/// use ray_tracer::render::aov::heatmap_color;
+///
+/// assert_eq!(heatmap_color(0, 10), ray_tracer::color::Color::new(0.0, 0.0, 1.0));
+/// assert_eq!(heatmap_color(10, 10), ray_tracer::color::Color::new(1.0, 0.0, 0.0));
+/// assert_eq!(heatmap_color(5, 10), ray_tracer::color::Color::new(0.5, 0.0, 0.5));
+///
+/// // With no tests anywhere, everything is blue
+/// assert_eq!(heatmap_color(0, 0), ray_tracer::color::Color::new(0.0, 0.0, 1.0));
+/// ```
+pub fn heatmap_color(count: u32, most: u32) -> Color {
+    let heat = if most == 0 {
+        0.0
+    } else {
+        f64::from(count.min(most)) / f64::from(most)
+    };
+    Color::new(heat, 0.0, 1.0 - heat)
+}
@@ -0,0 +1,569 @@
+//! This module renders a Scene seen by a Camera into pixels, and saves them to image files
+
+use sdl2::image::SaveSurface;
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::surface::Surface;
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use self::adaptive::AdaptiveSampler;
+use super::camera::frustum::Frustum;
+use super::camera::Camera;
+use super::color::{Color, ToneMapping};
+use super::error::RayTracerError;
+use super::framebuffer::Framebuffer;
+use super::object::Object;
+use super::random::RenderRng;
+use super::ray::Ray;
+use super::scene::Scene;
+
+/// The number of times a ray may be reflected or refracted
+pub const MAX_DEPTH: u32 = 5;
+
+/// The gamma used to encode the colors of rendered pixels
+pub const GAMMA: f64 = 2.0;
+
+/// The struct representing a rectangular region of an image
+///
+/// Contains the column and row of the region's top left pixel, and its width
+/// and height in pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    /// Creates a new region with the given top left pixel and size
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let region = ray_tracer::render::Rect::new(2, 3, 4, 5);
+    ///
+    /// assert_eq!(region.x, 2);
+    /// assert_eq!(region.height, 5);
+    /// ```
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// Returns the size of an image of the given width and height, scaled by the given factor
+///
+/// Each side is rounded to the nearest pixel, and is at least one pixel long, so that a small
+/// scale can be used to render quickly at a reduced resolution.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(ray_tracer::render::scaled_resolution(800, 600, 0.5), (400, 300));
+/// assert_eq!(ray_tracer::render::scaled_resolution(800, 600, 1.0), (800, 600));
+/// assert_eq!(ray_tracer::render::scaled_resolution(3, 2, 0.1), (1, 1));
+///
+/// let scene = ray_tracer::scene::Scene::default();
+/// let camera = ray_tracer::camera::Camera::default();
+/// let tone_mapping = ray_tracer::color::ToneMapping::None;
+/// let (width, height) = ray_tracer::render::scaled_resolution(64, 48, 0.5);
+/// let framebuffer = ray_tracer::render::render(&scene, &camera, width, height, tone_mapping, 0);
+/// assert_eq!(framebuffer.width, 32);
+/// assert_eq!(framebuffer.height, 24);
+/// ```
+pub fn scaled_resolution(width: u32, height: u32, scale: f64) -> (u32, u32) {
+    let scale_side = |side: u32| ((side as f64 * scale).round() as u32).max(1);
+    (scale_side(width), scale_side(height))
+}
+
+/// Renders the given scene seen by the given camera into a framebuffer of the given size.
+///
+/// The framebuffer holds the linear color of each pixel, after the given tone mapping, ready to
+/// be gamma corrected. Each pixel is sampled at its center.
+///
+/// Any randomness, such as from depth of field, motion blur, or rough reflections, comes from
+/// generators seeded with the given seed, one for each pixel, so rendering the same scene with
+/// the same seed always gives the same image.
+///
+/// # Example
+///
+/// ```
+/// let mut scene = ray_tracer::scene::Scene::new(ray_tracer::color::Color::new(0.0, 0.0, 1.0));
+/// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+///     1.0,
+///     ray_tracer::material::Material {
+///         emission: ray_tracer::color::Color::new(1.0, 0.0, 0.0),
+///         ..Default::default()
+///     },
+/// )));
+/// let camera = ray_tracer::camera::Camera::default();
+///
+/// let tone_mapping = ray_tracer::color::ToneMapping::None;
+/// let framebuffer = ray_tracer::render::render(&scene, &camera, 3, 3, tone_mapping, 0);
+/// assert_eq!(framebuffer.pixels().len(), 9);
+/// assert_eq!(framebuffer.get(1, 1), Some(&ray_tracer::color::Color::new(1.0, 0.0, 0.0)));
+/// assert_eq!(framebuffer.get(0, 0), Some(&ray_tracer::color::Color::new(0.0, 0.0, 1.0)));
+///
+/// let tone_mapping = ray_tracer::color::ToneMapping::Reinhard;
+/// let framebuffer = ray_tracer::render::render(&scene, &camera, 3, 3, tone_mapping, 0);
+/// assert_eq!(framebuffer.get(1, 1), Some(&ray_tracer::color::Color::new(0.5, 0.0, 0.0)));
+///
+/// // Blur from an open aperture is the same for the same seed
+/// let camera = ray_tracer::camera::Camera {
+///     aperture: 0.5,
+///     ..Default::default()
+/// };
+/// let blurred = ray_tracer::render::render(&scene, &camera, 3, 3, tone_mapping, 7);
+/// assert_eq!(ray_tracer::render::render(&scene, &camera, 3, 3, tone_mapping, 7), blurred);
+/// ```
+pub fn render(
+    scene: &Scene,
+    camera: &Camera,
+    width: u32,
+    height: u32,
+    tone_mapping: ToneMapping,
+    seed: u64,
+) -> Framebuffer {
+    render_with_progress(scene, camera, width, height, tone_mapping, seed, |_| {})
+}
+
+/// Renders the given scene like `render`, calling the given function after each row of pixels
+/// is finished with the fraction of the image done so far.
+///
+/// The fraction increases with each call, and is 1.0 once the last row is finished, so this
+/// can be used to show a progress bar during long renders.
+///
+/// # Example
+///
+/// ```
+/// let scene = ray_tracer::scene::Scene::default();
+/// let camera = ray_tracer::camera::Camera::default();
+/// let tone_mapping = ray_tracer::color::ToneMapping::None;
+///
+/// let mut fractions = vec![];
+/// ray_tracer::render::render_with_progress(&scene, &camera, 4, 10, tone_mapping, 0, |done| {
+///     fractions.push(done)
+/// });
+///
+/// assert_eq!(fractions.len(), 10);
+/// assert!(fractions.windows(2).all(|pair| pair[0] < pair[1]));
+/// assert_eq!(fractions[0], 0.1);
+/// assert_eq!(fractions[9], 1.0);
+/// ```
+pub fn render_with_progress(
+    scene: &Scene,
+    camera: &Camera,
+    width: u32,
+    height: u32,
+    tone_mapping: ToneMapping,
+    seed: u64,
+    mut progress: impl FnMut(f32),
+) -> Framebuffer {
+    let visible = Frustum::new(camera).cull(&scene.objects);
+    let mut framebuffer = Framebuffer::new(width, height);
+    for dy in 0..height {
+        for dx in 0..width {
+            let mut rng = RenderRng::for_pixel(seed, dx, dy);
+            let ray = center_ray(camera, dx, dy, width, height, &mut rng);
+            let color = render_pixel(scene, &visible, &ray, &mut rng);
+            framebuffer.set(dx, dy, tone_mapping.apply(color));
+        }
+        progress((dy + 1) as f32 / height as f32);
+    }
+    framebuffer
+}
+
+/// Renders the given scene like `render`, but antialiased, taking more samples at random points
+/// within pixels where the given sampler finds that they disagree, such as along edges.
+///
+/// All of the randomness comes from generators seeded with the given seed, one for each pixel,
+/// so rendering the same scene with the same seed always gives the same image.
+///
+/// # Example
+///
+/// ```
+/// let mut scene = ray_tracer::scene::Scene::new(ray_tracer::color::Color::new(0.0, 0.0, 0.0));
+/// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+///     2.0,
+///     ray_tracer::material::Material {
+///         emission: ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+///         ..Default::default()
+///     },
+/// )));
+/// let camera = ray_tracer::camera::Camera::default();
+/// let tone_mapping = ray_tracer::color::ToneMapping::None;
+/// // Enough initial samples that the edge pixel is all but certain to see both sides
+/// let sampler = ray_tracer::render::adaptive::AdaptiveSampler::new(32, 64, 0.001);
+///
+/// let framebuffer =
+///     ray_tracer::render::render_adaptive(&scene, &camera, 5, 5, tone_mapping, &sampler, 0);
+///
+/// // Pixels entirely on or off the sphere are exact, and pixels on its edge are blended
+/// assert_eq!(framebuffer.get(2, 2), Some(&ray_tracer::color::Color::new(1.0, 1.0, 1.0)));
+/// assert_eq!(framebuffer.get(0, 0), Some(&ray_tracer::color::Color::new(0.0, 0.0, 0.0)));
+/// let edge = framebuffer.get(2, 1).unwrap();
+/// assert!(edge.r > 0.0 && edge.r < 1.0);
+/// ```
+pub fn render_adaptive(
+    scene: &Scene,
+    camera: &Camera,
+    width: u32,
+    height: u32,
+    tone_mapping: ToneMapping,
+    sampler: &AdaptiveSampler,
+    seed: u64,
+) -> Framebuffer {
+    let visible = Frustum::new(camera).cull(&scene.objects);
+    let mut framebuffer = Framebuffer::new(width, height);
+    for dy in 0..height {
+        for dx in 0..width {
+            let mut rng = RenderRng::for_pixel(seed, dx, dy);
+            let color = sampler.sample_pixel(&mut rng, |offset_x, offset_y, rng| {
+                let u = (dx as f64 + offset_x) / width as f64;
+                let v = 1.0 - (dy as f64 + offset_y) / height as f64;
+                render_pixel(scene, &visible, &camera.generate_ray(u, v, rng), rng)
+            });
+            framebuffer.set(dx, dy, tone_mapping.apply(color));
+        }
+    }
+    framebuffer
+}
+
+/// Renders only the given region of the image that `render` would produce at the full size,
+/// into a framebuffer the size of the region.
+///
+/// Rays are generated, and each pixel's generator is seeded, as they would be for the full
+/// image, so renders of regions that tile the image, for example on different machines, can be
+/// stitched back together into exactly the full render with the same seed.
+///
+/// # Panics
+///
+/// Panics if the region does not lie within the full image.
+///
+/// # Example
+///
+/// ```
+/// let mut scene = ray_tracer::scene::Scene::new(ray_tracer::color::Color::new(0.0, 0.0, 1.0));
+/// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+///     ray_tracer::vector3d::Vector3::new(0.5, 0.2, -5.0),
+///     2.0,
+///     ray_tracer::material::Material::default(),
+/// )));
+/// scene.add_light(Box::new(ray_tracer::light::PointLight::new(
+///     ray_tracer::vector3d::Vector3::new(-2.0, 3.0, 0.0),
+///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+///     20.0,
+/// )));
+/// // Even the blur of an open aperture matches
+/// let camera = ray_tracer::camera::Camera {
+///     aperture: 0.2,
+///     ..Default::default()
+/// };
+/// let tone_mapping = ray_tracer::color::ToneMapping::None;
+/// let (width, height) = (9, 7);
+///
+/// let full = ray_tracer::render::render(&scene, &camera, width, height, tone_mapping, 3);
+///
+/// // Render the four quadrants separately, and stitch them back together
+/// let quadrants = [
+///     ray_tracer::render::Rect::new(0, 0, 4, 3),
+///     ray_tracer::render::Rect::new(4, 0, 5, 3),
+///     ray_tracer::render::Rect::new(0, 3, 4, 4),
+///     ray_tracer::render::Rect::new(4, 3, 5, 4),
+/// ];
+/// let mut stitched = ray_tracer::framebuffer::Framebuffer::new(width, height);
+/// for region in quadrants {
+///     let tile = ray_tracer::render::render_region(
+///         &scene,
+///         &camera,
+///         width,
+///         height,
+///         region,
+///         tone_mapping,
+///         3,
+///     );
+///     assert_eq!((tile.width, tile.height), (region.width, region.height));
+///     for y in 0..region.height {
+///         for x in 0..region.width {
+///             let color = tile.get(x, y).unwrap().clone();
+///             stitched.set(region.x + x, region.y + y, color);
+///         }
+///     }
+/// }
+///
+/// assert_eq!(stitched, full);
+/// ```
+pub fn render_region(
+    scene: &Scene,
+    camera: &Camera,
+    full_width: u32,
+    full_height: u32,
+    region: Rect,
+    tone_mapping: ToneMapping,
+    seed: u64,
+) -> Framebuffer {
+    let fits = |start: u32, length: u32, full: u32| {
+        start.checked_add(length).is_some_and(|end| end <= full)
+    };
+    assert!(
+        fits(region.x, region.width, full_width) && fits(region.y, region.height, full_height),
+        "region {:?} is outside of the {}x{} image",
+        region,
+        full_width,
+        full_height
+    );
+    let visible = Frustum::new(camera).cull(&scene.objects);
+    let mut framebuffer = Framebuffer::new(region.width, region.height);
+    for dy in 0..region.height {
+        for dx in 0..region.width {
+            let (x, y) = (region.x + dx, region.y + dy);
+            let mut rng = RenderRng::for_pixel(seed, x, y);
+            let ray = center_ray(camera, x, y, full_width, full_height, &mut rng);
+            let color = render_pixel(scene, &visible, &ray, &mut rng);
+            framebuffer.set(dx, dy, tone_mapping.apply(color));
+        }
+    }
+    framebuffer
+}
+
+/// Returns the linear color seen along the given primary ray, taking any random choices from
+/// the given generator
+///
+/// The primary ray is only tested against the scene's objects in the given list, which should
+/// hold those inside the camera's frustum, and those in its bounding volume hierarchy.
+fn render_pixel(scene: &Scene, visible: &[&dyn Object], ray: &Ray, rng: &mut RenderRng) -> Color {
+    let hit = scene.closest_hit_among(ray, visible);
+    scene.shade_hit(ray, hit, MAX_DEPTH, rng)
+}
+
+/// Returns the camera's ray through the center of the pixel at the given column and row of an
+/// image of the given size, taking the random points on the lens and times from the given
+/// generator
+fn center_ray(
+    camera: &Camera,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    rng: &mut RenderRng,
+) -> Ray {
+    // Rows run down the image, while v runs up it
+    let u = (x as f64 + 0.5) / width as f64;
+    let v = 1.0 - (y as f64 + 0.5) / height as f64;
+    camera.generate_ray(u, v, rng)
+}
+
+/// The struct holding the settings of an image rendered to a file
+///
+/// Contains the width and height of the image in pixels, the tone mapping
+/// applied to its colors, and the seed its random numbers are generated from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RenderSettings {
+    pub width: u32,
+    pub height: u32,
+    pub tone_mapping: ToneMapping,
+    pub seed: u64,
+}
+
+impl Default for RenderSettings {
+    fn default() -> Self {
+        RenderSettings {
+            width: 800,
+            height: 600,
+            tone_mapping: ToneMapping::None,
+            seed: 0,
+        }
+    }
+}
+
+impl RenderSettings {
+    /// Creates new settings for an image of the given width and height, without tone mapping,
+    /// rendered with a seed of 0
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let settings = ray_tracer::render::RenderSettings::new(640, 480);
+    ///
+    /// assert_eq!((settings.width, settings.height), (640, 480));
+    /// assert_eq!(settings.tone_mapping, ray_tracer::color::ToneMapping::None);
+    /// assert_eq!(settings.seed, 0);
+    ///
+    /// let default = ray_tracer::render::RenderSettings::default();
+    /// assert_eq!((default.width, default.height), (800, 600));
+    /// let expected = ray_tracer::render::RenderSettings {
+    ///     width: 640,
+    ///     height: 480,
+    ///     ..default
+    /// };
+    /// assert_eq!(settings, expected);
+    /// ```
+    pub fn new(width: u32, height: u32) -> RenderSettings {
+        RenderSettings {
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+}
+
+/// Renders the given scene seen by the given camera into an image with the given settings, and
+/// saves it to the file at the given path, without opening a window.
+///
+/// The seed in the settings and the given function are used as in `render_with_progress`, which
+/// calls the function with the fraction of the image rendered so far after each row.
+///
+/// Paths ending in `.ppm` are saved as binary PPM images, and all other paths are saved as
+/// PNG images.
+///
+/// # Example
+///
+/// ```
+/// let mut scene = ray_tracer::scene::Scene::default();
+/// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -2.0),
+///     1.0,
+///     ray_tracer::material::Material::default(),
+/// )));
+/// scene.add_light(Box::new(ray_tracer::light::PointLight::new(
+///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+///     4.0,
+/// )));
+/// let camera = ray_tracer::camera::Camera::default();
+///
+/// let path = std::env::temp_dir().join("ray_tracer_render_to_file.ppm");
+/// let path = path.to_str().unwrap();
+/// let settings = ray_tracer::render::RenderSettings {
+///     tone_mapping: ray_tracer::color::ToneMapping::Reinhard,
+///     ..ray_tracer::render::RenderSettings::new(4, 4)
+/// };
+/// let mut calls = 0;
+/// ray_tracer::render::render_to_file(&scene, &camera, &settings, path, |_| calls += 1).unwrap();
+/// assert_eq!(calls, 4);
+///
+/// let bytes = std::fs::read(path).unwrap();
+/// let header = b"P6\n4 4\n255\n";
+/// assert!(bytes.starts_with(header));
+/// assert_eq!(bytes.len(), header.len() + 4 * 4 * 3);
+/// assert!(bytes[header.len()..].iter().any(|&byte| byte > 0));
+/// std::fs::remove_file(path).unwrap();
+///
+/// // A file in a missing directory cannot be written
+/// let path = std::env::temp_dir().join("ray_tracer_missing").join("render.ppm");
+/// let result = ray_tracer::render::render_to_file(
+///     &scene,
+///     &camera,
+///     &settings,
+///     path.to_str().unwrap(),
+///     |_| {},
+/// );
+/// assert!(matches!(result, Err(ray_tracer::error::RayTracerError::Io(_))));
+/// ```
+pub fn render_to_file(
+    scene: &Scene,
+    camera: &Camera,
+    settings: &RenderSettings,
+    path: &str,
+    progress: impl FnMut(f32),
+) -> Result<(), RayTracerError> {
+    let framebuffer = render_with_progress(
+        scene,
+        camera,
+        settings.width,
+        settings.height,
+        settings.tone_mapping,
+        settings.seed,
+        progress,
+    );
+    save_framebuffer(&framebuffer, path)
+}
+
+/// Saves the given framebuffer, gamma corrected, to the file at the given path
+///
+/// Paths ending in `.ppm` are saved as binary PPM images, and all other paths are saved as
+/// PNG images.
+///
+/// # Example
+///
+/// ```
+/// let mut framebuffer = ray_tracer::framebuffer::Framebuffer::new(2, 1);
+/// framebuffer.set(1, 0, ray_tracer::color::Color::new(1.0, 1.0, 1.0));
+///
+/// let path = std::env::temp_dir().join("ray_tracer_save_framebuffer.ppm");
+/// let path = path.to_str().unwrap();
+/// ray_tracer::render::save_framebuffer(&framebuffer, path).unwrap();
+///
+/// let bytes = std::fs::read(path).unwrap();
+/// assert_eq!(bytes, b"P6\n2 1\n255\n\x00\x00\x00\xff\xff\xff");
+/// std::fs::remove_file(path).unwrap();
+/// ```
+pub fn save_framebuffer(framebuffer: &Framebuffer, path: &str) -> Result<(), RayTracerError> {
+    let (width, height) = (framebuffer.width, framebuffer.height);
+    let bytes = framebuffer.to_rgb_bytes(GAMMA);
+
+    let is_ppm = Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("ppm"));
+    if is_ppm {
+        Ok(write_ppm(path, width, height, &bytes)?)
+    } else {
+        write_png(path, width, height, &bytes).map_err(RayTracerError::Image)
+    }
+}
+
+/// Returns the name of the PNG file to save a screenshot taken at the given time since the Unix
+/// epoch to
+///
+/// The time is written in milliseconds, padded with zeros, so that screenshots taken at
+/// different times get different names, which sort in the order they were taken.
+///
+/// # Example
+///
+/// ```
+/// let first = ray_tracer::render::screenshot_filename(std::time::Duration::from_millis(999));
+/// let second = ray_tracer::render::screenshot_filename(std::time::Duration::from_secs(1));
+///
+/// assert_eq!(first, "screenshot-00000000000000000999.png");
+/// assert_ne!(first, second);
+/// assert!(first < second);
+/// ```
+pub fn screenshot_filename(since_epoch: Duration) -> String {
+    format!("screenshot-{:020}.png", since_epoch.as_millis())
+}
+
+/// Writes the given 8-bit RGB pixels to a binary PPM file
+fn write_ppm(path: &str, width: u32, height: u32, bytes: &[u8]) -> std::io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    write!(file, "P6\n{} {}\n255\n", width, height)?;
+    file.write_all(bytes)?;
+    file.flush()
+}
+
+/// Writes the given 8-bit RGB pixels to a PNG file
+fn write_png(path: &str, width: u32, height: u32, bytes: &[u8]) -> Result<(), String> {
+    let mut surface = Surface::new(width, height, PixelFormatEnum::RGB24)?;
+    let pitch = surface.pitch() as usize;
+    let row_length = width as usize * 3;
+    surface.with_lock_mut(|surface_bytes| {
+        for (y, row) in bytes.chunks(row_length).enumerate() {
+            surface_bytes[y * pitch..y * pitch + row_length].copy_from_slice(row);
+        }
+    });
+    surface.save(path)
+}
+
+pub mod accumulator;
+pub mod adaptive;
+pub mod aov;
+pub mod path_tracer;
@@ -0,0 +1,295 @@
+//! This module renders Scenes by path tracing, an alternative to the Phong shading in `Scene`
+//!
+//! Instead of lighting each surface directly from the scene's lights, each ray is scattered by
+//! the materials it hits until it escapes to the background or runs out of bounces, picking up
+//! the light emitted by every surface along the way. Averaging many such paths per pixel gives
+//! soft shadows, color bleeding, and glossy reflections, at the cost of noise when too few
+//! paths are traced.
+
+use rand::Rng;
+
+use crate::camera::Camera;
+use crate::color::{Color, ToneMapping};
+use crate::framebuffer::Framebuffer;
+use crate::material::Scatter;
+use crate::random::RenderRng;
+use crate::ray::Ray;
+use crate::scene::Scene;
+
+/// Returns one random estimate of the light arriving along the given ray, following it through
+/// at most the given number of bounces.
+///
+/// At each hit, the surface's emission is added to the light scattered towards it, attenuated
+/// by the surface. Rays that miss everything see the background, and paths that run out of
+/// bounces only see the emission of the last surface hit. The scene's lights are not used.
+///
+/// Every random choice along the path is taken from the given generator.
+///
+/// # Example
+///
+/// ```
+/// let mut scene = ray_tracer::scene::Scene::new(ray_tracer::color::Color::new(0.0, 0.0, 1.0));
+/// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+///     1.0,
+///     ray_tracer::material::Material {
+///         color: ray_tracer::color::Color::new(0.0, 0.0, 0.0),
+///         emission: ray_tracer::color::Color::new(1.0, 0.5, 0.0),
+///         ..Default::default()
+///     },
+/// )));
+///
+/// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+/// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+/// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+/// let mut rng = ray_tracer::random::RenderRng::seeded(0);
+/// let color = ray_tracer::render::path_tracer::ray_color(&scene, &ray, 5, &mut rng);
+/// assert_eq!(color, ray_tracer::color::Color::new(1.0, 0.5, 0.0));
+///
+/// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+/// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+/// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+/// let color = ray_tracer::render::path_tracer::ray_color(&scene, &ray, 5, &mut rng);
+/// assert_eq!(color, ray_tracer::color::Color::new(0.0, 0.0, 1.0));
+/// ```
+pub fn ray_color(scene: &Scene, ray: &Ray, depth: u32, rng: &mut RenderRng) -> Color {
+    let hit = match scene.closest_hit(ray) {
+        Some(hit) => hit,
+        None => return scene.background(ray),
+    };
+    let emitted = hit.material.emission.clone();
+    if depth == 0 {
+        return emitted;
+    }
+    match hit.material.scatter(ray, &hit, rng) {
+        Some((scattered, attenuation)) => {
+            let incoming = ray_color(scene, &scattered, depth - 1, rng);
+            emitted.add(&attenuation.component_mul(&incoming))
+        }
+        None => emitted,
+    }
+}
+
+/// The struct holding the settings of the path tracer
+///
+/// Contains the number of paths traced through each pixel, the number of
+/// times each path may bounce, and the luminance that a single path's color is
+/// clamped to before it is averaged, if any.
+///
+/// Rare paths that find a small, bright light carry far more light than their
+/// neighbours, and show up as lone bright pixels, or fireflies. Clamping them
+/// removes the fireflies, at the cost of darkening the image a little.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PathTracerSettings {
+    pub samples_per_pixel: u32,
+    pub max_depth: u32,
+    pub max_sample_luminance: Option<f64>,
+}
+
+impl Default for PathTracerSettings {
+    fn default() -> Self {
+        PathTracerSettings {
+            samples_per_pixel: 100,
+            max_depth: 10,
+            max_sample_luminance: None,
+        }
+    }
+}
+
+impl PathTracerSettings {
+    /// Creates new settings tracing the given number of paths through each pixel, each bouncing
+    /// at most the given number of times, without clamping
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let settings = ray_tracer::render::path_tracer::PathTracerSettings::new(64, 5);
+    ///
+    /// assert_eq!(settings.samples_per_pixel, 64);
+    /// assert_eq!(settings.max_depth, 5);
+    /// assert_eq!(settings.max_sample_luminance, None);
+    /// ```
+    pub fn new(samples_per_pixel: u32, max_depth: u32) -> PathTracerSettings {
+        PathTracerSettings {
+            samples_per_pixel,
+            max_depth,
+            max_sample_luminance: None,
+        }
+    }
+
+    /// Sets the luminance that each path's color is clamped to
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let settings =
+    ///     ray_tracer::render::path_tracer::PathTracerSettings::new(64, 5).with_max_luminance(10.0);
+    ///
+    /// assert_eq!(settings.max_sample_luminance, Some(10.0));
+    /// ```
+    pub fn with_max_luminance(mut self, max_luminance: f64) -> PathTracerSettings {
+        self.max_sample_luminance = Some(max_luminance);
+        self
+    }
+
+    /// Returns the given sample, scaled down to the maximum luminance if it is brighter
+    ///
+    /// Scaling the whole color, rather than clamping each component, keeps its hue.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let settings =
+    ///     ray_tracer::render::path_tracer::PathTracerSettings::new(64, 5).with_max_luminance(2.0);
+    ///
+    /// let normal = ray_tracer::color::Color::new(0.5, 0.8, 0.2);
+    /// assert_eq!(settings.clamp_sample(normal.clone()), normal);
+    ///
+    /// let outlier = ray_tracer::color::Color::new(100.0, 50.0, 100.0);
+    /// let clamped = settings.clamp_sample(outlier);
+    /// assert!((clamped.luminance() - 2.0).abs() < 1e-10);
+    /// assert!((clamped.r - clamped.b).abs() < 1e-10);
+    /// ```
+    pub fn clamp_sample(&self, sample: Color) -> Color {
+        match self.max_sample_luminance {
+            Some(max) => {
+                let luminance = sample.luminance();
+                if luminance > max {
+                    sample.mul(max / luminance)
+                } else {
+                    sample
+                }
+            }
+            None => sample,
+        }
+    }
+
+    /// Returns the average of the given samples, each clamped first
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let samples = vec![
+    ///     ray_tracer::color::Color::new(0.5, 0.5, 0.5),
+    ///     ray_tracer::color::Color::new(0.5, 0.5, 0.5),
+    ///     ray_tracer::color::Color::new(0.5, 0.5, 0.5),
+    ///     ray_tracer::color::Color::new(1000.0, 1000.0, 1000.0),
+    /// ];
+    ///
+    /// let unclamped = ray_tracer::render::path_tracer::PathTracerSettings::new(4, 5);
+    /// let clamped = unclamped.with_max_luminance(1.0);
+    ///
+    /// let firefly = unclamped.average(&samples);
+    /// assert!(firefly.r > 250.0);
+    /// let mean = clamped.average(&samples);
+    /// assert!(mean.approx_eq(&ray_tracer::color::Color::new(0.625, 0.625, 0.625), 1e-10));
+    /// ```
+    pub fn average(&self, samples: &[Color]) -> Color {
+        if samples.is_empty() {
+            return Color::default();
+        }
+        samples
+            .iter()
+            .fold(Color::default(), |sum, sample| {
+                sum.add(&self.clamp_sample(sample.clone()))
+            })
+            .mul(1.0 / samples.len() as f64)
+    }
+}
+
+/// Renders the given scene seen by the given camera into a framebuffer of the given size by
+/// path tracing, averaging paths through random points in each pixel, as given by the
+/// settings.
+///
+/// The framebuffer holds the linear color of each pixel, after the given tone mapping, as with
+/// `render::render`.
+///
+/// All of the randomness comes from generators seeded with the given seed, one for each pixel,
+/// so rendering the same scene with the same seed always gives the same image.
+///
+/// # Example
+///
+/// ```
+/// // A closed box, lit only by its glowing ceiling
+/// let mut scene = ray_tracer::scene::Scene::default();
+/// let grey = ray_tracer::material::Material {
+///     color: ray_tracer::color::Color::new(0.5, 0.5, 0.5),
+///     ..Default::default()
+/// };
+/// let walls = [
+///     ((0.0, -1.0, 0.0), (0.0, 1.0, 0.0)),
+///     ((0.0, 0.0, -1.0), (0.0, 0.0, 1.0)),
+///     ((0.0, 0.0, 1.0), (0.0, 0.0, -1.0)),
+///     ((-1.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+///     ((1.0, 0.0, 0.0), (-1.0, 0.0, 0.0)),
+/// ];
+/// for &(point, normal) in walls.iter() {
+///     scene.add(Box::new(ray_tracer::object::plane::Plane::new(
+///         point.into(),
+///         normal.into(),
+///         grey.clone(),
+///     )));
+/// }
+/// scene.add(Box::new(ray_tracer::object::plane::Plane::new(
+///     ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0),
+///     ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0),
+///     ray_tracer::material::Material {
+///         color: ray_tracer::color::Color::new(0.0, 0.0, 0.0),
+///         emission: ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+///         ..Default::default()
+///     },
+/// )));
+/// let camera = ray_tracer::camera::Camera::default();
+/// let tone_mapping = ray_tracer::color::ToneMapping::None;
+///
+/// let render = |width, height, samples, seed| {
+///     let settings = ray_tracer::render::path_tracer::PathTracerSettings::new(samples, 10);
+///     ray_tracer::render::path_tracer::render_path_traced(
+///         &scene,
+///         &camera,
+///         width,
+///         height,
+///         &settings,
+///         tone_mapping,
+///         seed,
+///     )
+/// };
+///
+/// // With enough samples, renders of the center pixel with different seeds settle on the same
+/// // color
+/// let first = render(1, 1, 4000, 1).get(0, 0).unwrap().r;
+/// let second = render(1, 1, 4000, 2).get(0, 0).unwrap().r;
+/// assert!(first > 0.0 && first < 1.0);
+/// assert!((first - second).abs() < 0.05);
+///
+/// // The same seed always renders the same image, and different seeds add different noise
+/// assert_eq!(render(4, 4, 4, 7), render(4, 4, 4, 7));
+/// assert_ne!(render(4, 4, 4, 7), render(4, 4, 4, 8));
+/// ```
+pub fn render_path_traced(
+    scene: &Scene,
+    camera: &Camera,
+    width: u32,
+    height: u32,
+    settings: &PathTracerSettings,
+    tone_mapping: ToneMapping,
+    seed: u64,
+) -> Framebuffer {
+    let samples_per_pixel = settings.samples_per_pixel.max(1);
+    let mut framebuffer = Framebuffer::new(width, height);
+    for dy in 0..height {
+        for dx in 0..width {
+            let mut rng = RenderRng::for_pixel(seed, dx, dy);
+            let samples: Vec<Color> = (0..samples_per_pixel)
+                .map(|_| {
+                    let u = (dx as f64 + rng.gen::<f64>()) / width as f64;
+                    let v = 1.0 - (dy as f64 + rng.gen::<f64>()) / height as f64;
+                    let ray = camera.generate_ray(u, v, &mut rng);
+                    ray_color(scene, &ray, settings.max_depth, &mut rng)
+                })
+                .collect();
+            framebuffer.set(dx, dy, tone_mapping.apply(settings.average(&samples)));
+        }
+    }
+    framebuffer
+}
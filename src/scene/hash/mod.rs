@@ -0,0 +1,347 @@
+//! This module computes keys identifying what a render of a Scene would show, for caching
+
+use std::hash::Hasher;
+
+use super::Scene;
+use crate::background::Background;
+use crate::camera::{Camera, ProjectionMode};
+use crate::color::Color;
+use crate::material::Material;
+use crate::texture::Texture;
+use crate::vector3d::Vector3;
+
+/// The FNV-1a offset basis, the state of a hasher that has not been fed anything
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// The FNV-1a prime, which the state is multiplied by after each byte
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The struct representing a hasher for the parts of a scene
+///
+/// Contains the state of a 64-bit FNV-1a hash. Unlike the standard library's
+/// default hasher, the algorithm is fixed, so the same inputs hash the same
+/// in every build.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SceneHasher {
+    state: u64,
+}
+
+impl Default for SceneHasher {
+    fn default() -> Self {
+        SceneHasher {
+            state: FNV_OFFSET_BASIS,
+        }
+    }
+}
+
+impl SceneHasher {
+    /// Creates a new hasher that has not been fed anything
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// // The empty input hashes to the FNV-1a offset basis
+    /// let hasher = ray_tracer::scene::hash::SceneHasher::new();
+    /// assert_eq!(hasher.finish(), 0xcbf29ce484222325);
+    ///
+    /// let mut hasher = ray_tracer::scene::hash::SceneHasher::new();
+    /// hasher.write(b"a");
+    /// assert_eq!(hasher.finish(), 0xaf63dc4c8601ec8c);
+    /// ```
+    pub fn new() -> SceneHasher {
+        SceneHasher::default()
+    }
+
+    /// Feeds the bits of the given number to this hasher
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// let hash = |value| {
+    ///     let mut hasher = ray_tracer::scene::hash::SceneHasher::new();
+    ///     hasher.write_f64(value);
+    ///     hasher.finish()
+    /// };
+    ///
+    /// assert_eq!(hash(1.0), hash(1.0));
+    /// assert_ne!(hash(1.0), hash(-1.0));
+    /// // Zeros of either sign look the same, but are hashed apart
+    /// assert_ne!(hash(0.0), hash(-0.0));
+    /// ```
+    pub fn write_f64(&mut self, value: f64) {
+        self.write_u64(value.to_bits());
+    }
+
+    /// Feeds the bits of each component of the given vector to this hasher
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// let hash = |v: &ray_tracer::vector3d::Vector3| {
+    ///     let mut hasher = ray_tracer::scene::hash::SceneHasher::new();
+    ///     hasher.write_vector(v);
+    ///     hasher.finish()
+    /// };
+    ///
+    /// let v = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0);
+    /// let swapped = ray_tracer::vector3d::Vector3::new(2.0, 1.0, 3.0);
+    /// assert_ne!(hash(&v), hash(&swapped));
+    /// ```
+    pub fn write_vector(&mut self, v: &Vector3) {
+        for &component in &v.to_array() {
+            self.write_f64(component);
+        }
+    }
+
+    /// Feeds the bits of each component of the given color to this hasher
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// let hash = |color: &ray_tracer::color::Color| {
+    ///     let mut hasher = ray_tracer::scene::hash::SceneHasher::new();
+    ///     hasher.write_color(color);
+    ///     hasher.finish()
+    /// };
+    ///
+    /// let red = ray_tracer::color::Color::new(1.0, 0.0, 0.0);
+    /// let green = ray_tracer::color::Color::new(0.0, 1.0, 0.0);
+    /// assert_ne!(hash(&red), hash(&green));
+    /// ```
+    pub fn write_color(&mut self, color: &Color) {
+        for &component in &[color.r, color.g, color.b] {
+            self.write_f64(component);
+        }
+    }
+
+    /// Feeds every field of the given material to this hasher, including its textures
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::hash::Hasher;
+    ///
+    /// let hash = |material: &ray_tracer::material::Material| {
+    ///     let mut hasher = ray_tracer::scene::hash::SceneHasher::new();
+    ///     hasher.write_material(material);
+    ///     hasher.finish()
+    /// };
+    ///
+    /// let plain = ray_tracer::material::Material::default();
+    /// let black = ray_tracer::color::Color::new(0.0, 0.0, 0.0);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let checkered = |odd| {
+    ///     ray_tracer::material::Material::builder()
+    ///         .texture(std::sync::Arc::new(ray_tracer::texture::Checker::new(
+    ///             black.clone(),
+    ///             odd,
+    ///         )))
+    ///         .build()
+    /// };
+    ///
+    /// assert_ne!(hash(&plain), hash(&checkered(white.clone())));
+    /// assert_eq!(hash(&checkered(white.clone())), hash(&checkered(white.clone())));
+    /// assert_ne!(hash(&checkered(white.clone())), hash(&checkered(white.mul(0.5))));
+    /// ```
+    pub fn write_material(&mut self, material: &Material) {
+        self.write_color(&material.color);
+        match material.texture {
+            Some(ref texture) => {
+                self.write_u8(1);
+                texture.hash_into(self);
+            }
+            None => self.write_u8(0),
+        }
+        for &value in &[
+            material.specular,
+            material.shininess,
+            material.reflectivity,
+            material.fuzz,
+            material.transparency,
+            material.index_of_refraction,
+        ] {
+            self.write_f64(value);
+        }
+        self.write_color(&material.emission);
+        match material.normal_map {
+            Some(ref normal_map) => {
+                self.write_u8(1);
+                normal_map.hash_into(self);
+            }
+            None => self.write_u8(0),
+        }
+    }
+}
+
+impl Hasher for SceneHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state ^= u64::from(byte);
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+/// Returns a hash of everything about the given scene, camera, and image size that changes the
+/// rendered image
+///
+/// This covers every parameter of every object and light, with the materials and textures of
+/// the objects, the ambient light, the background, the fog, every parameter of the camera, and
+/// the size of the image. The same inputs always hash the same, in every run, so an unchanged
+/// hash means a cached render can be reused. Images are identified by the digest computed when
+/// they are created, and textures computed by functions by their keys, as described for
+/// `ImageTexture` and `UvTexture`.
+///
+/// # Example
+///
+/// ```
+/// let build_scene = |radius| {
+///     let mut scene = ray_tracer::scene::Scene::default();
+///     scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+///         ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+///         radius,
+///         ray_tracer::material::Material::default(),
+///     )));
+///     scene
+/// };
+/// let camera = ray_tracer::camera::Camera::default();
+/// let hash = |scene: &ray_tracer::scene::Scene, camera| {
+///     ray_tracer::scene::hash::scene_hash(scene, camera, 640, 480)
+/// };
+///
+/// // Identical scenes hash the same
+/// assert_eq!(hash(&build_scene(1.0), &camera), hash(&build_scene(1.0), &camera));
+///
+/// // Changing a sphere's radius, or moving the camera, changes the hash
+/// assert_ne!(hash(&build_scene(1.0), &camera), hash(&build_scene(1.5), &camera));
+/// let mut moved = ray_tracer::camera::Camera::default();
+/// moved.translate(1.0, 0.0, 0.0);
+/// assert_ne!(hash(&build_scene(1.0), &camera), hash(&build_scene(1.0), &moved));
+///
+/// // So does the size of the image
+/// let scene = build_scene(1.0);
+/// assert_ne!(
+///     ray_tracer::scene::hash::scene_hash(&scene, &camera, 640, 480),
+///     ray_tracer::scene::hash::scene_hash(&scene, &camera, 320, 240)
+/// );
+///
+/// // And the background
+/// let mut sky = build_scene(1.0);
+/// sky.background = ray_tracer::background::Background::sky();
+/// assert_ne!(hash(&build_scene(1.0), &camera), hash(&sky, &camera));
+///
+/// // Tilting a plane, which has no bounds, changes the hash too
+/// let with_plane = |normal| {
+///     let mut scene = ray_tracer::scene::Scene::default();
+///     scene.add(Box::new(ray_tracer::object::plane::Plane::new(
+///         ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0),
+///         normal,
+///         ray_tracer::material::Material::default(),
+///     )));
+///     scene
+/// };
+/// let up = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+/// let tilted = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 1.0);
+/// assert_ne!(hash(&with_plane(up), &camera), hash(&with_plane(tilted), &camera));
+///
+/// // As does narrowing a spotlight, even though it still points the same way
+/// let with_spotlight = |outer_angle| {
+///     let mut scene = build_scene(1.0);
+///     scene.add_light(Box::new(ray_tracer::light::SpotLight::new(
+///         ray_tracer::vector3d::Vector3::new(0.0, 5.0, -5.0),
+///         ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0),
+///         ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+///         1.0,
+///         0.1,
+///         outer_angle,
+///     )));
+///     scene
+/// };
+/// assert_ne!(hash(&with_spotlight(0.3), &camera), hash(&with_spotlight(0.5), &camera));
+/// ```
+pub fn scene_hash(scene: &Scene, camera: &Camera, width: u32, height: u32) -> u64 {
+    let mut hasher = SceneHasher::new();
+
+    let objects = scene.bvh.objects().iter().chain(&scene.objects);
+    hasher.write_u64((scene.bvh.objects().len() + scene.objects.len()) as u64);
+    for object in objects {
+        object.hash_into(&mut hasher);
+    }
+
+    hasher.write_u64(scene.lights.len() as u64);
+    for light in &scene.lights {
+        light.hash_into(&mut hasher);
+    }
+    hasher.write_color(&scene.ambient.color);
+    hasher.write_f64(scene.ambient.intensity);
+    hash_background(&mut hasher, &scene.background);
+    match scene.fog {
+        Some(ref fog) => {
+            hasher.write_u8(1);
+            hasher.write_color(&fog.color);
+            hasher.write_f64(fog.density);
+        }
+        None => hasher.write_u8(0),
+    }
+
+    hash_camera(&mut hasher, camera);
+    hasher.write_u32(width);
+    hasher.write_u32(height);
+    hasher.finish()
+}
+
+/// Feeds the kind of the given background, and its colors or image, to the hasher
+fn hash_background(hasher: &mut SceneHasher, background: &Background) {
+    match background {
+        Background::Flat(color) => {
+            hasher.write_u8(0);
+            hasher.write_color(color);
+        }
+        Background::Gradient { bottom, top } => {
+            hasher.write_u8(1);
+            hasher.write_color(bottom);
+            hasher.write_color(top);
+        }
+        Background::Environment(environment) => {
+            hasher.write_u8(2);
+            environment.image.hash_into(hasher);
+        }
+    }
+}
+
+/// Feeds every parameter of the given camera to the hasher
+fn hash_camera(hasher: &mut SceneHasher, camera: &Camera) {
+    hasher.write_vector(&camera.position);
+    hasher.write_vector(&camera.target);
+    hasher.write_vector(&camera.up);
+    hasher.write_u8(match camera.projection {
+        ProjectionMode::Ortho => 0,
+        ProjectionMode::Perspective => 1,
+        ProjectionMode::Fisheye => 2,
+        ProjectionMode::Equirectangular => 3,
+    });
+    for &value in &[
+        camera.roll,
+        camera.fov_y,
+        camera.aspect,
+        camera.ortho_scale,
+        camera.aperture,
+        camera.focus_distance,
+        camera.shutter_open,
+        camera.shutter_close,
+    ] {
+        hasher.write_f64(value);
+    }
+}
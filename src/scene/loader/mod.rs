@@ -0,0 +1,638 @@
+//! This module loads Scenes from JSON scene files
+//!
+//! A scene file is a JSON object, where every member is optional:
+//!
+//! ```json
+//! {
+//!     "background": [0.0, 0.0, 0.0],
+//!     "ambient": { "color": [1.0, 1.0, 1.0], "intensity": 0.1 },
+//!     "fog": { "color": [0.5, 0.5, 0.5], "density": 0.05 },
+//!     "camera": {
+//!         "position": [0.0, 0.0, 0.0],
+//!         "target": [0.0, 0.0, -1.0],
+//!         "up": [0.0, 1.0, 0.0],
+//!         "roll": 0.0,
+//!         "fov_y": 90.0,
+//!         "aspect": 1.0,
+//!         "projection": "perspective",
+//!         "ortho_scale": 2.0,
+//!         "aperture": 0.0,
+//!         "focus_distance": 1.0,
+//!         "shutter_open": 0.0,
+//!         "shutter_close": 0.0
+//!     },
+//!     "objects": [
+//!         { "type": "sphere", "center": [0.0, 0.0, -5.0], "radius": 1.0, "material": {} },
+//!         { "type": "plane", "point": [0.0, -1.0, 0.0], "normal": [0.0, 1.0, 0.0] },
+//!         { "type": "triangle", "v0": [0, 0, 0], "v1": [1, 0, 0], "v2": [0, 1, 0] },
+//!         { "type": "mesh", "path": "teapot.obj" }
+//!     ],
+//!     "lights": [
+//!         { "type": "point", "position": [0.0, 5.0, 0.0], "color": [1, 1, 1], "intensity": 10 },
+//!         { "type": "directional", "direction": [0.0, -1.0, 0.0], "intensity": 0.5 },
+//!         {
+//!             "type": "spot",
+//!             "position": [0.0, 5.0, 0.0],
+//!             "direction": [0.0, -1.0, 0.0],
+//!             "inner_angle": 20.0,
+//!             "outer_angle": 30.0
+//!         },
+//!         {
+//!             "type": "area",
+//!             "corner": [-1.0, 5.0, -1.0],
+//!             "edge_u": [2.0, 0.0, 0.0],
+//!             "edge_v": [0.0, 0.0, 2.0],
+//!             "samples": 4
+//!         }
+//!     ]
+//! }
+//! ```
+//!
+//! Vectors and colors are arrays of three numbers. The background may instead be a gradient,
+//! `{ "bottom": color, "top": color }`, or the path of an equirectangular environment map
+//! image, relative to the current directory. A material may have any of the fields of
+//! `Material`, with a `"checker": { "even": color, "odd": color }` member for a checker texture.
+//! The camera's projection is `"perspective"`, `"ortho"`, `"fisheye"`, or `"equirectangular"`.
+//! Point, spot, and area lights may also have `"constant"`, `"linear"`, and `"quadratic"`
+//! attenuation coefficients. Spotlight cone angles are in degrees. Mesh paths are relative to
+//! the directory of the scene file.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::Scene;
+use crate::background::{Background, EnvironmentMap};
+use crate::camera::{Camera, ProjectionMode};
+use crate::color::Color;
+use crate::fog::Fog;
+use crate::json::{self, FromJson, JsonError, JsonValue, ToJson};
+use crate::light::{AmbientLight, AreaLight, DirectionalLight, Light, PointLight, SpotLight};
+use crate::material::Material;
+use crate::object::mesh::{self, ObjError};
+use crate::object::plane::Plane;
+use crate::object::sphere::Sphere;
+use crate::object::triangle::Triangle;
+use crate::object::Object;
+use crate::texture::Checker;
+use crate::vector3d::Vector3;
+
+/// An error from loading a scene file
+#[derive(Debug)]
+pub enum SceneError {
+    /// The scene file could not be read
+    Io(io::Error),
+    /// The scene file is not valid JSON, or does not describe a scene
+    Json(JsonError),
+    /// A mesh used by the scene could not be loaded
+    Mesh(ObjError),
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SceneError::Io(e) => write!(f, "could not read scene file: {}", e),
+            SceneError::Json(e) => write!(f, "invalid scene: {}", e),
+            SceneError::Mesh(e) => write!(f, "could not load mesh: {}", e),
+        }
+    }
+}
+
+impl Error for SceneError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SceneError::Io(e) => Some(e),
+            SceneError::Json(e) => Some(e),
+            SceneError::Mesh(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for SceneError {
+    fn from(e: io::Error) -> SceneError {
+        SceneError::Io(e)
+    }
+}
+
+impl From<JsonError> for SceneError {
+    fn from(e: JsonError) -> SceneError {
+        SceneError::Json(e)
+    }
+}
+
+impl From<ObjError> for SceneError {
+    fn from(e: ObjError) -> SceneError {
+        SceneError::Mesh(e)
+    }
+}
+
+/// Loads the scene described by the scene file at the given path
+///
+/// The objects are put into a bounding volume hierarchy, and the camera is stored in the
+/// scene's `camera` field.
+///
+/// # Example
+///
+/// ```
+/// let path = std::env::temp_dir().join("ray_tracer_load_scene.json");
+/// let path = path.to_str().unwrap();
+/// std::fs::write(path, r#"{ "objects": [{ "type": "sphere", "radius": 2 }] }"#).unwrap();
+///
+/// let scene = ray_tracer::scene::loader::load_scene(path).unwrap();
+/// assert_eq!(scene.bvh.objects().len(), 1);
+/// std::fs::remove_file(path).unwrap();
+///
+/// match ray_tracer::scene::loader::load_scene("does_not_exist.json") {
+///     Err(ray_tracer::scene::loader::SceneError::Io(_)) => {}
+///     _ => panic!("expected a missing file to be an IO error"),
+/// }
+/// ```
+pub fn load_scene(path: &str) -> Result<Scene, SceneError> {
+    let source = fs::read_to_string(path)?;
+    let directory = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+    scene_from_json(&json::parse(&source)?, directory)
+}
+
+/// Parses the scene described by the given scene file contents
+///
+/// Mesh paths are relative to the current directory.
+///
+/// # Example
+///
+/// ```
+/// use ray_tracer::object::Object;
+///
+/// let source = r#"{
+///     "background": [0.1, 0.2, 0.3],
+///     "camera": { "position": [0, 1, 5], "target": [0, 0, 0], "fov_y": 60 },
+///     "objects": [
+///         {
+///             "type": "sphere",
+///             "center": [0, 0, -5],
+///             "radius": 1,
+///             "material": { "color": [1, 0, 0], "specular": 0.5 }
+///         },
+///         {
+///             "type": "sphere",
+///             "center": [2, 0, -5],
+///             "radius": 0.5,
+///             "material": { "color": [0, 0, 1], "reflectivity": 0.8 }
+///         }
+///     ],
+///     "lights": [{ "type": "point", "position": [0, 5, 0], "intensity": 10 }]
+/// }"#;
+/// let scene = ray_tracer::scene::loader::parse_scene(source).unwrap();
+///
+/// assert_eq!(
+///     scene.background,
+///     ray_tracer::background::Background::Flat(ray_tracer::color::Color::new(0.1, 0.2, 0.3))
+/// );
+/// assert_eq!(scene.lights.len(), 1);
+/// let camera = scene.camera.as_ref().unwrap();
+/// assert_eq!(camera.position, ray_tracer::vector3d::Vector3::new(0.0, 1.0, 5.0));
+/// assert_eq!(camera.fov_y, 60.0);
+///
+/// let objects = scene.bvh.objects();
+/// assert_eq!(objects.len(), 2);
+/// let red = objects.iter().find(|o| o.material().color.r == 1.0).unwrap();
+/// let blue = objects.iter().find(|o| o.material().color.b == 1.0).unwrap();
+/// assert_eq!(red.material().color, ray_tracer::color::Color::new(1.0, 0.0, 0.0));
+/// assert_eq!(red.material().specular, 0.5);
+/// assert_eq!(blue.material().reflectivity, 0.8);
+///
+/// let ray = ray_tracer::ray::Ray::new(
+///     ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0),
+///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0),
+/// );
+/// assert_eq!(scene.closest_hit(&ray).unwrap().t, 4.5);
+///
+/// let source = r#"{ "objects": [{ "type": "cube" }] }"#;
+/// let error = ray_tracer::scene::loader::parse_scene(source).err().unwrap();
+/// assert_eq!(error.to_string(), "invalid scene: unknown object type \"cube\"");
+///
+/// let source = r#"{ "camera": { "fov_y": 0 } }"#;
+/// assert!(ray_tracer::scene::loader::parse_scene(source).is_err());
+///
+/// let source = r#"{ "fog": { "color": [0.5, 0.5, 0.5], "density": 0.1 } }"#;
+/// let scene = ray_tracer::scene::loader::parse_scene(source).unwrap();
+/// assert_eq!(scene.fog.unwrap().density, 0.1);
+/// ```
+pub fn parse_scene(source: &str) -> Result<Scene, SceneError> {
+    scene_from_json(&json::parse(source)?, Path::new(""))
+}
+
+/// Creates the scene described by the given JSON value, with mesh paths relative to the given
+/// directory
+fn scene_from_json(value: &JsonValue, directory: &Path) -> Result<Scene, SceneError> {
+    let mut scene = Scene::default();
+    if let Some(background) = value.field("background")? {
+        scene.background = background;
+    }
+    if let Some(ambient) = value.field("ambient")? {
+        scene.ambient = ambient;
+    }
+    scene.fog = value.field("fog")?;
+    scene.camera = value.field("camera")?;
+    for object in array_field(value, "objects")? {
+        for object in objects_from_json(object, directory)? {
+            scene.add(object);
+        }
+    }
+    for light in array_field(value, "lights")? {
+        scene.add_light(light_from_json(light)?);
+    }
+    scene.build_bvh();
+    Ok(scene)
+}
+
+/// Returns the elements of the array member with the given key, or no elements if it is missing
+fn array_field<'a>(value: &'a JsonValue, key: &str) -> Result<&'a [JsonValue], JsonError> {
+    match value.get(key) {
+        Some(array) => array
+            .as_array()
+            .ok_or_else(|| JsonError::Invalid(format!("\"{}\": expected an array", key))),
+        None => Ok(&[]),
+    }
+}
+
+/// Returns the "type" member of the given JSON object
+fn type_of(value: &JsonValue) -> Result<String, JsonError> {
+    value.required_field("type")
+}
+
+/// Creates the objects described by the given JSON value, which is a single object unless it
+/// is a mesh
+fn objects_from_json(
+    value: &JsonValue,
+    directory: &Path,
+) -> Result<Vec<Box<dyn Object>>, SceneError> {
+    let material = value.field("material")?.unwrap_or_default();
+    let object: Box<dyn Object> = match type_of(value)?.as_str() {
+        "sphere" => Box::new(Sphere::from_json(value)?),
+        "plane" => Box::new(Plane::new(
+            value.field("point")?.unwrap_or_default(),
+            value
+                .field("normal")?
+                .unwrap_or_else(|| Vector3::new(0.0, 1.0, 0.0)),
+            material,
+        )),
+        "triangle" => Box::new(Triangle::new(
+            value.required_field("v0")?,
+            value.required_field("v1")?,
+            value.required_field("v2")?,
+            material,
+        )),
+        "mesh" => {
+            let path: String = value.required_field("path")?;
+            let path = directory.join(path);
+            let triangles = mesh::load_obj(&path.to_string_lossy(), material)?;
+            return Ok(triangles
+                .into_iter()
+                .map(|triangle| Box::new(triangle) as Box<dyn Object>)
+                .collect());
+        }
+        other => {
+            return Err(JsonError::Invalid(format!("unknown object type \"{}\"", other)).into())
+        }
+    };
+    Ok(vec![object])
+}
+
+/// Creates the light described by the given JSON value
+fn light_from_json(value: &JsonValue) -> Result<Box<dyn Light>, JsonError> {
+    let color = value
+        .field("color")?
+        .unwrap_or_else(|| Color::new(1.0, 1.0, 1.0));
+    let intensity = value.field("intensity")?.unwrap_or(1.0);
+    match type_of(value)?.as_str() {
+        "point" => {
+            let mut light = PointLight::new(value.required_field("position")?, color, intensity);
+            light.constant = value.field("constant")?.unwrap_or(light.constant);
+            light.linear = value.field("linear")?.unwrap_or(light.linear);
+            light.quadratic = value.field("quadratic")?.unwrap_or(light.quadratic);
+            Ok(Box::new(light))
+        }
+        "directional" => Ok(Box::new(DirectionalLight::new(
+            value.required_field("direction")?,
+            color,
+            intensity,
+        ))),
+        "area" => {
+            let mut light = AreaLight::new(
+                value.required_field("corner")?,
+                value.required_field("edge_u")?,
+                value.required_field("edge_v")?,
+                color,
+                intensity,
+                value.field::<f64>("samples")?.unwrap_or(4.0) as u32,
+            );
+            light.constant = value.field("constant")?.unwrap_or(light.constant);
+            light.linear = value.field("linear")?.unwrap_or(light.linear);
+            light.quadratic = value.field("quadratic")?.unwrap_or(light.quadratic);
+            Ok(Box::new(light))
+        }
+        "spot" => {
+            let inner_angle: f64 = value.required_field("inner_angle")?;
+            let outer_angle: f64 = value.required_field("outer_angle")?;
+            let mut light = SpotLight::new(
+                value.required_field("position")?,
+                value.required_field("direction")?,
+                color,
+                intensity,
+                inner_angle.to_radians(),
+                outer_angle.to_radians(),
+            );
+            light.constant = value.field("constant")?.unwrap_or(light.constant);
+            light.linear = value.field("linear")?.unwrap_or(light.linear);
+            light.quadratic = value.field("quadratic")?.unwrap_or(light.quadratic);
+            Ok(Box::new(light))
+        }
+        other => Err(JsonError::Invalid(format!(
+            "unknown light type \"{}\"",
+            other
+        ))),
+    }
+}
+
+/// Returns the three numbers in the given JSON array
+fn triple_from_json(value: &JsonValue) -> Result<[f64; 3], JsonError> {
+    match value.as_array() {
+        Some([x, y, z]) => Ok([f64::from_json(x)?, f64::from_json(y)?, f64::from_json(z)?]),
+        _ => Err(JsonError::Invalid(format!(
+            "expected an array of three numbers, found {}",
+            value
+        ))),
+    }
+}
+
+impl FromJson for Vector3 {
+    fn from_json(value: &JsonValue) -> Result<Vector3, JsonError> {
+        let [x, y, z] = triple_from_json(value)?;
+        Ok(Vector3::new(x, y, z))
+    }
+}
+
+/// Returns a JSON array of the three given numbers
+fn triple_to_json(x: f64, y: f64, z: f64) -> JsonValue {
+    JsonValue::Array(vec![x.to_json(), y.to_json(), z.to_json()])
+}
+
+impl ToJson for Vector3 {
+    /// Converts this vector into an array of three numbers
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::json::{FromJson, ToJson};
+    ///
+    /// let vector = ray_tracer::vector3d::Vector3::new(1.0, -2.5, 3.0);
+    ///
+    /// assert_eq!(vector.to_json().to_string(), "[1,-2.5,3]");
+    /// let read_back = ray_tracer::vector3d::Vector3::from_json(&vector.to_json()).unwrap();
+    /// assert_eq!(read_back, vector);
+    /// ```
+    fn to_json(&self) -> JsonValue {
+        triple_to_json(self.x, self.y, self.z)
+    }
+}
+
+impl FromJson for Color {
+    fn from_json(value: &JsonValue) -> Result<Color, JsonError> {
+        let [r, g, b] = triple_from_json(value)?;
+        Ok(Color::new(r, g, b))
+    }
+}
+
+impl ToJson for Color {
+    fn to_json(&self) -> JsonValue {
+        triple_to_json(self.r, self.g, self.b)
+    }
+}
+
+impl FromJson for Background {
+    fn from_json(value: &JsonValue) -> Result<Background, JsonError> {
+        match value {
+            JsonValue::Array(_) => Ok(Background::Flat(Color::from_json(value)?)),
+            JsonValue::String(path) => EnvironmentMap::from_file(path)
+                .map(|environment| Background::Environment(Arc::new(environment)))
+                .map_err(|e| JsonError::Invalid(format!("could not load \"{}\": {}", path, e))),
+            _ => Ok(Background::Gradient {
+                bottom: value.required_field("bottom")?,
+                top: value.required_field("top")?,
+            }),
+        }
+    }
+}
+
+impl FromJson for Material {
+    fn from_json(value: &JsonValue) -> Result<Material, JsonError> {
+        let default = Material::default();
+        let texture = match value.get("checker") {
+            Some(checker) => Some(Arc::new(Checker::new(
+                checker.required_field("even")?,
+                checker.required_field("odd")?,
+            )) as Arc<_>),
+            None => None,
+        };
+        Ok(Material {
+            color: value.field("color")?.unwrap_or(default.color),
+            texture,
+            specular: value.field("specular")?.unwrap_or(default.specular),
+            shininess: value.field("shininess")?.unwrap_or(default.shininess),
+            reflectivity: value.field("reflectivity")?.unwrap_or(default.reflectivity),
+            fuzz: value.field("fuzz")?.unwrap_or(default.fuzz),
+            transparency: value.field("transparency")?.unwrap_or(default.transparency),
+            index_of_refraction: value
+                .field("index_of_refraction")?
+                .unwrap_or(default.index_of_refraction),
+            emission: value.field("emission")?.unwrap_or(default.emission),
+            normal_map: None,
+        })
+    }
+}
+
+impl ToJson for Material {
+    /// Converts this material into an object with a member for each of its fields
+    ///
+    /// A checker texture becomes a `"checker"` member. Other textures and normal maps cannot be
+    /// converted, so they are left out.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::json::{FromJson, ToJson};
+    ///
+    /// let material = ray_tracer::material::Material::builder()
+    ///     .color(ray_tracer::color::Color::new(0.2, 0.4, 0.6))
+    ///     .reflectivity(0.5)
+    ///     .index_of_refraction(1.5)
+    ///     .build();
+    ///
+    /// let json = material.to_json();
+    /// assert_eq!(json.get("reflectivity"), Some(&ray_tracer::json::JsonValue::Number(0.5)));
+    ///
+    /// let read_back = ray_tracer::material::Material::from_json(&json).unwrap();
+    /// assert_eq!(read_back.color, material.color);
+    /// assert_eq!(read_back.reflectivity, 0.5);
+    /// assert_eq!(read_back.index_of_refraction, 1.5);
+    /// assert_eq!(read_back.shininess, material.shininess);
+    /// assert!(read_back.texture.is_none());
+    ///
+    /// // Checker textures survive being saved and loaded again
+    /// let black = ray_tracer::color::Color::new(0.0, 0.0, 0.0);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let checkered = ray_tracer::material::Material::builder()
+    ///     .texture(std::sync::Arc::new(ray_tracer::texture::Checker::new(black, white.clone())))
+    ///     .build();
+    /// let read_back = ray_tracer::material::Material::from_json(&checkered.to_json()).unwrap();
+    /// let checker = read_back.texture.unwrap();
+    /// assert_eq!(checker.as_checker().unwrap().odd, white);
+    /// ```
+    fn to_json(&self) -> JsonValue {
+        let mut members = vec![
+            ("color".to_string(), self.color.to_json()),
+            ("specular".to_string(), self.specular.to_json()),
+            ("shininess".to_string(), self.shininess.to_json()),
+            ("reflectivity".to_string(), self.reflectivity.to_json()),
+            ("fuzz".to_string(), self.fuzz.to_json()),
+            ("transparency".to_string(), self.transparency.to_json()),
+            (
+                "index_of_refraction".to_string(),
+                self.index_of_refraction.to_json(),
+            ),
+            ("emission".to_string(), self.emission.to_json()),
+        ];
+        if let Some(checker) = self.texture.as_ref().and_then(|t| t.as_checker()) {
+            members.push(("checker".to_string(), checker.to_json()));
+        }
+        JsonValue::Object(members)
+    }
+}
+
+impl ToJson for Checker {
+    /// Converts this checkerboard into an object with its even and odd colors
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Object(vec![
+            ("even".to_string(), self.even.to_json()),
+            ("odd".to_string(), self.odd.to_json()),
+        ])
+    }
+}
+
+impl FromJson for Sphere {
+    fn from_json(value: &JsonValue) -> Result<Sphere, JsonError> {
+        Ok(Sphere::new(
+            value.field("center")?.unwrap_or_default(),
+            value.field("radius")?.unwrap_or(1.0),
+            value.field("material")?.unwrap_or_default(),
+        ))
+    }
+}
+
+impl ToJson for Sphere {
+    /// Converts this sphere into an object in the format of the scene file's objects
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::json::{FromJson, ToJson};
+    ///
+    /// let gold = ray_tracer::color::Color::new(0.8, 0.6, 0.2);
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(1.0, 2.0, -5.0),
+    ///     1.5,
+    ///     ray_tracer::material::Material::metal(gold, 0.3),
+    /// );
+    ///
+    /// let source = sphere.to_json().to_string();
+    /// let value = ray_tracer::json::parse(&source).unwrap();
+    /// assert_eq!(value.get("type"), Some(&ray_tracer::json::JsonValue::String("sphere".into())));
+    ///
+    /// let read_back = ray_tracer::object::sphere::Sphere::from_json(&value).unwrap();
+    /// assert_eq!(read_back.pos, sphere.pos);
+    /// assert_eq!(read_back.radius, sphere.radius);
+    /// assert_eq!(read_back.material.color, sphere.material.color);
+    /// assert_eq!(read_back.material.reflectivity, sphere.material.reflectivity);
+    /// assert_eq!(read_back.material.fuzz, sphere.material.fuzz);
+    /// ```
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Object(vec![
+            ("type".to_string(), "sphere".to_string().to_json()),
+            ("center".to_string(), self.pos.to_json()),
+            ("radius".to_string(), self.radius.to_json()),
+            ("material".to_string(), self.material.to_json()),
+        ])
+    }
+}
+
+impl FromJson for AmbientLight {
+    fn from_json(value: &JsonValue) -> Result<AmbientLight, JsonError> {
+        Ok(AmbientLight::new(
+            value
+                .field("color")?
+                .unwrap_or_else(|| Color::new(1.0, 1.0, 1.0)),
+            value.field("intensity")?.unwrap_or(1.0),
+        ))
+    }
+}
+
+impl FromJson for Fog {
+    fn from_json(value: &JsonValue) -> Result<Fog, JsonError> {
+        Ok(Fog::new(
+            value
+                .field("color")?
+                .unwrap_or_else(|| Color::new(1.0, 1.0, 1.0)),
+            value.field("density")?.unwrap_or(0.0),
+        ))
+    }
+}
+
+impl FromJson for ProjectionMode {
+    fn from_json(value: &JsonValue) -> Result<ProjectionMode, JsonError> {
+        match value.as_str() {
+            Some("perspective") => Ok(ProjectionMode::Perspective),
+            Some("ortho") => Ok(ProjectionMode::Ortho),
+            Some("fisheye") => Ok(ProjectionMode::Fisheye),
+            Some("equirectangular") => Ok(ProjectionMode::Equirectangular),
+            _ => Err(JsonError::Invalid(format!(
+                "expected \"perspective\", \"ortho\", \"fisheye\", or \"equirectangular\", \
+                 found {}",
+                value
+            ))),
+        }
+    }
+}
+
+impl FromJson for Camera {
+    fn from_json(value: &JsonValue) -> Result<Camera, JsonError> {
+        let default = Camera::default();
+        let fov_y = value.field("fov_y")?.unwrap_or(default.fov_y);
+        if !Camera::is_valid_fov(fov_y) {
+            return Err(JsonError::Invalid(format!(
+                "expected a fov_y between 0 and 180 degrees, found {}",
+                fov_y
+            )));
+        }
+        Ok(Camera {
+            position: value.field("position")?.unwrap_or(default.position),
+            target: value.field("target")?.unwrap_or(default.target),
+            up: value.field("up")?.unwrap_or(default.up),
+            roll: value.field("roll")?.unwrap_or(default.roll),
+            fov_y,
+            aspect: value.field("aspect")?.unwrap_or(default.aspect),
+            projection: value.field("projection")?.unwrap_or(default.projection),
+            ortho_scale: value.field("ortho_scale")?.unwrap_or(default.ortho_scale),
+            aperture: value.field("aperture")?.unwrap_or(default.aperture),
+            focus_distance: value
+                .field("focus_distance")?
+                .unwrap_or(default.focus_distance),
+            shutter_open: value.field("shutter_open")?.unwrap_or(default.shutter_open),
+            shutter_close: value
+                .field("shutter_close")?
+                .unwrap_or(default.shutter_close),
+        })
+    }
+}
@@ -0,0 +1,744 @@
+//! This module defines a struct representing a Scene to be ray traced
+
+use rand::Rng;
+
+use super::accel::bvh::{count_hit, Bvh};
+use super::background::Background;
+use super::camera::Camera;
+use super::color::Color;
+use super::fog::Fog;
+use super::light::{AmbientLight, Light};
+use super::material::schlick;
+use super::object::{HitRecord, Object};
+use super::ray::Ray;
+use super::vector3d::Vector3;
+
+/// The struct representing a Scene
+///
+/// Contains all of the objects to be rendered, the lights illuminating
+/// them, the ambient light reaching every surface, the background seen
+/// where no object is hit, the fog hiding distant objects, if any, and the
+/// camera to view it from, if the scene was loaded from a file that
+/// describes one
+///
+/// Objects are either tested one by one, or moved into a bounding volume
+/// hierarchy by `build_bvh`, which is much faster for large scenes.
+pub struct Scene {
+    pub objects: Vec<Box<dyn Object>>,
+    pub bvh: Bvh,
+    pub lights: Vec<Box<dyn Light>>,
+    pub ambient: AmbientLight,
+    pub background: Background,
+    pub fog: Option<Fog>,
+    pub camera: Option<Camera>,
+}
+
+impl Scene {
+    /// Creates a new, empty scene with the given flat background color and no ambient light
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let background = ray_tracer::color::Color::new(0.0, 0.0, 1.0);
+    /// let scene = ray_tracer::scene::Scene::new(background.clone());
+    ///
+    /// assert!(scene.objects.is_empty());
+    /// assert_eq!(scene.background, ray_tracer::background::Background::Flat(background));
+    /// ```
+    pub fn new(background: Color) -> Scene {
+        Scene {
+            objects: vec![],
+            bvh: Bvh::default(),
+            lights: vec![],
+            ambient: AmbientLight::default(),
+            background: Background::Flat(background),
+            fog: None,
+            camera: None,
+        }
+    }
+
+    /// Adds the given object to this scene
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut scene = ray_tracer::scene::Scene::default();
+    /// let sphere = ray_tracer::object::sphere::Sphere::default();
+    /// scene.add(Box::new(sphere));
+    ///
+    /// assert_eq!(scene.objects.len(), 1);
+    /// ```
+    pub fn add(&mut self, obj: Box<dyn Object>) {
+        self.objects.push(obj);
+    }
+
+    /// Moves all of the objects in this scene into a bounding volume hierarchy, so that rays
+    /// only need to be tested against the objects near them
+    ///
+    /// This should be called again after adding more objects, which are otherwise tested one
+    /// by one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut scene = ray_tracer::scene::Scene::default();
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// )));
+    /// scene.build_bvh();
+    /// assert!(scene.objects.is_empty());
+    /// assert_eq!(scene.bvh.objects().len(), 1);
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert_eq!(scene.closest_hit(&ray).unwrap().t, 4.0);
+    /// ```
+    pub fn build_bvh(&mut self) {
+        let mut objects = std::mem::take(&mut self.bvh).into_objects();
+        objects.append(&mut self.objects);
+        self.bvh = Bvh::new(objects);
+    }
+
+    /// Adds the given light to this scene
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut scene = ray_tracer::scene::Scene::default();
+    /// let light = ray_tracer::light::PointLight::default();
+    /// scene.add_light(Box::new(light));
+    ///
+    /// assert_eq!(scene.lights.len(), 1);
+    /// ```
+    pub fn add_light(&mut self, light: Box<dyn Light>) {
+        self.lights.push(light);
+    }
+
+    /// Finds the nearest intersection of the given ray with the objects in this scene.
+    ///
+    /// Only intersections within the ray's `t_min` and `t_max` bounds count, so objects behind
+    /// the ray are never hit. If two objects are hit at exactly the same _t_, the hit found
+    /// first is kept: objects in the bounding volume hierarchy come before the others, which
+    /// are tested in the order they were added.
+    ///
+    /// If the ray does not hit any object, then "None" is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut scene = ray_tracer::scene::Scene::default();
+    /// let far_sphere = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -10.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let near_sphere = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// scene.add(Box::new(far_sphere));
+    /// scene.add(Box::new(near_sphere));
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let hit = scene.closest_hit(&ray).unwrap();
+    /// assert_eq!(hit.t, 4.0);
+    /// assert_eq!(hit.point.z, -4.0);
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// assert!(scene.closest_hit(&ray).is_none());
+    ///
+    /// // Of two overlapping spheres, the one whose surface is nearer is hit
+    /// let mut scene = ray_tracer::scene::Scene::default();
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// )));
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.5),
+    ///     2.0,
+    ///     ray_tracer::material::Material {
+    ///         color: ray_tracer::color::Color::new(1.0, 0.0, 0.0),
+    ///         ..Default::default()
+    ///     },
+    /// )));
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let hit = scene.closest_hit(&ray).unwrap();
+    /// assert_eq!(hit.t, 3.5);
+    /// assert_eq!(hit.material.color.r, 1.0);
+    ///
+    /// // Of two spheres in the same place, the one added first is hit
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.5),
+    ///     2.0,
+    ///     ray_tracer::material::Material {
+    ///         color: ray_tracer::color::Color::new(0.0, 0.0, 1.0),
+    ///         ..Default::default()
+    ///     },
+    /// )));
+    /// let hit = scene.closest_hit(&ray).unwrap();
+    /// assert_eq!(hit.t, 3.5);
+    /// assert_eq!(hit.material.color.r, 1.0);
+    ///
+    /// // Objects behind the ray are not hit
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// assert!(scene.closest_hit(&ray).is_none());
+    /// ```
+    pub fn closest_hit(&self, ray: &Ray) -> Option<HitRecord<'_>> {
+        self.nearest_hit(ray, self.objects.iter().map(|obj| obj.as_ref()), &mut 0)
+    }
+
+    /// Returns the nearest intersection of the given ray with the objects in this scene's
+    /// bounding volume hierarchy and the given objects, instead of this scene's other objects.
+    ///
+    /// This is for rays that can only hit some of the objects, like the primary rays of a
+    /// camera, which can skip objects outside of its frustum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut scene = ray_tracer::scene::Scene::default();
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// )));
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let everything: Vec<_> = scene.objects.iter().map(|obj| obj.as_ref()).collect();
+    /// assert_eq!(scene.closest_hit_among(&ray, &everything).unwrap().t, 4.0);
+    /// assert!(scene.closest_hit_among(&ray, &[]).is_none());
+    /// ```
+    pub fn closest_hit_among<'a>(
+        &'a self,
+        ray: &Ray,
+        objects: &[&'a dyn Object],
+    ) -> Option<HitRecord<'a>> {
+        self.nearest_hit(ray, objects.iter().copied(), &mut 0)
+    }
+
+    /// Returns the nearest intersection of the given ray like `closest_hit_among`, adding the
+    /// number of objects whose intersection with the ray was tested to `tests`.
+    ///
+    /// This measures how much work finding a hit took, to profile the bounding volume hierarchy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut scene = ray_tracer::scene::Scene::default();
+    /// for i in 0..3 {
+    ///     scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///         ray_tracer::vector3d::Vector3::new(i as f64 * 3.0, 0.0, -5.0),
+    ///         1.0,
+    ///         ray_tracer::material::Material::default(),
+    ///     )));
+    /// }
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// // Without a bounding volume hierarchy, every object is tested
+    /// let everything: Vec<_> = scene.objects.iter().map(|obj| obj.as_ref()).collect();
+    /// let mut tests = 0;
+    /// assert_eq!(scene.closest_hit_counted(&ray, &everything, &mut tests).unwrap().t, 4.0);
+    /// assert_eq!(tests, 3);
+    ///
+    /// // With one, only the objects in boxes along the ray are
+    /// scene.build_bvh();
+    /// let mut tests = 0;
+    /// assert_eq!(scene.closest_hit_counted(&ray, &[], &mut tests).unwrap().t, 4.0);
+    /// assert_eq!(tests, 1);
+    /// ```
+    pub fn closest_hit_counted<'a>(
+        &'a self,
+        ray: &Ray,
+        objects: &[&'a dyn Object],
+        tests: &mut u32,
+    ) -> Option<HitRecord<'a>> {
+        self.nearest_hit(ray, objects.iter().copied(), tests)
+    }
+
+    /// Returns the nearest intersection of the given ray with the objects in this scene's
+    /// bounding volume hierarchy and the given objects, counting each object tested in `tests`
+    fn nearest_hit<'a>(
+        &'a self,
+        ray: &Ray,
+        objects: impl Iterator<Item = &'a dyn Object>,
+        tests: &mut u32,
+    ) -> Option<HitRecord<'a>> {
+        let mut nearest = self.bvh.hit_counted(ray, tests);
+        for obj in objects {
+            if let Some(hit) = count_hit(obj, ray, tests) {
+                match nearest {
+                    Some(ref nearest_hit) if nearest_hit.t <= hit.t => {}
+                    _ => nearest = Some(hit),
+                }
+            }
+        }
+        nearest
+    }
+
+    /// Returns whether the given ray intersects any object in this scene.
+    ///
+    /// This can stop at the first intersection found, so it is cheaper than `closest_hit` when the
+    /// nearest hit is not needed.
+    pub fn any_hit(&self, ray: &Ray) -> bool {
+        self.bvh.any_hit(ray)
+            || self
+                .objects
+                .iter()
+                .any(|obj| obj.ray_intersection(ray).is_some())
+    }
+
+    /// Returns whether the given light is blocked by an object before it reaches the given hit.
+    ///
+    /// The shadow ray starts slightly above the surface along the normal, so that the surface
+    /// does not shadow itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut scene = ray_tracer::scene::Scene::default();
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 2.0, 0.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// )));
+    /// scene.add(Box::new(ray_tracer::object::plane::Plane::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0),
+    ///     ray_tracer::material::Material::default(),
+    /// )));
+    /// scene.add_light(Box::new(ray_tracer::light::PointLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 10.0, 0.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     1.0,
+    /// )));
+    /// let down = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    ///
+    /// let below_sphere = ray_tracer::vector3d::Vector3::new(0.0, 0.5, 0.0);
+    /// let shadowed_ray = ray_tracer::ray::Ray::new(below_sphere, down.clone());
+    /// let hit = scene.closest_hit(&shadowed_ray).unwrap();
+    /// assert!(scene.in_shadow(&hit, scene.lights[0].as_ref()));
+    ///
+    /// let beside_sphere = ray_tracer::vector3d::Vector3::new(5.0, 0.5, 0.0);
+    /// let lit_ray = ray_tracer::ray::Ray::new(beside_sphere, down);
+    /// let hit = scene.closest_hit(&lit_ray).unwrap();
+    /// assert!(!scene.in_shadow(&hit, scene.lights[0].as_ref()));
+    ///
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(0);
+    /// assert!(scene.shade(&shadowed_ray, 0, &mut rng).r < scene.shade(&lit_ray, 0, &mut rng).r);
+    /// ```
+    pub fn in_shadow(&self, hit: &HitRecord, light: &dyn Light) -> bool {
+        let mut shadow_ray = hit.secondary_ray(light.direction_from(&hit.point));
+        shadow_ray.t_max = light.distance_from(&shadow_ray.pos);
+        self.any_hit(&shadow_ray)
+    }
+
+    /// Returns the fraction of the given light that reaches the given hit without being blocked
+    /// by an object, from 0.0 in full shadow to 1.0 when fully lit.
+    ///
+    /// Lights with an area trace a shadow ray towards each of their sample points, so a hit in
+    /// their penumbra is partly lit. Other lights are either fully blocked or not, as decided by
+    /// `in_shadow`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut scene = ray_tracer::scene::Scene::default();
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 2.5, 0.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// )));
+    /// scene.add(Box::new(ray_tracer::object::plane::Plane::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0),
+    ///     ray_tracer::material::Material::default(),
+    /// )));
+    /// // A 2x2 light above the sphere, sampled 4x4 times
+    /// scene.add_light(Box::new(ray_tracer::light::AreaLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(-1.0, 5.0, -1.0),
+    ///     ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 2.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     10.0,
+    ///     4,
+    /// )));
+    /// let light = scene.lights[0].as_ref();
+    /// let down = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let hit_below = |x: f64| {
+    ///     let ray_position = ray_tracer::vector3d::Vector3::new(x, 0.5, 0.0);
+    ///     let ray = ray_tracer::ray::Ray::new(ray_position, down.clone());
+    ///     scene.light_visibility(&scene.closest_hit(&ray).unwrap(), light)
+    /// };
+    ///
+    /// // Right under the sphere, it hides the whole light
+    /// assert_eq!(hit_below(0.0), 0.0);
+    /// // At the edge of the shadow, only part of the light is hidden
+    /// let penumbra = hit_below(2.0);
+    /// assert!(penumbra > 0.0 && penumbra < 1.0);
+    /// // Far from the sphere, none of the light is hidden
+    /// assert_eq!(hit_below(10.0), 1.0);
+    /// ```
+    pub fn light_visibility(&self, hit: &HitRecord, light: &dyn Light) -> f64 {
+        match light.sample_points() {
+            Some(points) if !points.is_empty() => {
+                let unblocked = points
+                    .iter()
+                    .filter(|point| {
+                        let mut shadow_ray = hit.secondary_ray(point.sub(&hit.point).into_unit());
+                        shadow_ray.t_max = shadow_ray.pos.distance(point);
+                        !self.any_hit(&shadow_ray)
+                    })
+                    .count();
+                unblocked as f64 / points.len() as f64
+            }
+            _ => {
+                if self.in_shadow(hit, light) {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+        }
+    }
+
+    /// Calculates the color seen along the given ray.
+    ///
+    /// The color is not clamped, so bright lighting may push its components above 1.0.
+    ///
+    /// The color of the nearest hit object is calculated using the Phong reflection model: the
+    /// ambient light, plus the diffuse and specular light from each of the lights in the scene
+    /// that is not blocked by another object. If nothing is hit, the background is returned.
+    ///
+    /// Reflective surfaces blend in the color seen along the reflected ray, by their
+    /// reflectivity. Transparent surfaces then blend in the color seen through them, by their
+    /// transparency. That color mixes the colors seen along the refracted and reflected rays,
+    /// weighted by the Fresnel reflectance, or is only the reflected color if the light is
+    /// totally internally reflected. `depth` is the number of times a ray may be reflected or
+    /// refracted, which bounds the recursion between facing mirrors.
+    ///
+    /// Then the light emitted by the surface is added, regardless of the light reaching it.
+    /// Finally, surfaces that fade with distance are blended into the background.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut scene = ray_tracer::scene::Scene::default();
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material {
+    ///         color: ray_tracer::color::Color::new(1.0, 0.0, 0.0),
+    ///         ..Default::default()
+    ///     },
+    /// );
+    /// scene.add(Box::new(sphere));
+    /// scene.add_light(Box::new(ray_tracer::light::PointLight::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     8.0,
+    /// )));
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(0);
+    ///
+    /// let color = scene.shade(&ray, 5, &mut rng);
+    /// assert_eq!(color, ray_tracer::color::Color::new(0.5, 0.0, 0.0));
+    ///
+    /// // A sphere in between blocks the light, leaving only the ambient light
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -2.0),
+    ///     0.5,
+    ///     ray_tracer::material::Material::default(),
+    /// )));
+    /// scene.ambient = ray_tracer::light::AmbientLight::new(
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     0.1,
+    /// );
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -3.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let color = scene.shade(&ray, 5, &mut rng);
+    /// assert_eq!(color, ray_tracer::color::Color::new(0.1, 0.0, 0.0));
+    ///
+    /// // A mirror below a sphere shows the sphere's color
+    /// let mut scene = ray_tracer::scene::Scene::default();
+    /// scene.ambient = ray_tracer::light::AmbientLight::new(
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     1.0,
+    /// );
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 2.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material {
+    ///         color: ray_tracer::color::Color::new(1.0, 0.0, 0.0),
+    ///         ..Default::default()
+    ///     },
+    /// )));
+    /// scene.add(Box::new(ray_tracer::object::plane::Plane::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0),
+    ///     ray_tracer::material::Material {
+    ///         reflectivity: 1.0,
+    ///         ..Default::default()
+    ///     },
+    /// )));
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 2.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, -4.0, -5.0).into_unit();
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let color = scene.shade(&ray, 5, &mut rng);
+    /// let red = ray_tracer::color::Color::new(1.0, 0.0, 0.0);
+    /// assert!(color.approx_eq(&red, 1e-10));
+    ///
+    /// // Without any reflections left, the mirror shows its own color
+    /// let color = scene.shade(&ray, 0, &mut rng);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// assert!(color.approx_eq(&white, 1e-10));
+    ///
+    /// // A glass ball bends a ray that would have hit a sphere behind it
+    /// let mut scene = ray_tracer::scene::Scene::new(ray_tracer::color::Color::new(0.0, 0.0, 1.0));
+    /// scene.ambient = ray_tracer::light::AmbientLight::new(
+    ///     ray_tracer::color::Color::new(1.0, 1.0, 1.0),
+    ///     1.0,
+    /// );
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.5, -10.0),
+    ///     0.3,
+    ///     ray_tracer::material::Material {
+    ///         color: ray_tracer::color::Color::new(1.0, 0.0, 0.0),
+    ///         ..Default::default()
+    ///     },
+    /// )));
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.5, 5.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// let red = ray_tracer::color::Color::new(1.0, 0.0, 0.0);
+    /// assert!(scene.shade(&ray, 5, &mut rng).approx_eq(&red, 1e-10));
+    ///
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material {
+    ///         transparency: 1.0,
+    ///         index_of_refraction: 1.5,
+    ///         ..Default::default()
+    ///     },
+    /// )));
+    /// let blue = ray_tracer::color::Color::new(0.0, 0.0, 1.0);
+    /// assert!(scene.shade(&ray, 5, &mut rng).approx_eq(&blue, 1e-3));
+    ///
+    /// // A glowing sphere shows its emission color, even with no lights in the scene
+    /// let mut scene = ray_tracer::scene::Scene::default();
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material {
+    ///         emission: ray_tracer::color::Color::new(1.0, 0.5, 0.0),
+    ///         ..Default::default()
+    ///     },
+    /// )));
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert_eq!(scene.shade(&ray, 5, &mut rng), ray_tracer::color::Color::new(1.0, 0.5, 0.0));
+    /// assert_eq!(scene.shade(&ray, 0, &mut rng), ray_tracer::color::Color::new(1.0, 0.5, 0.0));
+    ///
+    /// // A rough mirror blurs its reflection the same way for the same seed
+    /// let mut scene = ray_tracer::scene::Scene::new(ray_tracer::color::Color::new(0.0, 0.0, 1.0));
+    /// scene.background = ray_tracer::background::Background::sky();
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::metal(white, 0.5),
+    /// )));
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.1, 0.2, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    ///
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(3);
+    /// let color = scene.shade(&ray, 5, &mut rng);
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(3);
+    /// assert_eq!(scene.shade(&ray, 5, &mut rng), color);
+    /// ```
+    pub fn shade<R: Rng>(&self, ray: &Ray, depth: u32, rng: &mut R) -> Color {
+        self.shade_hit(ray, self.closest_hit(ray), depth, rng)
+    }
+
+    /// Calculates the color seen along the given ray like `shade`, given its nearest hit
+    /// in this scene, or "None" if it hits nothing.
+    ///
+    /// If the scene has fog, the color of a hit is blended towards the fog's color by the
+    /// distance to it. The background is left clear of fog.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut scene = ray_tracer::scene::Scene::new(ray_tracer::color::Color::new(0.0, 0.0, 1.0));
+    /// scene.add(Box::new(ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, -5.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material {
+    ///         emission: ray_tracer::color::Color::new(1.0, 0.5, 0.0),
+    ///         ..Default::default()
+    ///     },
+    /// )));
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 0.0, -1.0);
+    /// let ray = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(0);
+    ///
+    /// let hit = scene.closest_hit(&ray);
+    /// let color = scene.shade_hit(&ray, hit, 5, &mut rng);
+    /// assert_eq!(color, ray_tracer::color::Color::new(1.0, 0.5, 0.0));
+    /// let color = scene.shade_hit(&ray, None, 5, &mut rng);
+    /// assert_eq!(color, ray_tracer::color::Color::new(0.0, 0.0, 1.0));
+    ///
+    /// // Thick fog hides the sphere
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// scene.fog = Some(ray_tracer::fog::Fog::new(white.clone(), 10.0));
+    /// let hit = scene.closest_hit(&ray);
+    /// assert!(scene.shade_hit(&ray, hit, 5, &mut rng).approx_eq(&white, 1e-9));
+    /// ```
+    pub fn shade_hit<R: Rng>(
+        &self,
+        ray: &Ray,
+        hit: Option<HitRecord>,
+        depth: u32,
+        rng: &mut R,
+    ) -> Color {
+        match hit {
+            Some(hit) => {
+                let material = hit.material;
+                let dir = ray.dir.into_unit();
+                let mut color = self.phong(ray, &hit);
+                if depth > 0 && material.reflectivity > 0.0 {
+                    let reflected = self.reflection(&dir, &hit, depth, rng);
+                    color = color.lerp(&reflected, material.reflectivity);
+                }
+                if depth > 0 && material.transparency > 0.0 {
+                    let eta_ratio = if hit.front_face {
+                        1.0 / material.index_of_refraction
+                    } else {
+                        material.index_of_refraction
+                    };
+                    let reflected = self.reflection(&dir, &hit, depth, rng);
+                    let transmitted = match dir.refract(&hit.normal, eta_ratio) {
+                        Some(refracted_dir) => {
+                            let refracted =
+                                self.shade(&hit.secondary_ray(refracted_dir), depth - 1, rng);
+                            let cosine = -dir.dot(&hit.normal);
+                            let reflectance = schlick(cosine, material.index_of_refraction);
+                            refracted.lerp(&reflected, reflectance)
+                        }
+                        None => reflected,
+                    };
+                    color = color.lerp(&transmitted, material.transparency);
+                }
+                color = color.add(&material.emission);
+                if hit.background_fade > 0.0 {
+                    color = color.lerp(&self.background(ray), hit.background_fade);
+                }
+                if let Some(ref fog) = self.fog {
+                    color = fog.apply(&color, hit.t * ray.dir.length());
+                }
+                color
+            }
+            None => self.background(ray),
+        }
+    }
+
+    /// Returns the color of this scene's background seen along the given ray
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut scene = ray_tracer::scene::Scene::default();
+    /// scene.background = ray_tracer::background::Background::sky();
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// let up = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(0);
+    /// assert_eq!(scene.background(&up), ray_tracer::color::Color::new(0.5, 0.7, 1.0));
+    /// assert_eq!(scene.shade(&up, 5, &mut rng), ray_tracer::color::Color::new(0.5, 0.7, 1.0));
+    ///
+    /// let ray_position = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let ray_direction = ray_tracer::vector3d::Vector3::new(0.0, -1.0, 0.0);
+    /// let down = ray_tracer::ray::Ray::new(ray_position, ray_direction);
+    /// assert_eq!(scene.background(&down), ray_tracer::color::Color::new(1.0, 1.0, 1.0));
+    /// ```
+    pub fn background(&self, ray: &Ray) -> Color {
+        self.background.color_in_direction(&ray.dir)
+    }
+
+    /// Calculates the color seen by reflecting a ray with the given unit direction off the
+    /// surface at the given hit, blurred by the roughness of its material.
+    fn reflection<R: Rng>(&self, dir: &Vector3, hit: &HitRecord, depth: u32, rng: &mut R) -> Color {
+        let reflected_dir = hit.material.reflect(dir, &hit.normal, rng);
+        self.shade(&hit.secondary_ray(reflected_dir), depth - 1, rng)
+    }
+
+    /// Calculates the color of the given hit lit by the lights in this scene, using the Phong
+    /// reflection model, with the normal bent by the material's normal map.
+    fn phong(&self, ray: &Ray, hit: &HitRecord) -> Color {
+        let surface_color = hit.material.color_at(hit.u, hit.v, &hit.point);
+        let normal = hit
+            .material
+            .shading_normal(hit.u, hit.v, &hit.point, &hit.normal);
+        let ambient = self.ambient.reflected_by(&surface_color);
+        let view = ray.dir.negate().into_unit();
+        let direct: Color = self
+            .lights
+            .iter()
+            .map(|light| (light, self.light_visibility(hit, light.as_ref())))
+            .filter(|&(_, visibility)| visibility > 0.0)
+            .map(|(light, visibility)| {
+                let diffuse = light
+                    .color()
+                    .component_mul(&surface_color)
+                    .mul(light.diffuse(&hit.point, &normal));
+                let specular = light.color().mul(
+                    hit.material.specular
+                        * light.specular(&hit.point, &normal, &view, hit.material.shininess),
+                );
+                diffuse.add(&specular).mul(visibility)
+            })
+            .sum();
+        ambient.add(&direct)
+    }
+}
+
+impl Default for Scene {
+    fn default() -> Self {
+        Scene::new(Color::default())
+    }
+}
+
+pub mod hash;
+pub mod loader;
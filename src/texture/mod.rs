@@ -0,0 +1,526 @@
+//! This module defines textures that vary the color of a surface across it
+
+use sdl2::image::LoadSurface;
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::surface::Surface;
+
+use std::fmt::{self, Debug};
+use std::hash::Hasher;
+use std::path::Path;
+
+use super::color::Color;
+use super::scene::hash::SceneHasher;
+use super::vector3d::Vector3;
+
+use perlin::Perlin;
+
+/// How far points are nudged before finding their checker cell, so that surfaces lying exactly
+/// on a cell boundary do not flicker between two cells
+const CHECKER_EPSILON: f64 = 1e-6;
+
+pub trait Texture: Debug + Send + Sync {
+    /// Returns the color of this texture at the given texture coordinates _(u, v)_ of a
+    /// surface, or at the given point in space.
+    ///
+    /// Textures that are painted onto a surface use the texture coordinates, while textures
+    /// that fill space, like the checkerboard, use the point.
+    fn color_at(&self, u: f64, v: f64, point: &Vector3) -> Color;
+
+    /// Feeds every parameter of this texture that changes its colors to the given hasher, so
+    /// that `scene::hash::scene_hash` can tell when a scene has changed.
+    fn hash_into(&self, hasher: &mut SceneHasher);
+
+    /// Returns this texture as a checkerboard, if it is one, so that it can be saved to a scene
+    /// file. Other textures return "None", which is the default.
+    fn as_checker(&self) -> Option<&Checker> {
+        None
+    }
+}
+
+/// The struct representing a 3D checkerboard texture
+///
+/// Contains the two Colors that alternate between neighbouring unit cubes.
+#[derive(Debug, Default)]
+pub struct Checker {
+    pub even: Color,
+    pub odd: Color,
+}
+
+impl Checker {
+    /// Creates a new checkerboard alternating between the given colors
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let black = ray_tracer::color::Color::new(0.0, 0.0, 0.0);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let checker = ray_tracer::texture::Checker::new(black, white);
+    ///
+    /// assert_eq!(checker.odd.r, 1.0);
+    /// ```
+    pub fn new(even: Color, odd: Color) -> Checker {
+        Checker { even, odd }
+    }
+}
+
+impl Texture for Checker {
+    /// Returns the even color if _floor(x) + floor(y) + floor(z)_ is even, and the odd color
+    /// otherwise
+    ///
+    /// The texture coordinates are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::texture::Texture;
+    ///
+    /// let black = ray_tracer::color::Color::new(0.0, 0.0, 0.0);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let checker = ray_tracer::texture::Checker::new(black.clone(), white.clone());
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(0.5, 0.5, 0.5);
+    /// assert_eq!(checker.color_at(0.0, 0.0, &point), black);
+    ///
+    /// // Moving into a neighbouring cell along any axis switches color
+    /// let point = ray_tracer::vector3d::Vector3::new(1.5, 0.5, 0.5);
+    /// assert_eq!(checker.color_at(0.0, 0.0, &point), white);
+    /// let point = ray_tracer::vector3d::Vector3::new(0.5, -0.5, 0.5);
+    /// assert_eq!(checker.color_at(0.0, 0.0, &point), white);
+    /// let point = ray_tracer::vector3d::Vector3::new(1.5, 0.5, -0.5);
+    /// assert_eq!(checker.color_at(0.0, 0.0, &point), black);
+    /// ```
+    fn color_at(&self, _u: f64, _v: f64, point: &Vector3) -> Color {
+        let cell = (point.x + CHECKER_EPSILON).floor()
+            + (point.y + CHECKER_EPSILON).floor()
+            + (point.z + CHECKER_EPSILON).floor();
+        if cell.rem_euclid(2.0) == 0.0 {
+            self.even.clone()
+        } else {
+            self.odd.clone()
+        }
+    }
+
+    fn as_checker(&self) -> Option<&Checker> {
+        Some(self)
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"Checker");
+        hasher.write_color(&self.even);
+        hasher.write_color(&self.odd);
+    }
+}
+
+/// The ways of finding the color of an image between the centers of its pixels
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SamplingMode {
+    /// The color of the pixel containing the point, which looks blocky when magnified
+    Nearest,
+    /// A blend of the four pixels with the nearest centers, which looks smooth when magnified
+    #[default]
+    Bilinear,
+}
+
+/// The struct representing a texture sampled from an image
+///
+/// Contains the width and height of the image in pixels, the Color of each
+/// pixel, row by row from the top left, how colors between the pixels are
+/// sampled, and a digest of the size and pixels.
+///
+/// The digest is computed once, when the texture is created, since images are
+/// too large to hash every time a scene is hashed. Pixels changed afterwards
+/// are not seen by `scene::hash::scene_hash`.
+#[derive(Debug, PartialEq)]
+pub struct ImageTexture {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Color>,
+    pub sampling: SamplingMode,
+    digest: u64,
+}
+
+impl Default for ImageTexture {
+    fn default() -> Self {
+        ImageTexture::new(0, 0, vec![])
+    }
+}
+
+impl ImageTexture {
+    /// Creates a new image texture with the given size and pixels, sampled bilinearly
+    ///
+    /// The pixels are given row by row, starting from the top left of the image.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of pixels is not the width times the height.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let pixels = vec![ray_tracer::color::Color::new(1.0, 0.0, 0.0); 6];
+    /// let texture = ray_tracer::texture::ImageTexture::new(3, 2, pixels);
+    ///
+    /// assert_eq!(texture.width, 3);
+    /// assert_eq!(texture.height, 2);
+    /// assert_eq!(texture.sampling, ray_tracer::texture::SamplingMode::Bilinear);
+    /// ```
+    pub fn new(width: usize, height: usize, pixels: Vec<Color>) -> ImageTexture {
+        assert_eq!(
+            pixels.len(),
+            width * height,
+            "image texture needs one pixel for each position"
+        );
+        let mut hasher = SceneHasher::new();
+        hasher.write_u64(width as u64);
+        hasher.write_u64(height as u64);
+        for pixel in &pixels {
+            hasher.write_color(pixel);
+        }
+        ImageTexture {
+            width,
+            height,
+            pixels,
+            sampling: SamplingMode::default(),
+            digest: hasher.finish(),
+        }
+    }
+
+    /// Sets how colors between the pixels of this texture are sampled
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let pixels = vec![ray_tracer::color::Color::new(1.0, 0.0, 0.0); 4];
+    /// let texture = ray_tracer::texture::ImageTexture::new(2, 2, pixels)
+    ///     .with_sampling(ray_tracer::texture::SamplingMode::Nearest);
+    ///
+    /// assert_eq!(texture.sampling, ray_tracer::texture::SamplingMode::Nearest);
+    /// ```
+    pub fn with_sampling(mut self, sampling: SamplingMode) -> ImageTexture {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Loads an image texture from the image file at the given path
+    ///
+    /// Any format supported by SDL_image can be loaded, such as PNG, JPEG, or BMP.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// let texture = ray_tracer::texture::ImageTexture::from_file("earth.png").unwrap();
+    ///
+    /// println!("Loaded a {}x{} texture", texture.width, texture.height);
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<ImageTexture, String> {
+        let loaded = Surface::from_file(path)?;
+        let (width, height) = (loaded.width(), loaded.height());
+
+        // Convert the image to 8-bit RGB, so that its bytes are laid out the same way
+        // whatever the format of the file was
+        let mut surface = Surface::new(width, height, PixelFormatEnum::RGB24)?;
+        loaded.blit(None, &mut surface, None)?;
+
+        let pitch = surface.pitch() as usize;
+        let (width, height) = (width as usize, height as usize);
+        let pixels = surface.with_lock(|bytes| {
+            (0..height)
+                .flat_map(|y| (0..width).map(move |x| y * pitch + x * 3))
+                .map(|i| {
+                    Color::new(
+                        f64::from(bytes[i]) / 255.0,
+                        f64::from(bytes[i + 1]) / 255.0,
+                        f64::from(bytes[i + 2]) / 255.0,
+                    )
+                })
+                .collect()
+        });
+        Ok(ImageTexture::new(width, height, pixels))
+    }
+
+    /// Returns the color of the pixel at the given column and row
+    fn pixel(&self, x: usize, y: usize) -> &Color {
+        &self.pixels[y * self.width + x]
+    }
+}
+
+impl Texture for ImageTexture {
+    /// Returns the color of the image at the given texture coordinates, from the pixel
+    /// containing them or blending the four nearest pixels, depending on the sampling mode
+    ///
+    /// _u_ runs from the left of the image to the right, wrapping around at the edges, and _v_
+    /// runs from the bottom of the image to the top. The point is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::object::Object;
+    /// use ray_tracer::texture::Texture;
+    ///
+    /// let red = ray_tracer::color::Color::new(1.0, 0.0, 0.0);
+    /// let green = ray_tracer::color::Color::new(0.0, 1.0, 0.0);
+    /// let blue = ray_tracer::color::Color::new(0.0, 0.0, 1.0);
+    /// let white = ray_tracer::color::Color::new(1.0, 1.0, 1.0);
+    /// let pixels = vec![red.clone(), green, blue, white];
+    /// let texture = ray_tracer::texture::ImageTexture::new(2, 2, pixels);
+    ///
+    /// // A point on the upper front of a sphere maps to the center of the top left pixel
+    /// let sphere = ray_tracer::object::sphere::Sphere::new(
+    ///     ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0),
+    ///     1.0,
+    ///     ray_tracer::material::Material::default(),
+    /// );
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, 0.5f64.sqrt(), 0.5f64.sqrt());
+    /// let (u, v) = sphere.uv_at(&point);
+    /// assert!(texture.color_at(u, v, &point).approx_eq(&red, 1e-10));
+    ///
+    /// // Halfway between two pixels, their colors are blended
+    /// let yellow = ray_tracer::color::Color::new(0.5, 0.5, 0.0);
+    /// assert!(texture.color_at(0.5, 0.75, &point).approx_eq(&yellow, 1e-10));
+    ///
+    /// // Past the right edge, the image wraps around to the left
+    /// assert!(texture.color_at(1.25, 0.75, &point).approx_eq(&red, 1e-10));
+    ///
+    /// // At the center of the bottom left pixel, both modes give its color
+    /// let blue = ray_tracer::color::Color::new(0.0, 0.0, 1.0);
+    /// let nearest = ray_tracer::texture::ImageTexture::new(2, 2, texture.pixels.clone())
+    ///     .with_sampling(ray_tracer::texture::SamplingMode::Nearest);
+    /// assert_eq!(nearest.color_at(0.25, 0.25, &point), blue);
+    /// assert!(texture.color_at(0.25, 0.25, &point).approx_eq(&blue, 1e-10));
+    ///
+    /// // Off the center, nearest sampling keeps that pixel's color, while bilinear sampling
+    /// // blends in its neighbours
+    /// assert_eq!(nearest.color_at(0.4, 0.4, &point), blue);
+    /// let blended = texture.color_at(0.4, 0.4, &point);
+    /// assert!(blended.b > 0.0 && blended.b < 1.0);
+    /// assert!(blended.r > 0.0 && blended.g > 0.0);
+    /// ```
+    fn color_at(&self, u: f64, v: f64, _point: &Vector3) -> Color {
+        if self.pixels.is_empty() {
+            return Color::default();
+        }
+
+        let wrap = |x: f64| (x as i64).rem_euclid(self.width as i64) as usize;
+        let clamp = |y: f64| y.max(0.0).min((self.height - 1) as f64) as usize;
+        if self.sampling == SamplingMode::Nearest {
+            let x = (u * self.width as f64).floor();
+            let y = ((1.0 - v) * self.height as f64).floor();
+            return self.pixel(wrap(x), clamp(y)).clone();
+        }
+
+        // Find the pixel coordinates, with pixel centers at whole numbers
+        let x = u * self.width as f64 - 0.5;
+        let y = (1.0 - v) * self.height as f64 - 0.5;
+        let (fx, fy) = (x - x.floor(), y - y.floor());
+
+        let (x0, x1) = (wrap(x.floor()), wrap(x.floor() + 1.0));
+        let (y0, y1) = (clamp(y.floor()), clamp(y.floor() + 1.0));
+
+        let top = self.pixel(x0, y0).lerp(self.pixel(x1, y0), fx);
+        let bottom = self.pixel(x0, y1).lerp(self.pixel(x1, y1), fx);
+        top.lerp(&bottom, fy)
+    }
+
+    /// Feeds the digest of this texture's size and pixels, and its sampling mode, to the given
+    /// hasher
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::texture::Texture;
+    /// use std::hash::Hasher;
+    ///
+    /// let hash = |texture: &ray_tracer::texture::ImageTexture| {
+    ///     let mut hasher = ray_tracer::scene::hash::SceneHasher::new();
+    ///     texture.hash_into(&mut hasher);
+    ///     hasher.finish()
+    /// };
+    ///
+    /// // Separately loaded copies of the same image hash the same
+    /// let red = ray_tracer::color::Color::new(1.0, 0.0, 0.0);
+    /// let image = || ray_tracer::texture::ImageTexture::new(2, 2, vec![red.clone(); 4]);
+    /// assert_eq!(hash(&image()), hash(&image()));
+    ///
+    /// let mut pixels = vec![red.clone(); 4];
+    /// pixels[3] = ray_tracer::color::Color::new(0.0, 0.0, 1.0);
+    /// let changed = ray_tracer::texture::ImageTexture::new(2, 2, pixels);
+    /// assert_ne!(hash(&image()), hash(&changed));
+    ///
+    /// let nearest = image().with_sampling(ray_tracer::texture::SamplingMode::Nearest);
+    /// assert_ne!(hash(&image()), hash(&nearest));
+    /// ```
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"ImageTexture");
+        hasher.write_u64(self.digest);
+        hasher.write_u8(match self.sampling {
+            SamplingMode::Nearest => 0,
+            SamplingMode::Bilinear => 1,
+        });
+    }
+}
+
+/// The struct representing a texture of Perlin noise
+///
+/// Contains the Perlin noise generator, the frequency the noise is sampled at,
+/// and the number of octaves of turbulence. Higher frequencies make smaller
+/// features, and more octaves add finer detail on top of them.
+#[derive(Debug)]
+pub struct NoiseTexture {
+    pub perlin: Perlin,
+    pub frequency: f64,
+    pub octaves: u32,
+}
+
+impl NoiseTexture {
+    /// Creates a new noise texture from the given generator, with the given frequency and
+    /// number of octaves
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(0);
+    /// let perlin = ray_tracer::texture::perlin::Perlin::new(&mut rng);
+    /// let texture = ray_tracer::texture::NoiseTexture::new(perlin, 4.0, 7);
+    ///
+    /// assert_eq!(texture.frequency, 4.0);
+    /// assert_eq!(texture.octaves, 7);
+    /// ```
+    pub fn new(perlin: Perlin, frequency: f64, octaves: u32) -> NoiseTexture {
+        NoiseTexture {
+            perlin,
+            frequency,
+            octaves,
+        }
+    }
+}
+
+impl Texture for NoiseTexture {
+    /// Returns a shade of grey given by the turbulence at the point scaled by the frequency
+    ///
+    /// The texture coordinates are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use ray_tracer::texture::Texture;
+    ///
+    /// let mut rng = rand::XorShiftRng::from_seed([1, 2, 3, 4]);
+    /// let perlin = ray_tracer::texture::perlin::Perlin::new(&mut rng);
+    /// let texture = ray_tracer::texture::NoiseTexture::new(perlin, 2.0, 3);
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(0.3, 0.6, 0.9);
+    /// let scaled = ray_tracer::vector3d::Vector3::new(0.6, 1.2, 1.8);
+    /// let color = texture.color_at(0.0, 0.0, &point);
+    /// assert_eq!(color.r, texture.perlin.turbulence(&scaled, 3).min(1.0));
+    /// assert_eq!(color.r, color.g);
+    /// assert_eq!(color.g, color.b);
+    /// ```
+    fn color_at(&self, _u: f64, _v: f64, point: &Vector3) -> Color {
+        let value = self
+            .perlin
+            .turbulence(&point.mul(self.frequency), self.octaves)
+            .min(1.0);
+        Color::new(value, value, value)
+    }
+
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"NoiseTexture");
+        self.perlin.hash_into(hasher);
+        hasher.write_f64(self.frequency);
+        hasher.write_u32(self.octaves);
+    }
+}
+
+/// The type of the functions that compute a UvTexture's color from texture coordinates
+pub type UvFunction = dyn Fn(f64, f64) -> Color + Send + Sync;
+
+/// The struct representing a texture computed from texture coordinates
+///
+/// Contains a key naming the texture, and the function giving the color at
+/// each _(u, v)_, such as stripes along lines of latitude or longitude.
+///
+/// Functions cannot be compared, so `scene::hash::scene_hash` tells textures
+/// apart by their keys instead. Textures with different functions, or the
+/// same function with different parameters, need different keys.
+pub struct UvTexture {
+    pub key: String,
+    pub function: Box<UvFunction>,
+}
+
+impl UvTexture {
+    /// Creates a new texture with the given key, colored by the given function of the texture
+    /// coordinates
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::texture::Texture;
+    ///
+    /// let texture = ray_tracer::texture::UvTexture::new("uv", |u, v| {
+    ///     ray_tracer::color::Color::new(u, v, 0.0)
+    /// });
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// assert_eq!(
+    ///     texture.color_at(0.25, 0.75, &point),
+    ///     ray_tracer::color::Color::new(0.25, 0.75, 0.0)
+    /// );
+    /// ```
+    pub fn new<F>(key: &str, function: F) -> UvTexture
+    where
+        F: Fn(f64, f64) -> Color + Send + Sync + 'static,
+    {
+        UvTexture {
+            key: key.to_string(),
+            function: Box::new(function),
+        }
+    }
+}
+
+impl Debug for UvTexture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("UvTexture")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Texture for UvTexture {
+    /// Returns the color the function gives at the texture coordinates
+    ///
+    /// The point is ignored.
+    fn color_at(&self, u: f64, v: f64, _point: &Vector3) -> Color {
+        (self.function)(u, v)
+    }
+
+    /// Feeds this texture's key to the given hasher, since its function cannot be compared
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ray_tracer::texture::Texture;
+    /// use std::hash::Hasher;
+    ///
+    /// let hash = |texture: &ray_tracer::texture::UvTexture| {
+    ///     let mut hasher = ray_tracer::scene::hash::SceneHasher::new();
+    ///     texture.hash_into(&mut hasher);
+    ///     hasher.finish()
+    /// };
+    ///
+    /// let solid = |key: &str, color: ray_tracer::color::Color| {
+    ///     ray_tracer::texture::UvTexture::new(key, move |_, _| color.clone())
+    /// };
+    /// let red = ray_tracer::color::Color::new(1.0, 0.0, 0.0);
+    /// let blue = ray_tracer::color::Color::new(0.0, 0.0, 1.0);
+    /// assert_eq!(hash(&solid("red", red.clone())), hash(&solid("red", red.clone())));
+    /// assert_ne!(hash(&solid("red", red)), hash(&solid("blue", blue)));
+    /// ```
+    fn hash_into(&self, hasher: &mut SceneHasher) {
+        hasher.write(b"UvTexture");
+        hasher.write_u64(self.key.len() as u64);
+        hasher.write(self.key.as_bytes());
+    }
+}
+
+pub mod perlin;
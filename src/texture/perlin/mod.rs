@@ -0,0 +1,190 @@
+//! This module defines a Perlin noise generator
+
+use rand::Rng;
+
+use std::hash::Hasher;
+
+use crate::scene::hash::SceneHasher;
+use crate::vector3d::Vector3;
+
+/// The number of gradients in the lattice, after which the noise repeats
+const POINT_COUNT: usize = 256;
+
+/// The struct representing a Perlin noise generator
+///
+/// Contains a random unit gradient for each lattice point, and a random
+/// permutation for each axis used to pick the gradient of a lattice point.
+#[derive(Debug)]
+pub struct Perlin {
+    gradients: Vec<Vector3>,
+    perm_x: Vec<usize>,
+    perm_y: Vec<usize>,
+    perm_z: Vec<usize>,
+}
+
+/// Generates a random permutation of the lattice indices
+fn generate_perm<R: Rng>(rng: &mut R) -> Vec<usize> {
+    let mut perm: Vec<usize> = (0..POINT_COUNT).collect();
+    rng.shuffle(&mut perm);
+    perm
+}
+
+impl Perlin {
+    /// Creates a new Perlin noise generator using the given random number generator
+    ///
+    /// Generators created from identically seeded random number generators produce the same
+    /// noise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng1 = rand::XorShiftRng::from_seed([1, 2, 3, 4]);
+    /// let mut rng2 = rand::XorShiftRng::from_seed([1, 2, 3, 4]);
+    /// let perlin1 = ray_tracer::texture::perlin::Perlin::new(&mut rng1);
+    /// let perlin2 = ray_tracer::texture::perlin::Perlin::new(&mut rng2);
+    ///
+    /// let point = ray_tracer::vector3d::Vector3::new(1.3, -2.7, 0.4);
+    /// assert_eq!(perlin1.noise(&point), perlin2.noise(&point));
+    /// ```
+    pub fn new<R: Rng>(rng: &mut R) -> Perlin {
+        let gradients = (0..POINT_COUNT)
+            .map(|_| loop {
+                let v = Vector3::new(
+                    rng.gen_range(-1.0, 1.0),
+                    rng.gen_range(-1.0, 1.0),
+                    rng.gen_range(-1.0, 1.0),
+                );
+                if let Some(unit) = v.try_into_unit() {
+                    break unit;
+                }
+            })
+            .collect();
+        Perlin {
+            gradients,
+            perm_x: generate_perm(rng),
+            perm_y: generate_perm(rng),
+            perm_z: generate_perm(rng),
+        }
+    }
+
+    /// Returns the noise at the given point, from -1.0 to 1.0
+    ///
+    /// The noise varies smoothly between points, and is zero at every point with whole number
+    /// coordinates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(0);
+    /// let perlin = ray_tracer::texture::perlin::Perlin::new(&mut rng);
+    ///
+    /// let lattice_point = ray_tracer::vector3d::Vector3::new(3.0, -1.0, 7.0);
+    /// assert_eq!(perlin.noise(&lattice_point), 0.0);
+    ///
+    /// for i in 0..100 {
+    ///     let point = ray_tracer::vector3d::Vector3::new(i as f64 * 0.37, 1.5, -0.2);
+    ///     let noise = perlin.noise(&point);
+    ///     assert!(noise >= -1.0 && noise <= 1.0);
+    /// }
+    /// ```
+    pub fn noise(&self, point: &Vector3) -> f64 {
+        let (fx, fy, fz) = (point.x.floor(), point.y.floor(), point.z.floor());
+        let (u, v, w) = (point.x - fx, point.y - fy, point.z - fz);
+        let (i, j, k) = (fx as i64, fy as i64, fz as i64);
+
+        // Smooth the interpolation with a Hermite cubic, so the noise has no creases
+        let (uu, vv, ww) = (
+            u * u * (3.0 - 2.0 * u),
+            v * v * (3.0 - 2.0 * v),
+            w * w * (3.0 - 2.0 * w),
+        );
+
+        let mut accum = 0.0;
+        for di in 0..2 {
+            for dj in 0..2 {
+                for dk in 0..2 {
+                    let gradient = self.gradient(i + di, j + dj, k + dk);
+                    let (di, dj, dk) = (di as f64, dj as f64, dk as f64);
+                    let weight = Vector3::new(u - di, v - dj, w - dk);
+                    accum += (di * uu + (1.0 - di) * (1.0 - uu))
+                        * (dj * vv + (1.0 - dj) * (1.0 - vv))
+                        * (dk * ww + (1.0 - dk) * (1.0 - ww))
+                        * gradient.dot(&weight);
+                }
+            }
+        }
+        accum.clamp(-1.0, 1.0)
+    }
+
+    /// Returns the turbulence at the given point, from 0.0 to about 1.0
+    ///
+    /// Turbulence sums the given number of octaves of noise, each at twice the frequency and
+    /// half the strength of the one before it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = rand::XorShiftRng::from_seed([1, 2, 3, 4]);
+    /// let perlin = ray_tracer::texture::perlin::Perlin::new(&mut rng);
+    /// let point = ray_tracer::vector3d::Vector3::new(0.25, 0.5, 0.75);
+    ///
+    /// // A single octave is the magnitude of the noise
+    /// assert_eq!(perlin.turbulence(&point, 1), perlin.noise(&point).abs());
+    ///
+    /// let turbulence = perlin.turbulence(&point, 7);
+    /// assert!(turbulence >= 0.0 && turbulence < 2.0);
+    /// assert_eq!(turbulence, perlin.turbulence(&point, 7));
+    /// ```
+    pub fn turbulence(&self, point: &Vector3, octaves: u32) -> f64 {
+        let mut accum = 0.0;
+        let mut point = point.clone();
+        let mut weight = 1.0;
+        for _ in 0..octaves {
+            accum += weight * self.noise(&point);
+            weight *= 0.5;
+            point *= 2.0;
+        }
+        accum.abs()
+    }
+
+    /// Returns the gradient of the lattice point with the given integer coordinates
+    fn gradient(&self, i: i64, j: i64, k: i64) -> &Vector3 {
+        let mask = POINT_COUNT as i64 - 1;
+        let index = self.perm_x[(i & mask) as usize]
+            ^ self.perm_y[(j & mask) as usize]
+            ^ self.perm_z[(k & mask) as usize];
+        &self.gradients[index]
+    }
+
+    /// Feeds every gradient and permutation of this generator to the given hasher
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use std::hash::Hasher;
+    ///
+    /// let hash = |seed| {
+    ///     let mut rng = rand::XorShiftRng::from_seed([seed, 2, 3, 4]);
+    ///     let perlin = ray_tracer::texture::perlin::Perlin::new(&mut rng);
+    ///     let mut hasher = ray_tracer::scene::hash::SceneHasher::new();
+    ///     perlin.hash_into(&mut hasher);
+    ///     hasher.finish()
+    /// };
+    ///
+    /// assert_eq!(hash(1), hash(1));
+    /// assert_ne!(hash(1), hash(5));
+    /// ```
+    pub fn hash_into(&self, hasher: &mut SceneHasher) {
+        for gradient in &self.gradients {
+            hasher.write_vector(gradient);
+        }
+        for &index in self.perm_x.iter().chain(&self.perm_y).chain(&self.perm_z) {
+            hasher.write_u64(index as u64);
+        }
+    }
+}
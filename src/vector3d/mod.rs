@@ -1,7 +1,13 @@
 //! This module defines a struct representing a 3-Dimensional Vector
 
+use rand::Rng;
+
+use crate::math::{EPSILON, NEAR_ZERO};
+use std::iter::Sum;
+use std::ops::{AddAssign, MulAssign, SubAssign};
+
 /// The struct representing a 3-Dimensional Vector, with x, y, and z components
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Vector3 {
     pub x: f64,
     pub y: f64,
@@ -33,11 +39,29 @@ impl Vector3 {
     /// assert_eq!(my_vec.length(), (3.0f64).sqrt())
     /// ```
     pub fn length(&self) -> f64 {
-        self.dot(self).sqrt()
+        self.length_squared().sqrt()
+    }
+
+    /// Returns the squared length of the Vector3.
+    ///
+    /// This avoids the square root in `length`, which makes it cheaper for comparisons.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0);
+    /// assert_eq!(my_vec.length_squared(), 14.0);
+    /// assert!((my_vec.length_squared() - my_vec.length() * my_vec.length()).abs() < 1e-10);
+    /// ```
+    pub fn length_squared(&self) -> f64 {
+        self.dot(self)
     }
 
     /// Turns this vector into a unit vector.
     ///
+    /// A zero length vector has no direction, so normalizing it produces NaN components.
+    /// Use `try_into_unit` if the vector may be zero length.
+    ///
     /// # Example
     ///
     /// ```
@@ -54,6 +78,9 @@ impl Vector3 {
 
     /// Returns a unit vector pointing in the same direction as this vector.
     ///
+    /// A zero length vector has no direction, so the result will have NaN components.
+    /// Use `try_into_unit` if the vector may be zero length.
+    ///
     /// # Example
     ///
     /// ```
@@ -73,6 +100,46 @@ impl Vector3 {
         }
     }
 
+    /// Returns whether every component of this vector is so close to zero that it has no
+    /// meaningful direction
+    ///
+    /// Normalizing such a vector would divide by almost nothing, giving huge or NaN components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// assert!(ray_tracer::vector3d::Vector3::new(1e-9, 1e-9, 1e-9).near_zero());
+    /// assert!(ray_tracer::vector3d::Vector3::new(0.0, -1e-9, 0.0).near_zero());
+    /// assert!(!ray_tracer::vector3d::Vector3::new(0.1, 0.0, 0.0).near_zero());
+    /// ```
+    pub fn near_zero(&self) -> bool {
+        self.x.abs() < NEAR_ZERO && self.y.abs() < NEAR_ZERO && self.z.abs() < NEAR_ZERO
+    }
+
+    /// Returns a unit vector pointing in the same direction as this vector, if it has one.
+    ///
+    /// If the length of this vector is (nearly) zero, "None" is returned instead of a vector
+    /// with NaN components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec = ray_tracer::vector3d::Vector3::new(0.0, 3.0, 4.0);
+    /// let my_unit_vec = my_vec.try_into_unit().unwrap();
+    /// assert_eq!(my_unit_vec.y, 0.6);
+    /// assert_eq!(my_unit_vec.z, 0.8);
+    ///
+    /// let zero = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// assert!(zero.try_into_unit().is_none());
+    /// ```
+    pub fn try_into_unit(&self) -> Option<Vector3> {
+        if self.length() < EPSILON {
+            None
+        } else {
+            Some(self.into_unit())
+        }
+    }
+
     /// Returns the result of adding this vector and the given vector as a new vector.
     ///
     /// # Example
@@ -155,6 +222,27 @@ impl Vector3 {
         self.x * other.x + self.y * other.y + self.z * other.z
     }
 
+    /// Returns the cross product of this vector and the given vector
+    ///
+    /// The cross product is perpendicular to both vectors, and follows the right hand rule.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec1 = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let my_vec2 = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    ///
+    /// let my_vec3 = my_vec1.cross(&my_vec2);
+    /// assert_eq!(my_vec3, ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0));
+    /// ```
+    pub fn cross(&self, other: &Vector3) -> Vector3 {
+        Vector3 {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
     /// Reverses the direction of this vector
     ///
     /// # Example
@@ -173,4 +261,540 @@ impl Vector3 {
             z: -self.z,
         }
     }
+
+    /// Reflects this vector off a surface with the given normal.
+    ///
+    /// The reflection is calculated as _v - 2(v·n)n_, where _v_ is this vector and _n_ is the
+    /// normal. The normal is assumed to be a unit vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec = ray_tracer::vector3d::Vector3::new(1.0, -1.0, 0.0);
+    /// let normal = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    ///
+    /// let reflected = my_vec.reflect(&normal);
+    /// assert_eq!(reflected.x, 1.0);
+    /// assert_eq!(reflected.y, 1.0);
+    /// assert_eq!(reflected.z, 0.0);
+    /// ```
+    pub fn reflect(&self, normal: &Vector3) -> Vector3 {
+        self.sub(&normal.mul(2.0 * self.dot(normal)))
+    }
+
+    /// Refracts this vector through a surface with the given normal, using Snell's law.
+    ///
+    /// This vector is the incoming unit direction, and the normal is a unit vector pointing
+    /// against it. `eta_ratio` is the ratio of the indices of refraction, _n_in / n_out_.
+    ///
+    /// If the light is travelling into a less dense medium at a grazing enough angle, it
+    /// cannot leave the surface and undergoes total internal reflection. In that case "None"
+    /// is returned, otherwise "Some" unit vector with the refracted direction.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let incoming = ray_tracer::vector3d::Vector3::new(1.0, -1.0, 0.0).into_unit();
+    /// let normal = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    ///
+    /// // From air into glass, the ray bends towards the normal
+    /// let refracted = incoming.refract(&normal, 1.0 / 1.5).unwrap();
+    /// let sin_t = (std::f64::consts::PI / 4.0).sin() / 1.5;
+    /// assert!((refracted.x - sin_t).abs() < 1e-10);
+    /// assert!((refracted.y + (1.0 - sin_t * sin_t).sqrt()).abs() < 1e-10);
+    /// assert!((refracted.length() - 1.0).abs() < 1e-10);
+    ///
+    /// // From glass into air at the same angle, the ray is totally internally reflected
+    /// assert!(incoming.refract(&normal, 1.5).is_none());
+    /// ```
+    pub fn refract(&self, normal: &Vector3, eta_ratio: f64) -> Option<Vector3> {
+        let cos_i = -self.dot(normal);
+        let k = 1.0 - eta_ratio * eta_ratio * (1.0 - cos_i * cos_i);
+        if k < 0.0 {
+            None
+        } else {
+            Some(
+                self.mul(eta_ratio)
+                    .add(&normal.mul(eta_ratio * cos_i - k.sqrt())),
+            )
+        }
+    }
+
+    /// Returns the distance between the point represented by this vector and the given point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec1 = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let my_vec2 = ray_tracer::vector3d::Vector3::new(3.0, 4.0, 0.0);
+    ///
+    /// assert_eq!(my_vec1.distance(&my_vec2), 5.0);
+    /// ```
+    pub fn distance(&self, other: &Vector3) -> f64 {
+        self.distance_squared(other).sqrt()
+    }
+
+    /// Returns the squared distance between the point represented by this vector and the given
+    /// point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec1 = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let my_vec2 = ray_tracer::vector3d::Vector3::new(3.0, 4.0, 0.0);
+    ///
+    /// assert_eq!(my_vec1.distance_squared(&my_vec2), 25.0);
+    /// ```
+    pub fn distance_squared(&self, other: &Vector3) -> f64 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        let dz = self.z - other.z;
+        dx * dx + dy * dy + dz * dz
+    }
+
+    /// Linearly interpolates between this vector and the given vector.
+    ///
+    /// The result is _self * (1 - t) + other * t_, so a `t` of 0 gives this vector and a `t`
+    /// of 1 gives the other vector. Values of `t` outside of [0, 1] are not clamped, and
+    /// extrapolate beyond the two vectors instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec1 = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// let my_vec2 = ray_tracer::vector3d::Vector3::new(2.0, 2.0, 2.0);
+    ///
+    /// let midpoint = my_vec1.lerp(&my_vec2, 0.5);
+    /// assert_eq!(midpoint.x, 1.0);
+    /// assert_eq!(midpoint.y, 1.0);
+    /// assert_eq!(midpoint.z, 1.0);
+    /// ```
+    pub fn lerp(&self, other: &Vector3, t: f64) -> Vector3 {
+        self.mul(1.0 - t).add(&other.mul(t))
+    }
+
+    /// Returns whether each component of this vector is within `epsilon` of the given vector.
+    ///
+    /// This should be preferred over `==` when the vectors are the result of floating point
+    /// calculations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec1 = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0);
+    /// let my_vec2 = ray_tracer::vector3d::Vector3::new(1.0 + 1e-12, 2.0, 3.0);
+    /// let my_vec3 = ray_tracer::vector3d::Vector3::new(1.0, 2.0 + 1e-3, 3.0);
+    ///
+    /// assert_ne!(my_vec1, my_vec2);
+    /// assert!(my_vec1.approx_eq(&my_vec2, 1e-9));
+    /// assert!(!my_vec1.approx_eq(&my_vec3, 1e-9));
+    /// ```
+    pub fn approx_eq(&self, other: &Vector3, epsilon: f64) -> bool {
+        (self.x - other.x).abs() <= epsilon
+            && (self.y - other.y).abs() <= epsilon
+            && (self.z - other.z).abs() <= epsilon
+    }
+
+    /// Returns the angle between this vector and the given vector, in radians.
+    ///
+    /// The angle is in the range [0, PI], so parallel vectors give 0 and opposite vectors
+    /// give PI. The cosine is clamped to [-1, 1] so rounding errors cannot produce NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec1 = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let my_vec2 = ray_tracer::vector3d::Vector3::new(0.0, 2.0, 0.0);
+    ///
+    /// let angle = my_vec1.angle_between(&my_vec2);
+    /// assert!((angle - std::f64::consts::PI / 2.0).abs() < 1e-10);
+    /// assert_eq!(my_vec1.angle_between(&my_vec1.negate()), std::f64::consts::PI);
+    /// ```
+    pub fn angle_between(&self, other: &Vector3) -> f64 {
+        let cosine = self.dot(other) / (self.length() * other.length());
+        cosine.clamp(-1.0, 1.0).acos()
+    }
+
+    /// Returns the projection of this vector onto the given vector.
+    ///
+    /// This is the component of this vector that lies along the other vector. If the other
+    /// vector is zero length, the zero vector is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec1 = ray_tracer::vector3d::Vector3::new(2.0, 3.0, 0.0);
+    /// let my_vec2 = ray_tracer::vector3d::Vector3::new(4.0, 0.0, 0.0);
+    ///
+    /// let projection = my_vec1.project_onto(&my_vec2);
+    /// assert_eq!(projection, ray_tracer::vector3d::Vector3::new(2.0, 0.0, 0.0));
+    ///
+    /// let zero = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 0.0);
+    /// assert_eq!(my_vec1.project_onto(&zero), zero);
+    /// ```
+    pub fn project_onto(&self, other: &Vector3) -> Vector3 {
+        let other_length_squared = other.length_squared();
+        if other_length_squared < EPSILON * EPSILON {
+            Vector3::default()
+        } else {
+            other.mul(self.dot(other) / other_length_squared)
+        }
+    }
+
+    /// Returns the rejection of this vector from the given vector.
+    ///
+    /// This is the component of this vector that is perpendicular to the other vector, so
+    /// adding the projection and the rejection gives back this vector. If the other vector is
+    /// zero length, this vector is returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec1 = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0);
+    /// let my_vec2 = ray_tracer::vector3d::Vector3::new(1.0, 1.0, 0.0);
+    ///
+    /// let projection = my_vec1.project_onto(&my_vec2);
+    /// let rejection = my_vec1.reject_from(&my_vec2);
+    /// assert!(rejection.dot(&my_vec2).abs() < 1e-10);
+    /// assert!(projection.add(&rejection).approx_eq(&my_vec1, 1e-10));
+    /// ```
+    pub fn reject_from(&self, other: &Vector3) -> Vector3 {
+        self.sub(&self.project_onto(other))
+    }
+
+    /// Returns the components of this vector as an array, in x, y, z order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec = ray_tracer::vector3d::Vector3::from([1.0, 2.0, 3.0]);
+    ///
+    /// assert_eq!(my_vec.to_array(), [1.0, 2.0, 3.0]);
+    /// ```
+    pub fn to_array(&self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// Returns the component-wise product of this vector and the given vector.
+    ///
+    /// Unlike `mul`, which scales the vector by a scalar, this multiplies each component by
+    /// the matching component of the other vector. This is mostly useful for colors, such as
+    /// tinting a surface color by a light color.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec1 = ray_tracer::vector3d::Vector3::new(0.5, 1.0, 0.25);
+    /// let my_vec2 = ray_tracer::vector3d::Vector3::new(2.0, 2.0, 2.0);
+    ///
+    /// let product = my_vec1.component_mul(&my_vec2);
+    /// assert_eq!(product, ray_tracer::vector3d::Vector3::new(1.0, 2.0, 0.5));
+    /// ```
+    pub fn component_mul(&self, other: &Vector3) -> Vector3 {
+        Vector3 {
+            x: self.x * other.x,
+            y: self.y * other.y,
+            z: self.z * other.z,
+        }
+    }
+
+    /// Returns a copy of this vector with each component clamped to the range [min, max].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec = ray_tracer::vector3d::Vector3::new(-0.5, 0.5, 1.5);
+    ///
+    /// let clamped = my_vec.clamp(0.0, 1.0);
+    /// assert_eq!(clamped, ray_tracer::vector3d::Vector3::new(0.0, 0.5, 1.0));
+    /// ```
+    pub fn clamp(&self, min: f64, max: f64) -> Vector3 {
+        Vector3 {
+            x: self.x.clamp(min, max),
+            y: self.y.clamp(min, max),
+            z: self.z.clamp(min, max),
+        }
+    }
+
+    /// Returns the smallest of this vector's components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec = ray_tracer::vector3d::Vector3::new(2.0, -1.0, 3.0);
+    ///
+    /// assert_eq!(my_vec.min_component(), -1.0);
+    /// ```
+    pub fn min_component(&self) -> f64 {
+        self.x.min(self.y).min(self.z)
+    }
+
+    /// Returns the largest of this vector's components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec = ray_tracer::vector3d::Vector3::new(2.0, -1.0, 3.0);
+    ///
+    /// assert_eq!(my_vec.max_component(), 3.0);
+    /// ```
+    pub fn max_component(&self) -> f64 {
+        self.x.max(self.y).max(self.z)
+    }
+
+    /// Rotates this vector about the given axis by the given angle, in radians.
+    ///
+    /// This uses Rodrigues' rotation formula, and the rotation follows the right hand rule
+    /// around the axis. The axis must be a unit vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let my_vec = ray_tracer::vector3d::Vector3::new(1.0, 0.0, 0.0);
+    /// let axis = ray_tracer::vector3d::Vector3::new(0.0, 0.0, 1.0);
+    ///
+    /// let rotated = my_vec.rotate_around(&axis, std::f64::consts::PI / 2.0);
+    /// let expected = ray_tracer::vector3d::Vector3::new(0.0, 1.0, 0.0);
+    /// assert!(rotated.approx_eq(&expected, 1e-10));
+    /// ```
+    pub fn rotate_around(&self, axis: &Vector3, angle_radians: f64) -> Vector3 {
+        let (sin, cos) = angle_radians.sin_cos();
+        self.mul(cos)
+            .add(&axis.cross(self).mul(sin))
+            .add(&axis.mul(axis.dot(self) * (1.0 - cos)))
+    }
+
+    /// Returns two unit vectors that, together with this vector, form an orthonormal basis.
+    ///
+    /// The returned vectors are perpendicular to each other and to this vector, and
+    /// `(first, second, self)` is right handed. This uses the method from Duff et al.,
+    /// "Building an Orthonormal Basis, Revisited", which has no degenerate directions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let normal = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0).into_unit();
+    ///
+    /// let (tangent, bitangent) = normal.build_orthonormal_basis();
+    /// assert!(tangent.dot(&bitangent).abs() < 1e-10);
+    /// assert!(tangent.dot(&normal).abs() < 1e-10);
+    /// assert!(bitangent.dot(&normal).abs() < 1e-10);
+    /// assert!((tangent.length() - 1.0).abs() < 1e-10);
+    /// assert!((bitangent.length() - 1.0).abs() < 1e-10);
+    /// ```
+    pub fn build_orthonormal_basis(&self) -> (Vector3, Vector3) {
+        let n = self.into_unit();
+        let sign = 1.0f64.copysign(n.z);
+        let a = -1.0 / (sign + n.z);
+        let b = n.x * n.y * a;
+        (
+            Vector3::new(1.0 + sign * n.x * n.x * a, sign * b, -sign * n.x),
+            Vector3::new(b, sign + n.y * n.y * a, -n.y),
+        )
+    }
+
+    /// Returns a random point inside the unit sphere, taken from the given generator
+    ///
+    /// Points are drawn uniformly from the cube around the sphere until one lands inside it, so
+    /// every point inside the sphere is equally likely.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(7);
+    /// let samples: Vec<_> = (0..10000)
+    ///     .map(|_| ray_tracer::vector3d::Vector3::random_in_unit_sphere(&mut rng))
+    ///     .collect();
+    ///
+    /// assert!(samples.iter().all(|p| p.length() < 1.0));
+    ///
+    /// // The points are spread evenly around the origin
+    /// let mean = samples.iter().cloned().sum::<ray_tracer::vector3d::Vector3>().mul(1e-4);
+    /// assert!(mean.length() < 0.05);
+    ///
+    /// // Half of the sphere's volume is within 0.5^(1/3) of its center
+    /// let inner = samples.iter().filter(|p| p.length() < 0.5f64.cbrt()).count();
+    /// assert!((inner as f64 / 10000.0 - 0.5).abs() < 0.05);
+    /// ```
+    pub fn random_in_unit_sphere<R: Rng>(rng: &mut R) -> Vector3 {
+        loop {
+            let p = Vector3::new(
+                rng.gen_range(-1.0, 1.0),
+                rng.gen_range(-1.0, 1.0),
+                rng.gen_range(-1.0, 1.0),
+            );
+            if p.length_squared() < 1.0 {
+                return p;
+            }
+        }
+    }
+
+    /// Returns a random unit vector, taken from the given generator
+    ///
+    /// This projects a random point inside the unit sphere onto its surface, so every direction
+    /// is equally likely.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut rng = ray_tracer::random::RenderRng::seeded(7);
+    /// let samples: Vec<_> = (0..10000)
+    ///     .map(|_| ray_tracer::vector3d::Vector3::random_unit_vector(&mut rng))
+    ///     .collect();
+    ///
+    /// assert!(samples.iter().all(|v| (v.length() - 1.0).abs() < 1e-10));
+    ///
+    /// // The directions are spread evenly, with as many pointing up as down
+    /// let mean = samples.iter().cloned().sum::<ray_tracer::vector3d::Vector3>().mul(1e-4);
+    /// assert!(mean.length() < 0.05);
+    /// let up = samples.iter().filter(|v| v.y > 0.0).count();
+    /// assert!((up as f64 / 10000.0 - 0.5).abs() < 0.05);
+    /// ```
+    pub fn random_unit_vector<R: Rng>(rng: &mut R) -> Vector3 {
+        loop {
+            if let Some(unit) = Vector3::random_in_unit_sphere(rng).try_into_unit() {
+                return unit;
+            }
+        }
+    }
+}
+
+/// Creates a vector from an array of its x, y, and z components.
+///
+/// # Example
+///
+/// ```
+/// let my_vec: ray_tracer::vector3d::Vector3 = [1.0, 2.0, 3.0].into();
+///
+/// assert_eq!(my_vec, ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0));
+/// ```
+impl From<[f64; 3]> for Vector3 {
+    fn from(components: [f64; 3]) -> Vector3 {
+        Vector3::new(components[0], components[1], components[2])
+    }
+}
+
+/// Creates a vector from a tuple of its x, y, and z components.
+///
+/// # Example
+///
+/// ```
+/// let my_vec: ray_tracer::vector3d::Vector3 = (1.0, 2.0, 3.0).into();
+///
+/// assert_eq!(my_vec, ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0));
+/// ```
+impl From<(f64, f64, f64)> for Vector3 {
+    fn from((x, y, z): (f64, f64, f64)) -> Vector3 {
+        Vector3::new(x, y, z)
+    }
+}
+
+/// Adds the given vector to this vector in place.
+///
+/// # Example
+///
+/// ```
+/// let mut my_vec1 = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0);
+/// let my_vec2 = ray_tracer::vector3d::Vector3::new(3.0, 2.0, 1.0);
+/// let expected = my_vec1.add(&my_vec2);
+///
+/// my_vec1 += &my_vec2;
+/// assert_eq!(my_vec1, expected);
+///
+/// my_vec1 += my_vec2;
+/// assert_eq!(my_vec1, ray_tracer::vector3d::Vector3::new(7.0, 6.0, 5.0));
+/// ```
+impl AddAssign<&Vector3> for Vector3 {
+    fn add_assign(&mut self, other: &Vector3) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
+impl AddAssign for Vector3 {
+    fn add_assign(&mut self, other: Vector3) {
+        *self += &other;
+    }
+}
+
+/// Subtracts the given vector from this vector in place.
+///
+/// # Example
+///
+/// ```
+/// let mut my_vec1 = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0);
+/// let my_vec2 = ray_tracer::vector3d::Vector3::new(3.0, 2.0, 1.0);
+/// let expected = my_vec1.sub(&my_vec2);
+///
+/// my_vec1 -= &my_vec2;
+/// assert_eq!(my_vec1, expected);
+///
+/// my_vec1 -= my_vec2;
+/// assert_eq!(my_vec1, ray_tracer::vector3d::Vector3::new(-5.0, -2.0, 1.0));
+/// ```
+impl SubAssign<&Vector3> for Vector3 {
+    fn sub_assign(&mut self, other: &Vector3) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+
+impl SubAssign for Vector3 {
+    fn sub_assign(&mut self, other: Vector3) {
+        *self -= &other;
+    }
+}
+
+/// Multiplies this vector by the given scalar in place.
+///
+/// # Example
+///
+/// ```
+/// let mut my_vec = ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0);
+/// let expected = my_vec.mul(2.0);
+///
+/// my_vec *= 2.0;
+/// assert_eq!(my_vec, expected);
+/// ```
+impl MulAssign<f64> for Vector3 {
+    fn mul_assign(&mut self, other: f64) {
+        self.x *= other;
+        self.y *= other;
+        self.z *= other;
+    }
+}
+
+/// Sums an iterator of vectors, starting from the zero vector.
+///
+/// # Example
+///
+/// ```
+/// let vectors = vec![
+///     ray_tracer::vector3d::Vector3::new(1.0, 2.0, 3.0),
+///     ray_tracer::vector3d::Vector3::new(4.0, 5.0, 6.0),
+///     ray_tracer::vector3d::Vector3::new(7.0, 8.0, 9.0),
+/// ];
+/// let expected = vectors[0].add(&vectors[1]).add(&vectors[2]);
+///
+/// assert_eq!(vectors.iter().sum::<ray_tracer::vector3d::Vector3>(), expected);
+/// assert_eq!(vectors.into_iter().sum::<ray_tracer::vector3d::Vector3>(), expected);
+/// ```
+impl Sum for Vector3 {
+    fn sum<I: Iterator<Item = Vector3>>(iter: I) -> Vector3 {
+        iter.fold(Vector3::default(), |mut total, v| {
+            total += v;
+            total
+        })
+    }
+}
+
+impl<'a> Sum<&'a Vector3> for Vector3 {
+    fn sum<I: Iterator<Item = &'a Vector3>>(iter: I) -> Vector3 {
+        iter.fold(Vector3::default(), |mut total, v| {
+            total += v;
+            total
+        })
+    }
 }
@@ -0,0 +1,92 @@
+//! This module defines the settings of the window the ray tracer is shown in
+
+/// The struct holding the settings of the window
+///
+/// Contains the title of the window, its width and height in pixels, whether
+/// the user can resize it, and whether frames are only presented in time with
+/// the display's refresh.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowConfig {
+    pub title: String,
+    pub width: u32,
+    pub height: u32,
+    pub resizable: bool,
+    pub vsync: bool,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        WindowConfig {
+            title: "Ray Tracer".to_string(),
+            width: 800,
+            height: 600,
+            resizable: false,
+            vsync: false,
+        }
+    }
+}
+
+impl WindowConfig {
+    /// Creates new settings for a window of the given width and height, with the default title,
+    /// that cannot be resized and does not wait for vsync
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let config = ray_tracer::window::WindowConfig::new(640, 480);
+    ///
+    /// assert_eq!(config.title, "Ray Tracer");
+    /// assert_eq!((config.width, config.height), (640, 480));
+    /// assert!(!config.resizable);
+    /// assert!(!config.vsync);
+    ///
+    /// let default = ray_tracer::window::WindowConfig::default();
+    /// assert_eq!((default.width, default.height), (800, 600));
+    /// assert_eq!(config, ray_tracer::window::WindowConfig { width: 640, height: 480, ..default });
+    /// ```
+    pub fn new(width: u32, height: u32) -> WindowConfig {
+        WindowConfig {
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the aspect ratio (width / height) of the window
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let config = ray_tracer::window::WindowConfig::new(800, 400);
+    ///
+    /// assert_eq!(config.aspect(), 2.0);
+    /// ```
+    pub fn aspect(&self) -> f64 {
+        f64::from(self.width) / f64::from(self.height)
+    }
+
+    /// Sets the window's size to the given width and height, as reported by a resize event
+    ///
+    /// Each side is at least one pixel long, so that a window shrunk to nothing can still be
+    /// rendered to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut config = ray_tracer::window::WindowConfig::new(800, 600);
+    ///
+    /// config.resize(1024, 256);
+    /// assert_eq!((config.width, config.height), (1024, 256));
+    /// assert_eq!(config.aspect(), 4.0);
+    ///
+    /// config.resize(300, 0);
+    /// assert_eq!((config.width, config.height), (300, 1));
+    ///
+    /// config.resize(-5, 20);
+    /// assert_eq!((config.width, config.height), (1, 20));
+    /// ```
+    pub fn resize(&mut self, width: i32, height: i32) {
+        self.width = width.max(1) as u32;
+        self.height = height.max(1) as u32;
+    }
+}